@@ -0,0 +1,109 @@
+use validify::builder::{custom, email, is_in, iter, length, nested, range, required, Rules};
+use validify::{schema_err, schema_validation, Validate, ValidationError, ValidationErrors};
+
+const ROLES: &[&str] = &["admin", "user"];
+
+fn no_admin(name: &str) -> Result<(), ValidationError> {
+    if name == "admin" {
+        return Err(ValidationError::new_field("reserved"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Validate)]
+struct Address {
+    #[validate(length(min = 1))]
+    street: String,
+}
+
+#[derive(Debug, Validate)]
+#[validate(check_user)]
+struct User {
+    #[validate(length(min = 3, max = 8), custom(no_admin))]
+    name: String,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(range(min = 18., max = 150.))]
+    age: u8,
+    #[validate(is_in(ROLES))]
+    role: String,
+    #[validate(required)]
+    country: Option<String>,
+    #[validate(length(max = 2), iter(length(min = 1)))]
+    tags: Vec<String>,
+    #[validate]
+    addresses: Vec<Address>,
+}
+
+#[schema_validation]
+fn check_user(user: &User) -> Result<(), ValidationErrors> {
+    if user.role == "admin" && user.age < 21 {
+        schema_err!("young_admin");
+    }
+}
+
+fn user_rules() -> Rules<User> {
+    Rules::new()
+        .field(
+            "name",
+            |u: &User| u.name.as_str(),
+            (length().min(3).max(8), custom(no_admin)),
+        )
+        .optional("email", |u| u.email.as_ref(), [email()])
+        .field("age", |u| &u.age, [range().min(18.).max(150.)])
+        .field("role", |u| &u.role, [is_in(ROLES)])
+        .field("country", |u| &u.country, [required()])
+        .field(
+            "tags",
+            |u| &u.tags,
+            (length().max(2), iter([length().min(1)])),
+        )
+        .field("addresses", |u| &u.addresses, [nested()])
+        .schema(check_user)
+}
+
+#[test]
+fn builder_and_derived_errors_are_identical() {
+    let user = User {
+        name: "admin".to_string(),
+        email: Some("admin".to_string()),
+        age: 12,
+        role: "admin".to_string(),
+        country: None,
+        tags: vec!["a".to_string(), String::new(), String::new()],
+        addresses: vec![
+            Address {
+                street: "Main".to_string(),
+            },
+            Address {
+                street: String::new(),
+            },
+        ],
+    };
+
+    let derived = user.validate().unwrap_err();
+    let built = user_rules().validate(&user).unwrap_err();
+
+    assert_eq!(derived.errors().len(), 9);
+    assert_eq!(derived.errors(), built.errors());
+
+    let locations: Vec<_> = built.errors().iter().map(|e| e.location()).collect();
+    assert!(locations.contains(&"/tags/1"));
+    assert!(locations.contains(&"/addresses/1/street"));
+}
+
+#[test]
+fn builder_passes_valid_values() {
+    let user = User {
+        name: "alice".to_string(),
+        email: None,
+        age: 30,
+        role: "user".to_string(),
+        country: Some("NL".to_string()),
+        tags: vec!["a".to_string()],
+        addresses: vec![],
+    };
+
+    assert!(user.validate().is_ok());
+    assert!(user_rules().into_fn()(&user).is_ok());
+}
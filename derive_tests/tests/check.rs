@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use validify::rules::{Contains, Email, In, Length, Range, Time};
+use validify::{Validate, ValidationError, ValidationErrors};
+
+const ROLES: &[&str] = &["admin", "user"];
+
+#[derive(Debug, Validate)]
+struct Signup {
+    #[validate(length(min = 3, max = 8))]
+    name: String,
+    #[validate(email)]
+    email: String,
+    #[validate(range(min = 18., max = 150.))]
+    age: u8,
+    #[validate(contains(value = "@"))]
+    handle: String,
+    #[validate(is_in(ROLES))]
+    role: String,
+    #[validate(time(op = after, target = "2020-01-01", format = "%Y-%m-%d"))]
+    joined: NaiveDate,
+}
+
+/// Validates the struct by hand the way the derive does.
+fn validate_manually(signup: &Signup) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    let mut check = |field: &'static str, result: Result<(), ValidationError>| {
+        if let Err(mut err) = result {
+            err.set_field(field);
+            err.set_location(field);
+            errors.add(err);
+        }
+    };
+
+    check(
+        "name",
+        Length {
+            min: Some(3),
+            max: Some(8),
+            equal: None,
+        }
+        .check(&signup.name),
+    );
+    check("email", Email.check(&signup.email));
+    check(
+        "age",
+        Range {
+            min: Some(18.),
+            max: Some(150.),
+        }
+        .check(&signup.age),
+    );
+    check(
+        "handle",
+        Contains {
+            value: "@",
+            not: false,
+        }
+        .check(&signup.handle),
+    );
+    check(
+        "role",
+        In {
+            collection: &ROLES,
+            not: false,
+        }
+        .check(&signup.role.as_str()),
+    );
+    check(
+        "joined",
+        Time::After {
+            target: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            inclusive: false,
+        }
+        .check(&signup.joined),
+    );
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[test]
+fn manual_and_derived_errors_are_identical() {
+    let signup = Signup {
+        name: "al".to_string(),
+        email: "al".to_string(),
+        age: 12,
+        handle: "al".to_string(),
+        role: "root".to_string(),
+        joined: NaiveDate::from_ymd_opt(2019, 1, 1).unwrap(),
+    };
+
+    let derived = signup.validate().unwrap_err();
+    let manual = validate_manually(&signup).unwrap_err();
+
+    assert_eq!(derived.errors().len(), 6);
+    assert_eq!(derived.errors(), manual.errors());
+    assert_eq!(derived.errors()[5].params()["target"], "2020-01-01");
+}
@@ -0,0 +1,107 @@
+use serde_json::json;
+use validify::dynamic::Spec;
+use validify::Validate;
+
+#[derive(Debug, Validate, serde::Serialize)]
+struct Signup {
+    #[validate(length(min = 3, max = 8))]
+    name: String,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(range(min = 18., max = 150.))]
+    age: u8,
+    #[validate(time(op = after, target = "2020-01-01", format = "%Y-%m-%d"))]
+    joined: chrono::NaiveDate,
+    #[validate(iter(length(min = 1)))]
+    tags: Vec<String>,
+}
+
+const YAML: &str = r#"
+fields:
+  - field: name
+    rules:
+      - rule: length
+        min: 3
+        max: 8
+  - field: email
+    rules:
+      - rule: email
+  - field: age
+    rules:
+      - rule: range
+        min: 18
+        max: 150
+  - field: joined
+    rules:
+      - rule: time
+        op: after
+        target: "2020-01-01"
+  - field: tags
+    items:
+      rules:
+        - rule: length
+          min: 1
+"#;
+
+const TOML: &str = r#"
+[[fields]]
+field = "name"
+rules = [{ rule = "length", min = 3, max = 8 }]
+
+[[fields]]
+field = "email"
+rules = [{ rule = "email" }]
+
+[[fields]]
+field = "age"
+rules = [{ rule = "range", min = 18, max = 150 }]
+
+[[fields]]
+field = "joined"
+rules = [{ rule = "time", op = "after", target = "2020-01-01" }]
+
+[[fields]]
+field = "tags"
+items = { rules = [{ rule = "length", min = 1 }] }
+"#;
+
+fn invalid_signup() -> Signup {
+    Signup {
+        name: "al".to_string(),
+        email: Some("al".to_string()),
+        age: 12,
+        joined: chrono::NaiveDate::from_ymd_opt(2019, 1, 1).unwrap(),
+        tags: vec!["a".to_string(), String::new()],
+    }
+}
+
+#[test]
+fn yaml_spec_errors_match_derived() {
+    let signup = invalid_signup();
+    let schema = Spec::from_yaml(YAML).unwrap().compile().unwrap();
+
+    let derived = signup.validate().unwrap_err();
+    let dynamic = schema
+        .validate(&serde_json::to_value(&signup).unwrap())
+        .unwrap_err();
+
+    assert_eq!(derived.errors().len(), 5);
+    assert_eq!(derived.errors(), dynamic.errors());
+}
+
+#[test]
+fn toml_and_yaml_specs_are_equal() {
+    let yaml = Spec::from_yaml(YAML).unwrap();
+    let toml = Spec::from_toml(TOML).unwrap();
+    assert_eq!(yaml, toml);
+
+    let schema = toml.compile().unwrap();
+    let valid = json!({
+        "name": "alice",
+        "email": null,
+        "age": 30,
+        "joined": "2021-06-01",
+        "tags": ["a"]
+    });
+    assert!(schema.validate(&valid).is_ok());
+}
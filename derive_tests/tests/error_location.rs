@@ -122,9 +122,8 @@ fn with_complex_nesting() {
         "/second/invalid_grandchildren/2/obfuscated"
     );
 
-    // Indices in hashsets are unreliable
-    assert!(errors[6].location().contains("/second/more_invalid"));
-    assert!(errors[6].location().contains("head_count"));
+    // Hashsets of `Ord` elements are iterated in order
+    assert_eq!(errors[6].location(), "/second/more_invalid/0/head_count");
 
     #[derive(Debug, Validate, PartialEq, Eq)]
     struct Family {
@@ -160,7 +159,7 @@ fn with_complex_nesting() {
         extra_limbs: usize,
     }
 
-    #[derive(Debug, Validate, PartialEq, Eq, Hash)]
+    #[derive(Debug, Validate, PartialEq, Eq, Hash, PartialOrd, Ord)]
     struct SecondGrandChild {
         #[validate(range(min = 1.))]
         head_count: usize,
@@ -0,0 +1,183 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::marker::PhantomData;
+use validify::{Payload, Validate, ValidationErrors, Validify, ValidifyPayload};
+
+#[derive(Debug, Clone, Deserialize, Validify, Payload)]
+struct Item {
+    #[modify(trim)]
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validify, Payload)]
+struct Page<T> {
+    #[validify]
+    items: Vec<T>,
+    #[validate(range(min = 1.))]
+    page: u32,
+}
+
+#[derive(Debug, Validify)]
+enum Either<L, R> {
+    Left(#[validify] L),
+    Right {
+        #[validify]
+        right: Option<R>,
+    },
+}
+
+#[derive(Debug, Validate)]
+struct First<I: Iterator> {
+    #[validate]
+    first: Option<I::Item>,
+}
+
+/// Validates regardless of the marker, so the inferred `M: Validate` bound is too strict.
+#[derive(Debug)]
+struct Ids<M>(Vec<u32>, PhantomData<M>);
+
+impl<M> Validate for Ids<M> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if self.0.contains(&0) {
+            errors.add(validify::field_err!("zero_id"));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[derive(Debug, Validate)]
+#[validate(bound = "")]
+struct Tagged<M> {
+    #[validate]
+    ids: Ids<M>,
+}
+
+/// Only the bounds of the `Validate` impl are replaced, the others are still inferred.
+#[derive(Debug, Validify)]
+#[validate(bound(validate = "T: Validate"))]
+struct Batch<M, T> {
+    #[validate]
+    ids: Ids<M>,
+    #[validify]
+    items: Vec<T>,
+}
+
+/// Other serde arguments must not be mistaken for `bound`.
+#[derive(Debug, Clone, Deserialize, Validify, Payload)]
+#[serde(rename = "outbound")]
+struct Outbound<T> {
+    #[validify]
+    items: Vec<T>,
+}
+
+#[derive(Debug)]
+struct Marker;
+
+fn locations(errors: ValidationErrors) -> Vec<String> {
+    errors
+        .errors()
+        .iter()
+        .map(|e| e.location().to_string())
+        .collect()
+}
+
+#[test]
+fn infers_bounds_of_nested_type_params() {
+    let mut page = Page {
+        items: vec![
+            Item {
+                name: "  ok  ".to_string(),
+            },
+            Item {
+                name: "   ".to_string(),
+            },
+        ],
+        page: 0,
+    };
+
+    let errors = page.validify().unwrap_err();
+    assert_eq!(locations(errors), ["/items/1/name", "/page"]);
+    assert_eq!(page.items[0].name, "ok");
+}
+
+#[test]
+fn infers_bounds_in_generic_enums() {
+    let mut left = Either::<Item, Item>::Left(Item {
+        name: " ".to_string(),
+    });
+    assert_eq!(locations(left.validify().unwrap_err()), ["/0/name"]);
+
+    let mut right = Either::<Item, Item>::Right {
+        right: Some(Item {
+            name: " a ".to_string(),
+        }),
+    };
+    assert!(right.validify().is_ok());
+    let Either::Right { right: Some(item) } = right else {
+        panic!("Expected right")
+    };
+    assert_eq!(item.name, "a");
+}
+
+#[test]
+fn infers_bounds_of_associated_types() {
+    let first = First::<std::vec::IntoIter<Item>> {
+        first: Some(Item {
+            name: "".to_string(),
+        }),
+    };
+    assert_eq!(locations(first.validate().unwrap_err()), ["/first/name"]);
+}
+
+#[test]
+fn custom_bound_replaces_inferred() {
+    let tagged = Tagged::<Marker> {
+        ids: Ids(vec![1, 0], PhantomData),
+    };
+    let errors = tagged.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].code(), "zero_id");
+}
+
+#[test]
+fn custom_bound_of_single_impl() {
+    let mut batch = Batch::<Marker, Item> {
+        ids: Ids(vec![0], PhantomData),
+        items: vec![Item {
+            name: "  ".to_string(),
+        }],
+    };
+    assert_eq!(
+        locations(batch.validify().unwrap_err()),
+        ["/ids", "/items/0/name"]
+    );
+}
+
+#[test]
+fn generic_payload_with_serde_attributes() {
+    let json = json!({ "items": [{ "name": " a " }] });
+    let payload = serde_json::from_value::<OutboundPayload<Item>>(json).unwrap();
+    let outbound = Outbound::<Item>::validify_from(payload).unwrap();
+    assert_eq!(outbound.items[0].name, "a");
+}
+
+#[test]
+fn generic_payload() {
+    let json = json!({ "items": [{ "name": " a " }, { "name": null }], "page": 1 });
+    let payload = serde_json::from_value::<PagePayload<Item>>(json).unwrap();
+    let errors = Page::<Item>::validify_from(payload).unwrap_err();
+    assert_eq!(locations(errors), ["/items/1/name"]);
+
+    let json = json!({ "items": [{ "name": " a " }], "page": 1 });
+    let payload = serde_json::from_value::<PagePayload<Item>>(json).unwrap();
+    let page = Page::<Item>::validify_from(payload).unwrap();
+    assert_eq!(page.items[0].name, "a");
+
+    let payload = PagePayload::from(page);
+    assert_eq!(payload.items.unwrap()[0].name.as_deref(), Some("a"));
+}
@@ -0,0 +1,260 @@
+use validify::{field_err, Modify, ValidationErrors, Validify};
+
+#[derive(Debug, Validify)]
+#[modify(custom(fill_display_name), custom(default_currency))]
+#[validify(before_modify = count_before, after_validate = record_errors)]
+struct Account {
+    #[modify(trim)]
+    first_name: String,
+    #[modify(trim)]
+    last_name: String,
+    #[validate(length(min = 1))]
+    display_name: String,
+    #[modify(uppercase)]
+    country: String,
+    #[validate(length(equal = 3))]
+    currency: Option<String>,
+    log: Vec<String>,
+}
+
+fn fill_display_name(account: &mut Account) {
+    if account.display_name.is_empty() {
+        account.display_name = format!("{} {}", account.first_name, account.last_name)
+            .trim()
+            .to_string();
+    }
+}
+
+fn default_currency(account: &mut Account) {
+    if account.currency.is_none() {
+        account.currency = match account.country.as_str() {
+            "US" => Some("USD".to_string()),
+            "HR" => Some("EUR".to_string()),
+            _ => None,
+        };
+    }
+}
+
+fn count_before(account: &mut Account) {
+    // Field modifiers have not run yet
+    account.log.push(format!("before:{}", account.first_name));
+}
+
+fn record_errors(account: &mut Account, errors: &mut ValidationErrors) {
+    account.log.push(format!("after:{}", errors.errors().len()));
+    if account.country == "XX" {
+        errors.add(field_err!("unknown_country", "unknown country", "country"));
+    }
+}
+
+fn account(first_name: &str, last_name: &str, country: &str) -> Account {
+    Account {
+        first_name: first_name.to_string(),
+        last_name: last_name.to_string(),
+        display_name: String::new(),
+        country: country.to_string(),
+        currency: None,
+        log: vec![],
+    }
+}
+
+#[test]
+fn container_modifiers_run_after_field_modifiers() {
+    let mut acc = account("  John ", " Doe ", "us");
+
+    acc.modify();
+
+    assert_eq!(acc.display_name, "John Doe");
+    assert_eq!(acc.currency, Some("USD".to_string()));
+    // Hooks only run in validify
+    assert!(acc.log.is_empty());
+}
+
+#[test]
+fn lifecycle_hooks() {
+    let mut acc = account("  John ", " Doe ", "hr");
+
+    acc.validify().unwrap();
+
+    assert_eq!(acc.display_name, "John Doe");
+    assert_eq!(acc.currency, Some("EUR".to_string()));
+    assert_eq!(acc.log, vec!["before:  John ", "after:0"]);
+
+    let mut acc = account("", "", "xx");
+
+    let errors = acc.validify().unwrap_err();
+    let errors = errors.errors();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].location(), "/display_name");
+    assert_eq!(errors[1].code(), "unknown_country");
+    assert_eq!(errors[1].location(), "/country");
+    assert_eq!(acc.log, vec!["before:", "after:1"]);
+}
+
+#[derive(Debug, Validify)]
+#[modify(custom(normalize_payment))]
+#[validify(after_validate = reject_cash)]
+enum Payment {
+    Card {
+        #[modify(trim)]
+        #[validate(length(min = 1))]
+        holder: String,
+        number: String,
+    },
+    Cash(#[modify(uppercase)] String),
+}
+
+fn normalize_payment(payment: &mut Payment) {
+    if let Payment::Card { number, .. } = payment {
+        number.retain(|c| c.is_ascii_digit());
+    }
+}
+
+fn reject_cash(payment: &mut Payment, errors: &mut ValidationErrors) {
+    if let Payment::Cash(currency) = payment {
+        if currency != "EUR" {
+            errors.add(field_err!("currency", "unsupported currency", "0"));
+        }
+    }
+}
+
+#[test]
+fn hooks_in_enum() {
+    let mut payment = Payment::Card {
+        holder: " John ".to_string(),
+        number: "4111 1111-1111 1111".to_string(),
+    };
+
+    payment.validify().unwrap();
+
+    assert!(matches!(
+        payment,
+        Payment::Card { ref holder, ref number } if holder == "John" && number == "4111111111111111"
+    ));
+
+    let mut payment = Payment::Cash("eur".to_string());
+    payment.validify().unwrap();
+
+    let mut payment = Payment::Cash("usd".to_string());
+    let errors = payment.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/0");
+}
+
+fn hash_pw(password: &mut String) {
+    *password = format!("hashed:{}", password.len());
+}
+
+fn encrypt(secret: &mut String) -> Result<(), validify::ValidationError> {
+    if secret == "unencryptable" {
+        return Err(field_err!("encryption"));
+    }
+    *secret = secret.chars().rev().collect();
+    Ok(())
+}
+
+#[derive(Debug, Clone, serde::Deserialize, Validify, validify::Payload)]
+struct Credentials {
+    #[modify(trim)]
+    #[modify(after_validate, custom(hash_pw))]
+    #[validate(length(min = 8))]
+    password: String,
+    #[modify(after_validate, try_custom(encrypt))]
+    secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Validify)]
+struct Registration {
+    #[validify]
+    credentials: Credentials,
+    #[validify]
+    previous: Vec<Credentials>,
+}
+
+#[test]
+fn after_validate_modifiers() {
+    let mut creds = Credentials {
+        password: "  supersecret  ".to_string(),
+        secret: Some("abc".to_string()),
+    };
+
+    // Plain modify does not run the post validation phase
+    creds.modify();
+    assert_eq!(creds.password, "supersecret");
+
+    creds.validify().unwrap();
+    assert_eq!(creds.password, "hashed:11");
+    assert_eq!(creds.secret, Some("cba".to_string()));
+
+    let mut creds = Credentials {
+        password: "  short  ".to_string(),
+        secret: Some("abc".to_string()),
+    };
+
+    let errors = creds.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(creds.password, "short");
+    assert_eq!(creds.secret, Some("abc".to_string()));
+
+    let mut creds = Credentials {
+        password: "supersecret".to_string(),
+        secret: Some("unencryptable".to_string()),
+    };
+
+    let errors = creds.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/secret");
+}
+
+#[test]
+fn after_validate_modifiers_nested() {
+    let registration = Registration {
+        credentials: Credentials {
+            password: "supersecret".to_string(),
+            secret: None,
+        },
+        previous: vec![Credentials {
+            password: "oldsecret".to_string(),
+            secret: Some("unencryptable".to_string()),
+        }],
+    };
+
+    // The phase is not transactional, modifiers which do not fail are still applied
+    let mut partial = registration.clone();
+    let errors = partial.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/previous/0/secret");
+    assert_eq!(partial.credentials.password, "hashed:11");
+    assert_eq!(partial.previous[0].password, "hashed:9");
+    assert_eq!(
+        partial.previous[0].secret,
+        Some("unencryptable".to_string())
+    );
+
+    // Unless the value is only replaced on success
+    assert!(registration.validified().is_err());
+    assert_eq!(registration.credentials.password, "supersecret");
+    assert_eq!(registration.previous[0].password, "oldsecret");
+
+    let mut registration = registration;
+    registration.previous[0].secret = Some("abc".to_string());
+
+    registration.validify().unwrap();
+    assert_eq!(registration.credentials.password, "hashed:11");
+    assert_eq!(registration.previous[0].password, "hashed:9");
+    assert_eq!(registration.previous[0].secret, Some("cba".to_string()));
+}
+
+#[test]
+fn validify_from_returns_transformed_original() {
+    use validify::ValidifyPayload;
+
+    let payload = CredentialsPayload {
+        password: Some(" supersecret ".to_string()),
+        secret: None,
+    };
+
+    let creds = Credentials::validify_from(payload).unwrap();
+    assert_eq!(creds.password, "hashed:11");
+}
@@ -0,0 +1,200 @@
+use serde::Deserialize;
+use validify::{RuleParam, TypeKind, Validate, ValidatedNewtype};
+
+const MAX_NAME: u64 = 32;
+const ROLES: &[&str] = &["admin", "user"];
+
+fn not_reserved(_name: &str) -> Result<(), validify::ValidationError> {
+    Ok(())
+}
+
+validify::rule_set!(pub Tag = [length(min = 1, max = 8)]);
+
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    #[validate(length(min = 1, max = MAX_NAME), custom(not_reserved))]
+    user_name: String,
+
+    #[serde(rename = "mail")]
+    #[validate(email(code = "mail", message = "Invalid mail"))]
+    email: Option<String>,
+
+    #[validate(range(min = 18., max = 150.))]
+    age: u8,
+
+    #[validate(is_in(ROLES))]
+    role: String,
+
+    #[validate(contains_not(value = "x"))]
+    handle: String,
+
+    #[validate(time(op = after, target = "2020-01-01", format = "%Y-%m-%d"))]
+    birthday: chrono::NaiveDate,
+
+    #[validate(length(max = 4), iter(rules(Tag)))]
+    tags: Vec<String>,
+
+    #[validate]
+    addresses: Vec<Address>,
+
+    #[validate(max_depth = 2)]
+    manager: Option<Box<User>>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Validate, Deserialize)]
+struct Address {
+    #[validate(length(min = 1))]
+    street: String,
+    zip: u32,
+}
+
+#[derive(Debug, Validate)]
+struct Page<T> {
+    #[validate]
+    items: Vec<T>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Validate)]
+enum Contact {
+    Phone(#[validate(phone)] String),
+    Email {
+        #[validate(email)]
+        address: String,
+    },
+}
+
+#[derive(Debug, ValidatedNewtype)]
+struct Zip(#[validate(length(equal = 5))] String);
+
+#[derive(Debug, Validate)]
+struct Order {
+    #[validate]
+    zip: Zip,
+}
+
+#[test]
+fn describes_fields() {
+    let rules = User::rules();
+    let names: Vec<_> = rules.iter().map(|field| field.name).collect();
+    assert_eq!(
+        names,
+        [
+            "userName",
+            "mail",
+            "age",
+            "role",
+            "handle",
+            "birthday",
+            "tags",
+            "addresses",
+            "manager"
+        ]
+    );
+
+    let name = &rules[0];
+    assert_eq!(name.kind, TypeKind::String);
+    assert!(!name.optional);
+    assert!(name.nested.is_none());
+
+    let length = name.rule("length").unwrap();
+    assert_eq!(length.code, "length");
+    assert_eq!(length.param("min"), Some(&RuleParam::Number(1.)));
+    assert_eq!(length.param("max"), Some(&RuleParam::Expr("MAX_NAME")));
+    assert_eq!(length.param("equal"), None);
+    assert_eq!(
+        name.rule("custom").unwrap().param("function"),
+        Some(&RuleParam::Expr("not_reserved"))
+    );
+
+    let email = &rules[1];
+    assert!(email.optional);
+    assert_eq!(email.kind, TypeKind::String);
+    assert_eq!(email.rules[0].name, "email");
+    assert_eq!(email.rules[0].code, "mail");
+    assert_eq!(email.rules[0].message, Some("Invalid mail"));
+
+    let age = &rules[2];
+    assert_eq!(age.kind, TypeKind::Integer);
+    assert_eq!(age.rules[0].param("min").unwrap().as_number(), Some(18.));
+
+    assert_eq!(
+        rules[3].rules[0].param("collection"),
+        Some(&RuleParam::Expr("ROLES"))
+    );
+
+    let contains = &rules[4].rules[0];
+    assert_eq!(contains.name, "contains_not");
+    assert_eq!(contains.param("value").unwrap().as_str(), Some("x"));
+
+    let birthday = &rules[5];
+    assert_eq!(birthday.kind, TypeKind::Date);
+    let time = &birthday.rules[0];
+    assert_eq!(time.name, "time");
+    assert_eq!(time.code, "after");
+    assert_eq!(time.param("op").unwrap().as_str(), Some("after"));
+    assert_eq!(time.param("target").unwrap().as_str(), Some("2020-01-01"));
+    assert_eq!(time.param("inclusive"), Some(&RuleParam::Bool(false)));
+}
+
+#[test]
+fn describes_collections_and_rule_sets() {
+    let tags = &User::rules()[6];
+    assert_eq!(tags.kind, TypeKind::List);
+    assert_eq!(tags.rules.len(), 2);
+
+    let iter = tags.rule("iter").unwrap();
+    let rule_set = &iter.rules[0];
+    assert_eq!(rule_set.name, "rules");
+    assert_eq!(rule_set.param("rule_set"), Some(&RuleParam::Expr("Tag")));
+    assert_eq!(rule_set.rules, Tag::RULES);
+    assert_eq!(rule_set.rules[0].param("max"), Some(&RuleParam::Number(8.)));
+}
+
+#[test]
+fn describes_nested_types() {
+    let rules = User::rules();
+
+    let addresses = &rules[7];
+    assert!(addresses.rules.is_empty());
+    let address = addresses.nested_rules();
+    assert_eq!(address.len(), 2);
+    assert_eq!(address[0].name, "street");
+    assert_eq!(address[1].kind, TypeKind::Integer);
+    assert!(address[1].rules.is_empty());
+
+    // Recursive types are described lazily
+    let manager = &rules[8];
+    assert!(manager.optional);
+    assert_eq!(manager.kind, TypeKind::Other);
+    assert_eq!(manager.rules[0].param("max"), Some(&RuleParam::Number(2.)));
+    assert_eq!(manager.nested_rules()[8].nested_rules()[0].name, "userName");
+
+    let page = Page::<Address>::rules();
+    assert_eq!(page[0].nested_rules()[0].name, "street");
+    assert!(Page::<Page<Address>>::rules()[0].nested_rules()[0]
+        .nested_rules()
+        .iter()
+        .any(|field| field.name == "zip"));
+}
+
+#[test]
+fn describes_enums_and_newtypes() {
+    let rules = Contact::rules();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].variant, Some("Phone"));
+    assert_eq!(rules[0].name, "0");
+    assert_eq!(rules[0].rules[0].name, "phone");
+    assert_eq!(rules[1].variant, Some("Email"));
+    assert_eq!(rules[1].name, "address");
+
+    let zip = Order::rules()[0].nested_rules();
+    assert_eq!(zip.len(), 1);
+    assert_eq!(zip[0].name, "");
+    assert_eq!(
+        zip[0].rule("length").unwrap().param("equal"),
+        Some(&RuleParam::Number(5.))
+    );
+}
@@ -1,5 +1,7 @@
 use chrono::NaiveDate;
 use regex::Regex;
+use std::borrow::Cow;
+use std::rc::Rc;
 use validify::{field_err, Validate, ValidationError};
 
 #[test]
@@ -383,3 +385,46 @@ fn iter_works_with_option() {
     assert_eq!("/test/0", error.location());
     assert_eq!("email", error.code());
 }
+
+#[test]
+fn nested_iter_works() {
+    #[derive(Debug, Validate)]
+    struct IterTest<'a> {
+        #[validate(iter(iter(length(min = 2))))]
+        matrix: Vec<Vec<String>>,
+        #[validate(iter(email))]
+        emails: Option<Vec<Option<String>>>,
+        #[validate(iter(length(min = 2)), length(max = 2))]
+        boxed: Rc<[Box<str>]>,
+        #[validate(iter(length(min = 2)))]
+        cows: Vec<Cow<'a, str>>,
+    }
+
+    let test = IterTest {
+        matrix: vec![
+            vec!["ab".to_string()],
+            vec!["ab".to_string(), "a".to_string()],
+        ],
+        emails: Some(vec![None, Some("nope".to_string())]),
+        boxed: Rc::from(vec!["ab".into(), "a".into(), "abc".into()]),
+        cows: vec![Cow::Borrowed("a"), Cow::Owned("ab".to_string())],
+    };
+
+    let errors = test.validate().unwrap_err();
+    let errors: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|e| (e.location(), e.code()))
+        .collect();
+
+    assert_eq!(
+        errors,
+        vec![
+            ("/matrix/1/1", "length".to_string()),
+            ("/emails/1", "email".to_string()),
+            ("/boxed/1", "length".to_string()),
+            ("/boxed", "length".to_string()),
+            ("/cows/0", "length".to_string()),
+        ]
+    );
+}
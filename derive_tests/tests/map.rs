@@ -0,0 +1,278 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use validify::{Modify, Validate, Validify};
+
+#[test]
+fn map_keys_and_values_validation() {
+    #[derive(Debug, Validate)]
+    struct Labels {
+        #[validate(keys(length(max = 5), non_control_char), values(length(min = 1)))]
+        labels: BTreeMap<String, String>,
+        #[validate(values(range(min = 0.)))]
+        weights: Option<HashMap<String, f64>>,
+    }
+
+    let labels = Labels {
+        labels: BTreeMap::from([
+            ("env".to_string(), "prod".to_string()),
+            ("team".to_string(), "core".to_string()),
+        ]),
+        weights: Some(HashMap::from([("a".to_string(), 1.)])),
+    };
+
+    assert!(labels.validate().is_ok());
+
+    let labels = Labels {
+        labels: BTreeMap::from([
+            ("env".to_string(), "".to_string()),
+            ("too_long".to_string(), "core".to_string()),
+        ]),
+        weights: Some(HashMap::from([("neg".to_string(), -1.)])),
+    };
+
+    let errors = labels.validate().unwrap_err();
+    let errors = errors.errors();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].code(), "length");
+    assert_eq!(errors[0].location(), "/labels/too_long");
+    assert_eq!(errors[1].code(), "length");
+    assert_eq!(errors[1].location(), "/labels/env");
+    assert_eq!(errors[2].code(), "range");
+    assert_eq!(errors[2].location(), "/weights/neg");
+}
+
+#[test]
+fn map_keys_and_values_modification() {
+    #[derive(Debug, Serialize, Validify)]
+    struct Labels {
+        #[modify(keys(trim, lowercase), values(trim))]
+        #[validate(keys(length(max = 5)))]
+        labels: HashMap<String, String>,
+        #[modify(values(clamp(min = 0, max = 10)))]
+        counts: Option<IndexMap<String, i32>>,
+    }
+
+    let mut labels = Labels {
+        labels: HashMap::from([
+            (" Env ".to_string(), " prod ".to_string()),
+            ("TEAM".to_string(), "core".to_string()),
+        ]),
+        counts: Some(IndexMap::from([
+            ("a".to_string(), -5),
+            ("b".to_string(), 5),
+            ("c".to_string(), 50),
+        ])),
+    };
+
+    let report = labels.modify_with_report();
+
+    assert_eq!(labels.labels["env"], "prod");
+    assert_eq!(labels.labels["team"], "core");
+    assert_eq!(
+        labels.counts,
+        Some(IndexMap::from([
+            ("a".to_string(), 0),
+            ("b".to_string(), 5),
+            ("c".to_string(), 10),
+        ]))
+    );
+
+    let mut changes: Vec<_> = report
+        .modifications()
+        .iter()
+        .map(|m| (m.location().to_string(), m.modifier()))
+        .collect();
+    changes.sort();
+
+    assert_eq!(
+        changes,
+        vec![
+            ("/counts/a".to_string(), "clamp"),
+            ("/counts/c".to_string(), "clamp"),
+            ("/labels".to_string(), "lowercase"),
+            ("/labels".to_string(), "trim"),
+            ("/labels/env".to_string(), "trim"),
+        ]
+    );
+
+    let counts = report
+        .modifications()
+        .iter()
+        .find(|m| m.location() == "/counts/c")
+        .unwrap();
+    assert_eq!(counts.before(), Some(&json!(50)));
+    assert_eq!(counts.after(), Some(&json!(10)));
+
+    let mut labels = Labels {
+        labels: HashMap::from([(" Environment ".to_string(), "prod".to_string())]),
+        counts: None,
+    };
+
+    let errors = labels.validify().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/labels/environment");
+}
+
+#[test]
+fn map_key_modifier_collisions() {
+    #[derive(Debug, Validify)]
+    struct Labels {
+        #[modify(keys(trim, lowercase))]
+        #[validate(values(range(min = 0.)))]
+        labels: BTreeMap<String, i32>,
+    }
+
+    let mut labels = Labels {
+        labels: BTreeMap::from([
+            ("A".to_string(), 1),
+            ("a".to_string(), 2),
+            (" a/b".to_string(), 3),
+            ("a/b".to_string(), 4),
+            ("c".to_string(), -1),
+        ]),
+    };
+
+    let errors = labels.validify().unwrap_err();
+    let errors = errors.errors();
+
+    // Colliding keys are not overwritten, the remaining values are still validated
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].code(), "key_collision");
+    assert_eq!(errors[0].field_name(), Some("labels"));
+    assert_eq!(errors[0].location(), "/labels/a~1b");
+    assert_eq!(errors[1].code(), "key_collision");
+    assert_eq!(errors[1].location(), "/labels/a");
+    assert_eq!(errors[2].code(), "range");
+    assert_eq!(errors[2].location(), "/labels/c");
+    assert_eq!(
+        labels.labels,
+        BTreeMap::from([
+            ("a".to_string(), 1),
+            ("a/b".to_string(), 3),
+            ("c".to_string(), -1),
+        ])
+    );
+}
+
+#[test]
+fn map_modifiers_in_enum() {
+    #[derive(Debug, Validify)]
+    enum Meta {
+        Labels(#[modify(keys(uppercase))] BTreeMap<String, u8>),
+    }
+
+    let mut meta = Meta::Labels(BTreeMap::from([("a".to_string(), 1)]));
+    meta.modify();
+
+    let Meta::Labels(labels) = meta;
+    assert_eq!(labels, BTreeMap::from([("A".to_string(), 1)]));
+}
+
+#[test]
+fn nested_map_values_location() {
+    #[derive(Debug, Clone, Validify)]
+    struct Settings {
+        #[validify]
+        settings: HashMap<String, Setting>,
+        #[validate]
+        optional: BTreeMap<&'static str, Option<Setting>>,
+        #[validate]
+        lists: Option<HashMap<String, Vec<Setting>>>,
+    }
+
+    #[derive(Debug, Clone, Validify)]
+    struct Setting {
+        #[modify(trim)]
+        #[validate(length(min = 1))]
+        color: String,
+    }
+
+    let setting = |color: &str| Setting {
+        color: color.to_string(),
+    };
+
+    let mut settings = Settings {
+        settings: HashMap::from([
+            ("theme".to_string(), setting("  ")),
+            ("font".to_string(), setting(" ")),
+            ("a/b~c".to_string(), setting("")),
+            ("layout".to_string(), setting(" red ")),
+        ]),
+        optional: BTreeMap::from([("none", None), ("some", Some(setting("")))]),
+        lists: Some(HashMap::from([(
+            "list".to_string(),
+            vec![setting("red"), setting("")],
+        )])),
+    };
+
+    let errors = settings.validify().unwrap_err();
+    let locations: Vec<_> = errors.errors().iter().map(|e| e.location()).collect();
+
+    // Hashmaps with `Ord` keys are iterated in order
+    assert_eq!(
+        locations,
+        vec![
+            "/settings/a~1b~0c/color",
+            "/settings/font/color",
+            "/settings/theme/color",
+            "/optional/some/color",
+            "/lists/list/1/color",
+        ]
+    );
+    assert_eq!(settings.settings["layout"].color, "red");
+
+    let report = settings.clone().modify_with_report();
+    assert!(report.is_empty());
+
+    let mut settings = Settings {
+        settings: HashMap::from([
+            ("b".to_string(), setting(" b")),
+            ("a".to_string(), setting(" a")),
+        ]),
+        optional: BTreeMap::new(),
+        lists: None,
+    };
+
+    let report = settings.modify_with_report();
+    let locations: Vec<_> = report
+        .modifications()
+        .iter()
+        .map(|m| m.location())
+        .collect();
+    assert_eq!(locations, vec!["/settings/a/color", "/settings/b/color"]);
+}
+
+#[test]
+fn map_keys_are_escaped_and_ordered() {
+    #[derive(Debug, Validate)]
+    struct Paths {
+        #[validate(keys(length(max = 3)), values(range(min = 0.)))]
+        paths: HashMap<String, i32>,
+    }
+
+    let paths = Paths {
+        paths: HashMap::from([
+            ("/root".to_string(), -1),
+            ("~".to_string(), -1),
+            ("/".to_string(), 1),
+        ]),
+    };
+
+    let errors = paths.validate().unwrap_err();
+    let locations: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|e| (e.location(), e.code()))
+        .collect();
+
+    assert_eq!(
+        locations,
+        vec![
+            ("/paths/~1root", "length".to_string()),
+            ("/paths/~1root", "range".to_string()),
+            ("/paths/~0", "range".to_string()),
+        ]
+    );
+}
@@ -1,5 +1,6 @@
 use chrono::NaiveDate;
 use serde::Deserialize;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use validify::{Modify, Payload, Validify, ValidifyPayload};
 
@@ -281,3 +282,604 @@ fn trim() {
     assert!(matches!(second.c, Some(a) if a == "WORKS"));
     assert_eq!(second.d, "WORKS");
 }
+
+#[test]
+fn case_modifiers() {
+    #[derive(Debug, Validify)]
+    struct Cases {
+        #[modify(snake_case)]
+        a: String,
+        #[modify(kebab_case)]
+        b: String,
+        #[modify(camel_case)]
+        c: String,
+        #[modify(pascal_case)]
+        d: String,
+        #[modify(screaming_snake_case)]
+        e: String,
+        #[modify(trim, snake_case)]
+        f: Option<String>,
+        #[modify(kebab_case)]
+        g: Vec<String>,
+    }
+
+    let mut cases = Cases {
+        a: "someFieldName".to_string(),
+        b: "Some Field Name".to_string(),
+        c: "some_field_name".to_string(),
+        d: "some-field-name".to_string(),
+        e: "someFieldName".to_string(),
+        f: Some("  HTTPServer  ".to_string()),
+        g: vec!["FooBar".to_string(), "baz_qux".to_string()],
+    };
+
+    cases.modify();
+
+    assert_eq!(cases.a, "some_field_name");
+    assert_eq!(cases.b, "some-field-name");
+    assert_eq!(cases.c, "someFieldName");
+    assert_eq!(cases.d, "SomeFieldName");
+    assert_eq!(cases.e, "SOME_FIELD_NAME");
+    assert_eq!(cases.f, Some("http_server".to_string()));
+    assert_eq!(cases.g, vec!["foo-bar".to_string(), "baz-qux".to_string()]);
+}
+
+#[test]
+fn slugify_modifier() {
+    #[derive(Debug, Validify)]
+    struct Slugs {
+        #[modify(slugify)]
+        #[validate(length(max = 32))]
+        a: String,
+        #[modify(slugify(separator = "_", max_len = 10))]
+        b: String,
+        #[modify(slugify)]
+        c: Option<String>,
+        #[modify(slugify(max_len = 5))]
+        d: Vec<String>,
+    }
+
+    let mut slugs = Slugs {
+        a: "  Ünïcödé Title: Part 2! ".to_string(),
+        b: "Hello beautiful world".to_string(),
+        c: Some("Čevapi & Pljeskavica".to_string()),
+        d: vec!["Hello World".to_string()],
+    };
+
+    slugs.validify().unwrap();
+
+    assert_eq!(slugs.a, "unicode-title-part-2");
+    assert_eq!(slugs.b, "hello_beau");
+    assert_eq!(slugs.c, Some("cevapi-pljeskavica".to_string()));
+    assert_eq!(slugs.d, vec!["hello".to_string()]);
+}
+
+#[test]
+fn case_modifiers_in_enum() {
+    #[derive(Debug, Validify)]
+    enum Tag {
+        Named {
+            #[modify(pascal_case)]
+            name: String,
+        },
+        Unnamed(#[modify(slugify)] String),
+    }
+
+    let mut named = Tag::Named {
+        name: "user account".to_string(),
+    };
+    named.modify();
+    assert!(matches!(named, Tag::Named { ref name } if name == "UserAccount"));
+
+    let mut unnamed = Tag::Unnamed("User Account".to_string());
+    unnamed.modify();
+    assert!(matches!(unnamed, Tag::Unnamed(ref slug) if slug == "user-account"));
+}
+
+#[test]
+fn numeric_modifiers() {
+    const MAX: i32 = 100;
+
+    #[derive(Debug, Validify)]
+    struct Numbers {
+        #[modify(clamp(min = 0, max = MAX))]
+        #[validate(range(min = 0., max = 100.))]
+        a: i32,
+        #[modify(clamp(min = -1.5, max = 1.5))]
+        b: f64,
+        #[modify(round(2))]
+        c: f64,
+        #[modify(round(decimals = 1))]
+        d: Option<f32>,
+        #[modify(floor)]
+        e: f64,
+        #[modify(ceil)]
+        f: Vec<f64>,
+        #[modify(abs, clamp(max = 10))]
+        g: Option<i64>,
+        #[modify(abs)]
+        h: Vec<i8>,
+        #[modify(round)]
+        i: u32,
+        #[modify(clamp(min = 0, max = 1))]
+        j: Vec<f32>,
+    }
+
+    let mut numbers = Numbers {
+        a: 420,
+        b: -3.,
+        c: 1.23456,
+        d: Some(2.75),
+        e: 2.9,
+        f: vec![1.1, -1.1, 2.],
+        g: Some(-42),
+        h: vec![-1, 1, i8::MIN],
+        i: 7,
+        j: vec![-2.5, 0.5, 3.],
+    };
+
+    numbers.validify().unwrap();
+
+    assert_eq!(numbers.a, 100);
+    assert_eq!(numbers.b, -1.5);
+    assert_eq!(numbers.c, 1.23);
+    assert_eq!(numbers.d, Some(2.8));
+    assert_eq!(numbers.e, 2.);
+    assert_eq!(numbers.f, vec![2., -1., 2.]);
+    assert_eq!(numbers.g, Some(10));
+    assert_eq!(numbers.h, vec![1, 1, i8::MAX]);
+    assert_eq!(numbers.i, 7);
+    assert_eq!(numbers.j, vec![0., 0.5, 1.]);
+}
+
+#[test]
+fn temporal_modifiers() {
+    use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+
+    fn target() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    #[derive(Debug, Validify)]
+    struct Times {
+        // Would fail without truncation since the time is after the target
+        #[modify(truncate(days))]
+        #[validate(time(op = before, target = target, inclusive = true, time = true))]
+        a: NaiveDateTime,
+        #[modify(truncate(minutes))]
+        b: Option<NaiveDateTime>,
+        #[modify(truncate(seconds))]
+        c: Vec<DateTime<Utc>>,
+        #[modify(to_utc)]
+        d: DateTime<FixedOffset>,
+        #[modify(to_utc, truncate(hours))]
+        e: Option<DateTime<FixedOffset>>,
+    }
+
+    let time = NaiveDate::from_ymd_opt(2024, 3, 15)
+        .unwrap()
+        .and_hms_milli_opt(13, 45, 30, 123)
+        .unwrap();
+    let offset = DateTime::parse_from_rfc3339("2024-03-15T13:45:30+02:00").unwrap();
+
+    let mut times = Times {
+        a: time,
+        b: Some(time),
+        c: vec![time.and_utc()],
+        d: offset,
+        e: Some(offset),
+    };
+
+    times.validify().unwrap();
+
+    assert_eq!(times.a, target());
+    assert_eq!(
+        times.b.unwrap().to_string(),
+        "2024-03-15 13:45:00".to_string()
+    );
+    assert_eq!(times.c[0].to_rfc3339(), "2024-03-15T13:45:30+00:00");
+    assert_eq!(times.d.to_rfc3339(), "2024-03-15T11:45:30+00:00");
+    assert_eq!(times.e.unwrap().to_rfc3339(), "2024-03-15T11:00:00+00:00");
+}
+
+#[test]
+fn collection_modifiers() {
+    use std::collections::VecDeque;
+
+    #[derive(Debug, Validify)]
+    struct Collections {
+        // Modifiers run in the order they are specified, element modifiers included
+        #[modify(trim, lowercase, retain_non_empty, sort, dedup)]
+        #[validate(length(max = 3))]
+        tags: Vec<String>,
+        #[modify(dedup, truncate(max = 2))]
+        ids: Option<Vec<u64>>,
+        #[modify(sort_by_key = String::len)]
+        words: Vec<String>,
+        #[modify(trim, retain_non_empty, sort, truncate(max = 2))]
+        queue: VecDeque<String>,
+        // Truncates each element instead of the list
+        #[modify(trim, iter(truncate(max = 3, ellipsis = ".")), truncate(max = 2))]
+        labels: Option<Vec<String>>,
+        #[modify(iter(sort, dedup))]
+        groups: Vec<Vec<i32>>,
+    }
+
+    let mut collections = Collections {
+        tags: vec![
+            "  Rust ".to_string(),
+            "   ".to_string(),
+            "api".to_string(),
+            "RUST".to_string(),
+            "".to_string(),
+            "Web".to_string(),
+        ],
+        ids: Some(vec![3, 1, 3, 2, 1]),
+        words: vec!["ccc".to_string(), "a".to_string(), "bb".to_string()],
+        queue: VecDeque::from(vec![
+            " c ".to_string(),
+            " ".to_string(),
+            "a".to_string(),
+            "b".to_string(),
+        ]),
+        labels: Some(vec![
+            " abcdef ".to_string(),
+            "ab".to_string(),
+            "c".to_string(),
+        ]),
+        groups: vec![vec![2, 1, 2], vec![], vec![3, 3]],
+    };
+
+    collections.validify().unwrap();
+
+    assert_eq!(collections.tags, vec!["api", "rust", "web"]);
+    assert_eq!(collections.ids, Some(vec![3, 1]));
+    assert_eq!(collections.words, vec!["a", "bb", "ccc"]);
+    assert_eq!(
+        collections.queue,
+        VecDeque::from(vec!["a".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+        collections.labels,
+        Some(vec!["ab.".to_string(), "ab".to_string()])
+    );
+    assert_eq!(collections.groups, vec![vec![1, 2], vec![], vec![3]]);
+}
+
+#[test]
+fn truncate_string_modifier() {
+    const MAX: usize = 8;
+
+    #[derive(Debug, Validify)]
+    struct Text {
+        #[modify(truncate(max = MAX, ellipsis = "…"))]
+        #[validate(length(max = 8))]
+        a: String,
+        #[modify(truncate(max = 7, unit = bytes))]
+        b: String,
+        #[modify(trim, truncate(max = 2, unit = graphemes, ellipsis = "."))]
+        c: Option<String>,
+        #[modify(truncate(max = 3))]
+        d: String,
+    }
+
+    let mut text = Text {
+        a: "Hello beautiful world".to_string(),
+        b: "日本語".to_string(),
+        c: Some("  e\u{301}e\u{301}e\u{301}  ".to_string()),
+        d: "Hello".to_string(),
+    };
+
+    text.validify().unwrap();
+
+    assert_eq!(text.a, "Hello b…");
+    assert_eq!(text.b, "日本");
+    assert_eq!(text.c, Some("e\u{301}.".to_string()));
+    assert_eq!(text.d, "Hel");
+}
+
+#[test]
+fn html_modifiers() {
+    #[derive(Debug, Validify)]
+    struct Comment {
+        #[modify(sanitize_html)]
+        body: String,
+        #[modify(sanitize_html(tags("b", "a"), attributes("href")))]
+        bio: Option<String>,
+        #[modify(strip_html, trim)]
+        title: String,
+        #[modify(strip_html)]
+        tags: Vec<String>,
+    }
+
+    let mut comment = Comment {
+        body: "<p onclick=\"evil()\">Hi<script>alert(1)</script></p>".to_string(),
+        bio: Some(
+            "<b class=\"x\">Me</b> <a href=\"https://example.com\" id=\"y\">site</a> <i>!</i>"
+                .to_string(),
+        ),
+        title: " <h1>Hello &amp; welcome</h1> ".to_string(),
+        tags: vec!["<em>rust</em>".to_string(), "web".to_string()],
+    };
+
+    comment.validify().unwrap();
+
+    assert_eq!(comment.body, "<p>Hi</p>");
+    assert_eq!(
+        comment.bio,
+        Some(
+            "<b>Me</b> <a href=\"https://example.com\" rel=\"noopener noreferrer\">site</a> !"
+                .to_string()
+        )
+    );
+    assert_eq!(comment.title, "Hello &amp; welcome");
+    assert_eq!(comment.tags, vec!["rust", "web"]);
+}
+
+#[test]
+fn sanitize_html_allows_rel_and_clean_content_tags() {
+    #[derive(Debug, Validify)]
+    struct Page {
+        #[modify(sanitize_html(tags("a"), attributes("href", "rel")))]
+        link: String,
+        #[modify(sanitize_html(tags("style", "p")))]
+        styled: String,
+        #[modify(sanitize_html(tags("script")))]
+        script: String,
+    }
+
+    let mut page = Page {
+        link: "<a href=\"/x\" rel=\"nofollow\">x</a>".to_string(),
+        styled: "<style>p { color: red }</style><p>Hi</p>".to_string(),
+        script: "<script>run()</script>".to_string(),
+    };
+
+    page.validify().unwrap();
+
+    assert_eq!(page.link, "<a href=\"/x\" rel=\"nofollow\">x</a>");
+    assert_eq!(page.styled, "<style>p { color: red }</style><p>Hi</p>");
+    assert_eq!(page.script, "<script>run()</script>");
+}
+
+#[test]
+fn canonicalizing_modifiers() {
+    #[derive(Debug, Validify)]
+    struct Contact {
+        #[modify(trim, email_canonical)]
+        #[validate(email)]
+        email: String,
+        #[modify(phone_e164(default_region = "US"))]
+        #[validate(phone)]
+        phone: String,
+        #[modify(phone_e164)]
+        phones: Vec<String>,
+        #[modify(url_normalize)]
+        #[validate(url)]
+        website: Option<String>,
+    }
+
+    let mut contact = Contact {
+        email: " John.Doe@Bücher.EXAMPLE ".to_string(),
+        phone: "(415) 237-0800".to_string(),
+        phones: vec!["+33 6 42 92 68 29".to_string(), "123".to_string()],
+        website: Some("HTTPS://Example.com:443/?debug&b=2&a=1".to_string()),
+    };
+
+    contact.validify().unwrap();
+
+    assert_eq!(contact.email, "John.Doe@xn--bcher-kva.example");
+    assert_eq!(contact.phone, "+14152370800");
+    assert_eq!(contact.phones, vec!["+33642926829", "123"]);
+    assert_eq!(
+        contact.website,
+        Some("https://example.com/?a=1&b=2&debug".to_string())
+    );
+
+    let mut contact = Contact {
+        email: "not an email".to_string(),
+        phone: "TEXT".to_string(),
+        phones: vec![],
+        website: Some("not a url".to_string()),
+    };
+
+    let errors = contact.validify().unwrap_err();
+    let errors = errors.errors();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(contact.email, "not an email");
+    assert_eq!(contact.phone, "TEXT");
+    assert_eq!(contact.website, Some("not a url".to_string()));
+}
+
+fn parse_phone(phone: &mut String) -> Result<(), validify::ValidationError> {
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() != 10 {
+        return Err(validify::field_err!("unparsable_phone"));
+    }
+    *phone = format!("+1{digits}");
+    Ok(())
+}
+
+#[test]
+fn try_custom_modifier() {
+    #[derive(Debug, Validify)]
+    struct Contact {
+        #[modify(try_custom(parse_phone))]
+        #[validate(length(equal = 12))]
+        phone: String,
+        #[modify(trim, try_custom(parse_phone))]
+        fax: Option<String>,
+        #[validate(length(min = 1))]
+        name: String,
+    }
+
+    #[derive(Debug, Validify)]
+    struct Company {
+        #[validify]
+        contacts: Vec<Contact>,
+        #[validify]
+        owner: Contact,
+    }
+
+    let mut contact = Contact {
+        phone: "(415) 237-0800".to_string(),
+        fax: Some(" 415.237.0801 ".to_string()),
+        name: "Mr Foo".to_string(),
+    };
+
+    contact.validify().unwrap();
+
+    assert_eq!(contact.phone, "+14152370800");
+    assert_eq!(contact.fax, Some("+14152370801".to_string()));
+
+    let mut contact = Contact {
+        phone: "123".to_string(),
+        fax: Some("456".to_string()),
+        name: "".to_string(),
+    };
+
+    let errors = contact.validify().unwrap_err();
+    let errors = errors.errors();
+
+    // The length validator on phone does not run
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].code(), "unparsable_phone");
+    assert_eq!(errors[0].location(), "/phone");
+    assert_eq!(errors[1].code(), "unparsable_phone");
+    assert_eq!(errors[1].location(), "/fax");
+    assert_eq!(errors[2].code(), "length");
+    assert_eq!(errors[2].location(), "/name");
+    assert_eq!(contact.phone, "123");
+
+    let mut company = Company {
+        contacts: vec![
+            Contact {
+                phone: "4152370800".to_string(),
+                fax: None,
+                name: "Mr Foo".to_string(),
+            },
+            Contact {
+                phone: "123".to_string(),
+                fax: None,
+                name: "Mr Bar".to_string(),
+            },
+        ],
+        owner: Contact {
+            phone: "123".to_string(),
+            fax: None,
+            name: "".to_string(),
+        },
+    };
+
+    let errors = company.validify().unwrap_err();
+    let mut locations: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|err| (err.code(), err.location().to_string()))
+        .collect();
+    locations.sort();
+
+    assert_eq!(
+        locations,
+        vec![
+            ("length".to_string(), "/owner/name".to_string()),
+            (
+                "unparsable_phone".to_string(),
+                "/contacts/1/phone".to_string()
+            ),
+            ("unparsable_phone".to_string(), "/owner/phone".to_string()),
+        ]
+    );
+    assert_eq!(company.contacts[0].phone, "+14152370800");
+}
+
+#[test]
+fn try_custom_modifier_in_enum() {
+    #[derive(Debug, Validify)]
+    enum Contact {
+        Phone {
+            #[modify(try_custom(parse_phone))]
+            #[validate(length(equal = 12))]
+            phone: String,
+        },
+        Fax(#[modify(try_custom(parse_phone))] String),
+    }
+
+    let mut contact = Contact::Phone {
+        phone: "4152370800".to_string(),
+    };
+    contact.validify().unwrap();
+    assert!(matches!(contact, Contact::Phone { ref phone } if phone == "+14152370800"));
+
+    let mut contact = Contact::Fax("123".to_string());
+    let errors = contact.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/0");
+
+    // Plain modify ignores the errors
+    let mut contact = Contact::Phone {
+        phone: "123".to_string(),
+    };
+    contact.modify();
+    assert!(matches!(contact, Contact::Phone { ref phone } if phone == "123"));
+}
+
+#[test]
+fn deeply_nested_modify() {
+    #[derive(Debug, Clone, Validify)]
+    struct Parent {
+        #[modify(trim)]
+        matrix: Vec<Vec<String>>,
+        #[modify(uppercase)]
+        boxed: Option<Box<[String]>>,
+        #[modify(trim, lowercase)]
+        optional: Option<Vec<Option<String>>>,
+        #[modify(trim)]
+        cow: Cow<'static, str>,
+        #[modify(clamp(max = 10))]
+        numbers: Vec<Option<i32>>,
+        #[validify]
+        children: Vec<Option<Box<Child>>>,
+    }
+
+    #[derive(Debug, Clone, Validify)]
+    struct Child {
+        #[modify(trim)]
+        #[validate(length(min = 1))]
+        name: String,
+    }
+
+    let mut parent = Parent {
+        matrix: vec![
+            vec![" a ".to_string()],
+            vec!["b ".to_string(), " c".to_string()],
+        ],
+        boxed: Some(vec!["boxed".to_string()].into_boxed_slice()),
+        optional: Some(vec![Some(" A ".to_string()), None]),
+        cow: Cow::Borrowed(" cow "),
+        numbers: vec![Some(20), None, Some(5)],
+        children: vec![
+            None,
+            Some(Box::new(Child {
+                name: " child ".to_string(),
+            })),
+            Some(Box::new(Child {
+                name: "  ".to_string(),
+            })),
+        ],
+    };
+
+    let errors = parent.validify().unwrap_err();
+
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/children/2/name");
+    assert_eq!(parent.matrix, vec![vec!["a"], vec!["b", "c"]]);
+    assert_eq!(parent.boxed.as_deref().unwrap(), ["BOXED"]);
+    assert_eq!(parent.optional, Some(vec![Some("a".to_string()), None]));
+    assert_eq!(parent.cow, "cow");
+    assert_eq!(parent.numbers, vec![Some(10), None, Some(5)]);
+    assert_eq!(parent.children[1].as_ref().unwrap().name, "child");
+}
@@ -1,4 +1,7 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
 use validify::Validate;
 
 #[derive(Debug, Validate)]
@@ -490,3 +493,54 @@ fn multiple_nested_hashmap_location_str() {
     assert_eq!(err.errors().len(), 1);
     assert_eq!(err.errors()[0].location(), "/child/children/four/allowance");
 }
+
+#[test]
+fn can_validate_deeply_nested_fields() {
+    #[derive(Debug, Validate)]
+    struct Parent<'a> {
+        #[validate]
+        a: Option<Vec<Option<Child>>>,
+        #[validate]
+        boxed: Box<Child>,
+        #[validate]
+        shared: Option<Arc<Child>>,
+        #[validate]
+        counted: Vec<Rc<Child>>,
+        #[validate]
+        cow: Cow<'a, Child>,
+        #[validate]
+        matrix: Vec<Vec<Child>>,
+    }
+
+    #[derive(Debug, Clone, Validate)]
+    struct Child {
+        #[validate(range(min = 1.))]
+        value: usize,
+    }
+
+    let child = |value| Child { value };
+
+    let parent = Parent {
+        a: Some(vec![Some(child(1)), None, Some(child(0))]),
+        boxed: Box::new(child(0)),
+        shared: Some(Arc::new(child(0))),
+        counted: vec![Rc::new(child(1)), Rc::new(child(0))],
+        cow: Cow::Owned(child(0)),
+        matrix: vec![vec![child(1)], vec![child(1), child(0)]],
+    };
+
+    let err = parent.validate().unwrap_err();
+    let locations: Vec<_> = err.errors().iter().map(|e| e.location()).collect();
+
+    assert_eq!(
+        locations,
+        vec![
+            "/a/2/value",
+            "/boxed/value",
+            "/shared/value",
+            "/counted/1/value",
+            "/cow/value",
+            "/matrix/1/1/value",
+        ]
+    );
+}
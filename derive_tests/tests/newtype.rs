@@ -0,0 +1,118 @@
+use serde_json::json;
+use validify::{Validate, ValidatedNewtype, ValidationErrors};
+
+#[derive(Debug, Clone, PartialEq, ValidatedNewtype)]
+struct Email(
+    #[modify(trim, lowercase)]
+    #[validate(email)]
+    String,
+);
+
+#[derive(Debug, Clone, ValidatedNewtype)]
+struct Username(
+    #[modify(trim)]
+    #[validate(length(min = 3, max = 16), custom(not_admin))]
+    String,
+);
+
+#[derive(Debug, Clone, Copy, ValidatedNewtype)]
+struct Port(#[validate(range(min = 1024.))] u16);
+
+#[derive(Debug, ValidatedNewtype)]
+#[newtype(skip(from_str, display))]
+struct Tags(#[validate(length(max = 2))] Vec<String>);
+
+fn not_admin(name: &str) -> Result<(), validify::ValidationError> {
+    if name == "admin" {
+        return Err(validify::ValidationError::new_field("reserved"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Validate, serde::Deserialize)]
+struct Signup {
+    #[validate]
+    email: Email,
+    #[validate]
+    aliases: Vec<Username>,
+}
+
+fn codes(errors: &ValidationErrors) -> Vec<String> {
+    errors.errors().iter().map(|e| e.code()).collect()
+}
+
+#[test]
+fn try_from_modifies_and_validates() {
+    let email = Email::try_from(String::from("  Foo@Bar.com ")).unwrap();
+    assert_eq!(email, Email(String::from("foo@bar.com")));
+    assert_eq!(email.as_ref(), "foo@bar.com");
+    assert_eq!(email.len(), 11);
+    assert_eq!(email.to_string(), "foo@bar.com");
+
+    let errors = Email::try_from(String::from("foo")).unwrap_err();
+    assert_eq!(codes(&errors), ["email"]);
+    let err = &errors.errors()[0];
+    assert_eq!(err.location(), "");
+    assert_eq!(err.field_name(), None);
+
+    let errors = Username::try_from(String::from(" admin ")).unwrap_err();
+    assert_eq!(codes(&errors), ["reserved"]);
+    assert!(Username::try_from(String::from("ok")).is_err());
+}
+
+#[test]
+fn from_str_parses_inner() {
+    let port: Port = "8080".parse().unwrap();
+    assert_eq!(*port, 8080);
+
+    let errors = "80".parse::<Port>().unwrap_err();
+    assert_eq!(codes(&errors), ["range"]);
+
+    let errors = "eighty".parse::<Port>().unwrap_err();
+    assert_eq!(codes(&errors), ["parse"]);
+    let err = &errors.errors()[0];
+    assert_eq!(err.location(), "");
+    assert_eq!(err.field_name(), None);
+    assert_eq!(err.params()["actual"], "eighty");
+}
+
+#[test]
+fn skipped_impls_allow_inner_types_without_them() {
+    let tags = Tags::try_from(vec![String::from("a")]).unwrap();
+    assert_eq!(tags.len(), 1);
+
+    let errors = Tags::try_from(vec![String::new(); 3]).unwrap_err();
+    assert_eq!(codes(&errors), ["length"]);
+}
+
+#[test]
+fn deserialize_validates() {
+    let email: Email = serde_json::from_value(json!(" A@B.com")).unwrap();
+    assert_eq!(&*email, "a@b.com");
+
+    let res = serde_json::from_value::<Email>(json!("nope"));
+    assert!(res.unwrap_err().to_string().contains("code: email"));
+
+    let res = serde_json::from_value::<Signup>(json!({ "email": "nope", "aliases": [] }));
+    assert!(res.is_err());
+}
+
+#[test]
+fn nested_errors_are_located_at_outer_field() {
+    let signup = Signup {
+        email: Email(String::from("invalid")),
+        aliases: vec![
+            Username(String::from("alias")),
+            Username(String::from("admin")),
+        ],
+    };
+
+    let errors = signup.validate().unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].location(), "/email");
+    assert_eq!(errors[0].field_name(), Some("email"));
+    assert_eq!(errors[1].location(), "/aliases/1");
+    assert_eq!(errors[1].field_name(), Some("aliases"));
+    assert_eq!(errors[1].code(), "reserved");
+}
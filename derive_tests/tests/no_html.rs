@@ -0,0 +1,89 @@
+use validify::{Validate, Validify};
+
+#[test]
+fn can_validate_no_html_ok() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(no_html)]
+        val: String,
+    }
+
+    let s = TestStruct {
+        val: "1 < 2 and 3 > 2".to_string(),
+    };
+
+    assert!(s.validate().is_ok());
+}
+
+#[test]
+fn html_fails_validation() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(no_html)]
+        val: String,
+    }
+
+    let s = TestStruct {
+        val: "Hello <b>world</b>".to_string(),
+    };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].code(), "no_html");
+    assert_eq!(errs[0].location(), "/val");
+}
+
+#[test]
+fn can_specify_code_and_message_for_no_html() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(no_html(code = "oops", message = "no markup"))]
+        val: Option<String>,
+    }
+    let s = TestStruct {
+        val: Some("<!-- comment -->".to_string()),
+    };
+    let res = s.validate();
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    let errs = err.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].code(), "oops");
+    assert_eq!(errs[0].message(), Some("no markup".to_string()));
+    assert_eq!(errs[0].params()["actual"], "<!-- comment -->");
+}
+
+#[test]
+fn no_html_in_collection() {
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(iter(no_html))]
+        val: Vec<String>,
+    }
+    let s = TestStruct {
+        val: vec!["fine".to_string(), "<script>".to_string()],
+    };
+    let errs = s.validate().unwrap_err();
+    let errs = errs.field_errors();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].location(), "/val/1");
+}
+
+#[test]
+fn stripped_html_passes_no_html() {
+    #[derive(Debug, Validify)]
+    struct TestStruct {
+        #[modify(strip_html)]
+        #[validate(no_html)]
+        val: String,
+    }
+
+    // Escaped markup must not be decoded into live markup
+    let mut s = TestStruct {
+        val: "<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>".to_string(),
+    };
+    s.validify().unwrap();
+    assert_eq!(s.val, "&lt;script&gt;alert(1)&lt;/script&gt;");
+}
@@ -1,5 +1,6 @@
 use prost::Message;
 
+#[allow(dead_code)]
 #[derive(Message, validify::Validify)]
 struct Data {
     #[validate(range(min = 5.0, max = 10.0))]
@@ -11,3 +12,27 @@ struct Data {
     #[validate(length(min = 1, max = 10))]
     v1: String,
 }
+
+/// A generated message without validify attributes.
+#[derive(Message)]
+struct Generated {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(validify::Validate)]
+#[validate(remote = "Generated")]
+struct GeneratedDef {
+    #[validate(length(min = 1, max = 10))]
+    name: String,
+}
+
+#[test]
+fn validates_generated_messages_remotely() {
+    let message = Generated {
+        name: String::new(),
+    };
+    let errors = GeneratedDef::validate_remote(&message).unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/name");
+}
@@ -88,6 +88,8 @@ fn value_out_of_range_fails_validation_with_crate_path() {
     let errs = err.field_errors();
     assert_eq!(errs.len(), 1);
     assert_eq!(errs[0].code(), "range");
+    assert_eq!(errs[0].params()["min"].as_u64(), Some(0));
+    assert_eq!(errs[0].params()["max"].as_u64(), Some(10));
 }
 
 #[test]
@@ -0,0 +1,223 @@
+use validify::{Validate, Validify};
+
+#[derive(Debug, Clone, Validify)]
+struct Comment {
+    #[modify(trim)]
+    #[validate(length(min = 1))]
+    text: String,
+    #[validify]
+    #[validate(max_depth = 3)]
+    replies: Vec<Comment>,
+}
+
+impl Comment {
+    fn new(text: &str, replies: Vec<Comment>) -> Self {
+        Self {
+            text: text.to_string(),
+            replies,
+        }
+    }
+
+    /// Builds a thread of single replies `depth` levels deep.
+    fn thread(depth: usize) -> Self {
+        (0..depth).fold(Comment::new("leaf", vec![]), |reply, _| {
+            Comment::new("reply", vec![reply])
+        })
+    }
+}
+
+#[derive(Debug, Validate)]
+enum Filter {
+    Eq(#[validate(length(min = 1))] String),
+    Not(#[validate] Box<Filter>),
+    And(#[validate(max_depth = 2)] Vec<Filter>),
+}
+
+#[derive(Debug, Validate)]
+struct Category {
+    #[validate(length(min = 1))]
+    name: String,
+    #[validate]
+    parent: Option<Box<Category>>,
+}
+
+impl Category {
+    /// Builds a chain of parents `depth` levels deep.
+    fn chain(depth: usize) -> Self {
+        (0..depth).fold(
+            Category {
+                name: "root".to_string(),
+                parent: None,
+            },
+            |parent, _| Category {
+                name: "child".to_string(),
+                parent: Some(Box::new(parent)),
+            },
+        )
+    }
+}
+
+#[derive(Debug, Validate)]
+struct Folder {
+    #[validate]
+    content: Box<Entry>,
+}
+
+#[derive(Debug, Validate)]
+struct Entry {
+    #[validate(length(min = 1))]
+    name: String,
+    #[validate]
+    folder: Option<Box<Folder>>,
+}
+
+#[derive(Debug, Validate)]
+struct Thread {
+    #[validate(max_depth = 2)]
+    replies: Vec<Thread>,
+    #[validate]
+    filter: Option<Filter>,
+}
+
+#[test]
+fn validates_recursive_types() {
+    let mut comment = Comment::new(
+        "root",
+        vec![
+            Comment::new(" ok ", vec![]),
+            Comment::new("", vec![Comment::new("  ", vec![])]),
+        ],
+    );
+
+    let errors = comment.validify().unwrap_err();
+    let locations: Vec<_> = errors.errors().iter().map(|e| e.location()).collect();
+    assert_eq!(
+        locations,
+        vec!["/replies/1/text", "/replies/1/replies/0/text"]
+    );
+    assert_eq!(comment.replies[0].text, "ok");
+
+    let category = Category {
+        name: "child".to_string(),
+        parent: Some(Box::new(Category {
+            name: "".to_string(),
+            parent: None,
+        })),
+    };
+    let errors = category.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/parent/name");
+
+    let filter = Filter::Not(Box::new(Filter::Eq(String::new())));
+    let errors = filter.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/0/0");
+}
+
+#[test]
+fn max_depth_limits_nesting() {
+    let mut comment = Comment::thread(3);
+    assert!(comment.validify().is_ok());
+
+    let mut comment = Comment::thread(4);
+    let errors = comment.validify().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+
+    let error = &errors.errors()[0];
+    assert_eq!(error.code(), "max_depth");
+    assert_eq!(error.location(), "/replies/0/replies/0/replies/0/replies/0");
+    assert_eq!(error.params()["max"], 3);
+
+    // The depth is reset after validation
+    let mut comment = Comment::thread(3);
+    assert!(comment.validify().is_ok());
+
+    let filter = Filter::And(vec![Filter::And(vec![Filter::And(vec![])])]);
+    assert!(filter.validate().is_ok());
+
+    let filter = Filter::And(vec![Filter::And(vec![Filter::And(vec![Filter::And(
+        vec![],
+    )])])]);
+    let errors = filter.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].code(), "max_depth");
+    assert_eq!(errors.errors()[0].location(), "/0/0/0/0/0/0");
+}
+
+#[test]
+fn max_depth_prevents_stack_overflow() {
+    // Deep enough to overflow the stack of the test thread if validated without a limit
+    let comment = Comment::thread(100_000);
+
+    let errors = comment.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].code(), "max_depth");
+
+    // Dropping such deep structures recursively overflows on its own
+    std::mem::forget(comment);
+}
+
+#[test]
+fn recursive_fields_have_a_default_depth_limit() {
+    assert!(Category::chain(validify::DEFAULT_MAX_DEPTH)
+        .validate()
+        .is_ok());
+
+    let category = Category::chain(validify::DEFAULT_MAX_DEPTH + 1);
+    let errors = category.validate().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+
+    let error = &errors.errors()[0];
+    assert_eq!(error.code(), "max_depth");
+    assert_eq!(error.params()["max"], validify::DEFAULT_MAX_DEPTH);
+    assert_eq!(
+        error.location(),
+        "/parent".repeat(validify::DEFAULT_MAX_DEPTH + 1)
+    );
+
+    let filter = (0..100_000).fold(Filter::Eq("a".to_string()), |filter, _| {
+        Filter::Not(Box::new(filter))
+    });
+    let errors = filter.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].code(), "max_depth");
+    std::mem::forget(filter);
+}
+
+#[test]
+fn mutually_recursive_fields_have_a_default_depth_limit() {
+    let folder = (0..100_000).fold(
+        Folder {
+            content: Box::new(Entry {
+                name: "file".to_string(),
+                folder: None,
+            }),
+        },
+        |folder, _| Folder {
+            content: Box::new(Entry {
+                name: "dir".to_string(),
+                folder: Some(Box::new(folder)),
+            }),
+        },
+    );
+
+    let errors = folder.validate().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].code(), "max_depth");
+    assert_eq!(
+        errors.errors()[0].location(),
+        "/content/folder".repeat(validify::DEFAULT_MAX_DEPTH) + "/content"
+    );
+    std::mem::forget(folder);
+}
+
+#[test]
+fn depth_limits_are_tracked_per_type() {
+    // Both types are at their limit, which only fails if their depths were added up
+    let thread = Thread {
+        replies: vec![Thread {
+            replies: vec![Thread {
+                replies: vec![],
+                filter: Some(Filter::And(vec![Filter::And(vec![])])),
+            }],
+            filter: None,
+        }],
+        filter: None,
+    };
+    assert!(thread.validate().is_ok());
+}
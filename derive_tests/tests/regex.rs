@@ -76,3 +76,36 @@ fn can_specify_message_for_regex() {
     assert_eq!(errs.len(), 1);
     assert_eq!(errs[0].clone().message().unwrap(), "oops");
 }
+
+#[test]
+fn can_validate_with_regex_set() {
+    lazy_static! {
+        static ref CODES: regex::RegexSet =
+            regex::RegexSet::new([r"^[a-z]{2}$", r"^\d{3}$"]).unwrap();
+    }
+
+    #[derive(Debug, Validate)]
+    struct TestStruct {
+        #[validate(regex(CODES))]
+        val: String,
+        #[validate(iter(regex(CODES)))]
+        vals: Vec<String>,
+    }
+
+    let s = TestStruct {
+        val: "123".to_string(),
+        vals: vec!["ab".to_string()],
+    };
+    assert!(s.validate().is_ok());
+
+    let s = TestStruct {
+        val: "1a".to_string(),
+        vals: vec!["ab".to_string(), "abc".to_string()],
+    };
+    let err = s.validate().unwrap_err();
+    let errs = err.errors();
+    assert_eq!(errs.len(), 2);
+    assert_eq!(errs[0].code(), "regex");
+    assert_eq!(errs[0].params()["actual"], "1a");
+    assert_eq!(errs[1].location(), "/vals/1");
+}
@@ -0,0 +1,195 @@
+use validify::{schema_err, schema_validation, Validate, ValidationErrors};
+
+/// Stands in for types from another crate, e.g. generated messages.
+mod external {
+    pub struct Signup {
+        pub name: String,
+        pub email: Option<String>,
+        pub age: u32,
+        pub address: Address,
+        pub previous_addresses: Vec<Address>,
+        pub tags: Vec<String>,
+    }
+
+    pub struct Address {
+        pub street: String,
+    }
+
+    pub enum Contact {
+        Phone(String),
+        Email { address: String },
+    }
+
+    pub struct Page<T> {
+        pub items: Vec<T>,
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Validate)]
+#[validate(remote = "external::Signup", check_signup)]
+struct SignupDef {
+    #[validate(length(min = 3))]
+    name: String,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(range(min = 18.))]
+    age: u32,
+    #[validate(remote = "AddressDef")]
+    address: external::Address,
+    #[validate(remote = "AddressDef")]
+    previous_addresses: Vec<external::Address>,
+    #[validate(iter(length(min = 1)))]
+    tags: Vec<String>,
+}
+
+#[schema_validation]
+fn check_signup(signup: &external::Signup) -> Result<(), ValidationErrors> {
+    if signup.email.is_none() && signup.age < 21 {
+        schema_err!("email_required");
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Validate)]
+#[validate(remote = "external::Address")]
+struct AddressDef {
+    #[validate(length(min = 1))]
+    street: String,
+}
+
+#[allow(dead_code)]
+#[derive(Validate)]
+#[validate(remote = "external::Contact")]
+enum ContactDef {
+    Phone(#[validate(phone)] String),
+    Email {
+        #[validate(email)]
+        address: String,
+    },
+}
+
+#[derive(Debug, Validate)]
+struct Item {
+    #[validate(length(max = 3))]
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(Validate)]
+#[validate(remote = "external::Page<T>")]
+struct PageDef<T> {
+    #[validate(length(max = 2))]
+    #[validate]
+    items: Vec<T>,
+}
+
+#[test]
+fn validates_remote_structs() {
+    let signup = external::Signup {
+        name: "al".to_string(),
+        email: None,
+        age: 12,
+        address: external::Address {
+            street: String::new(),
+        },
+        previous_addresses: vec![
+            external::Address {
+                street: "Main".to_string(),
+            },
+            external::Address {
+                street: String::new(),
+            },
+        ],
+        tags: vec![String::new()],
+    };
+
+    let errors = SignupDef::validate_remote(&signup).unwrap_err();
+    let errors = errors.errors();
+    let codes: Vec<_> = errors.iter().map(|e| e.code()).collect();
+    assert_eq!(
+        codes,
+        [
+            "length",
+            "range",
+            "length",
+            "length",
+            "length",
+            "email_required"
+        ]
+    );
+    assert_eq!(errors[0].location(), "/name");
+    assert_eq!(errors[2].location(), "/address/street");
+    assert_eq!(errors[2].field_name(), Some("street"));
+    assert_eq!(errors[3].location(), "/previous_addresses/1/street");
+    assert_eq!(errors[4].location(), "/tags/0");
+
+    let signup = external::Signup {
+        name: "alice".to_string(),
+        email: Some("alice@example.com".to_string()),
+        age: 30,
+        address: external::Address {
+            street: "Main".to_string(),
+        },
+        previous_addresses: vec![],
+        tags: vec![],
+    };
+    assert!(SignupDef::validate_remote(&signup).is_ok());
+}
+
+#[test]
+fn validates_remote_enums() {
+    let contact = external::Contact::Email {
+        address: "nope".to_string(),
+    };
+    let errors = ContactDef::validate_remote(&contact).unwrap_err();
+    assert_eq!(errors.errors()[0].code(), "email");
+    assert_eq!(errors.errors()[0].location(), "/address");
+
+    let contact = external::Contact::Phone("+385 91 123 4567".to_string());
+    assert!(ContactDef::validate_remote(&contact).is_ok());
+}
+
+#[test]
+fn validates_generic_remotes() {
+    let page = external::Page {
+        items: vec![
+            Item {
+                name: "a".to_string(),
+            },
+            Item {
+                name: "abcd".to_string(),
+            },
+            Item {
+                name: "b".to_string(),
+            },
+        ],
+    };
+
+    let errors = PageDef::<Item>::validate_remote(&page).unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].location(), "/items/1/name");
+    assert_eq!(errors[1].location(), "/items");
+}
+
+#[test]
+fn validates_remote_fields_of_local_types() {
+    #[derive(Validate)]
+    struct Shipment {
+        #[validate(remote = "AddressDef")]
+        destination: Option<external::Address>,
+    }
+
+    let shipment = Shipment {
+        destination: Some(external::Address {
+            street: String::new(),
+        }),
+    };
+    let errors = shipment.validate().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/destination/street");
+
+    let shipment = Shipment { destination: None };
+    assert!(shipment.validate().is_ok());
+    assert!(Shipment::rules()[0].nested_rules().is_empty());
+}
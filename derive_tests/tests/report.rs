@@ -0,0 +1,165 @@
+use serde::Serialize;
+use serde_json::json;
+use validify::{Modify, Validify};
+
+#[derive(Debug, Serialize, Validify)]
+struct Address {
+    #[modify(trim, uppercase)]
+    city: String,
+}
+
+#[derive(Debug, Serialize, Validify)]
+struct User {
+    #[modify(trim)]
+    name: String,
+    #[modify(trim, lowercase)]
+    email: Option<String>,
+    #[modify(trim, dedup)]
+    tags: Vec<String>,
+    #[modify(trim)]
+    password: String,
+    #[validify]
+    address: Address,
+    #[validify]
+    previous: Vec<Address>,
+}
+
+fn user() -> User {
+    User {
+        name: " John ".to_string(),
+        email: Some("John@Example.com".to_string()),
+        tags: vec!["a".to_string(), " b ".to_string(), "b".to_string()],
+        password: " secret ".to_string(),
+        address: Address {
+            city: "zagreb".to_string(),
+        },
+        previous: vec![
+            Address {
+                city: "SPLIT".to_string(),
+            },
+            Address {
+                city: " rijeka".to_string(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn modify_with_report() {
+    let mut user = user();
+
+    let report = user.modify_with_report();
+
+    let changes: Vec<_> = report
+        .modifications()
+        .iter()
+        .map(|m| (m.location(), m.modifier()))
+        .collect();
+
+    assert_eq!(
+        changes,
+        vec![
+            ("/name", "trim"),
+            ("/email", "lowercase"),
+            ("/tags/1", "trim"),
+            ("/tags", "dedup"),
+            ("/password", "trim"),
+            ("/address/city", "uppercase"),
+            ("/previous/1/city", "trim"),
+            ("/previous/1/city", "uppercase"),
+        ]
+    );
+
+    let name = &report.modifications()[0];
+    assert_eq!(name.before(), Some(&json!(" John ")));
+    assert_eq!(name.after(), Some(&json!("John")));
+
+    let dedup = &report.modifications()[3];
+    assert_eq!(dedup.before(), Some(&json!(["a", "b", "b"])));
+    assert_eq!(dedup.after(), Some(&json!(["a", "b"])));
+
+    // Modifications are the same as with a regular modify
+    let mut modified = self::user();
+    modified.modify();
+    assert_eq!(
+        serde_json::to_value(&user).unwrap(),
+        serde_json::to_value(&modified).unwrap()
+    );
+}
+
+#[test]
+fn redacted_report() {
+    let mut user = user();
+
+    let mut report = user.modify_with_report();
+    report.redact("/password");
+
+    let password = report
+        .modifications()
+        .iter()
+        .find(|m| m.location() == "/password")
+        .unwrap();
+
+    assert!(password.is_redacted());
+    assert!(password.before().is_none());
+    assert!(password.after().is_none());
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(!json.to_string().contains("secret"));
+}
+
+#[test]
+fn report_without_serialize() {
+    #[derive(Debug, PartialEq)]
+    struct Opaque(u8);
+
+    fn bump(o: &mut Opaque) {
+        o.0 += 1;
+    }
+
+    #[derive(Debug, Validify)]
+    #[modify(custom(reset))]
+    enum Thing {
+        Named {
+            #[modify(trim)]
+            name: String,
+            #[modify(custom(bump))]
+            opaque: Opaque,
+        },
+    }
+
+    fn reset(thing: &mut Thing) {
+        let Thing::Named { opaque, .. } = thing;
+        opaque.0 = 0;
+    }
+
+    let mut thing = Thing::Named {
+        name: " a ".to_string(),
+        opaque: Opaque(1),
+    };
+
+    let report = thing.modify_with_report();
+
+    let changes: Vec<_> = report
+        .modifications()
+        .iter()
+        .map(|m| {
+            (
+                m.location(),
+                m.modifier(),
+                m.before().cloned(),
+                m.is_unknown(),
+            )
+        })
+        .collect();
+
+    // Values which are not serializable are always reported as unknown, without values
+    assert_eq!(
+        changes,
+        vec![
+            ("/name", "trim", Some(json!(" a ")), false),
+            ("/opaque", "custom", None, true),
+            ("", "custom", None, true),
+        ]
+    );
+}
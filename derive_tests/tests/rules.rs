@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use validify::{Validate, ValidationError, Validify};
+
+mod rules {
+    use lazy_static::lazy_static;
+    use regex::Regex;
+
+    lazy_static! {
+        static ref USERNAME_RE: Regex = Regex::new(r"^[a-z0-9_]+$").unwrap();
+    }
+
+    fn not_reserved(name: &str) -> Result<(), validify::ValidationError> {
+        if name == "admin" {
+            return Err(validify::ValidationError::new_field("reserved"));
+        }
+        Ok(())
+    }
+
+    validify::rule_set!(
+        /// Lowercase usernames of 3 to 16 characters.
+        pub Username = [
+            length(min = 3, max = 16),
+            regex(USERNAME_RE),
+            non_control_char,
+            custom(not_reserved)
+        ],
+        modify = [trim, lowercase]
+    );
+
+    validify::rule_set!(pub Percentage: f64 = [range(min = 0., max = 100.)]);
+
+    validify::rule_set!(pub Tags: Vec<String> = [length(max = 2), iter(length(min = 1))], modify = [trim, dedup]);
+
+    fn no_spaces(code: &mut str) -> Result<(), validify::ValidationError> {
+        if code.contains(' ') {
+            return Err(validify::ValidationError::new_field("spaces"));
+        }
+        Ok(())
+    }
+
+    validify::rule_set!(pub Code = [length(min = 1)], modify = [trim, uppercase, try_custom(no_spaces)]);
+}
+
+#[derive(Debug, Validify)]
+struct Signup {
+    #[validate(rules(rules::Username))]
+    username: String,
+
+    #[validate(rules(rules::Username, code = "alias", message = "Invalid alias"))]
+    alias: Option<String>,
+
+    #[validate(rules(rules::Percentage))]
+    discount: f64,
+
+    #[validate(rules(rules::Tags))]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Validate)]
+struct Team {
+    #[validate(iter(rules(rules::Username)))]
+    members: Vec<String>,
+}
+
+#[derive(Debug, Validify)]
+struct Roster {
+    #[validate(iter(rules(rules::Username)))]
+    members: Vec<String>,
+
+    #[validate(iter(iter(rules(rules::Code))))]
+    codes: Vec<Vec<String>>,
+
+    #[validate(keys(rules(rules::Code)), values(rules(rules::Username)))]
+    roles: BTreeMap<String, String>,
+}
+
+fn signup() -> Signup {
+    Signup {
+        username: "  Bob_1 ".to_string(),
+        alias: None,
+        discount: 10.,
+        tags: vec![" a".to_string(), "a ".to_string()],
+    }
+}
+
+#[test]
+fn rule_set_modifies_and_validates() {
+    let mut signup = signup();
+    assert!(signup.validify().is_ok());
+    assert_eq!(signup.username, "bob_1");
+    assert_eq!(signup.tags, ["a"]);
+}
+
+#[test]
+fn rule_set_errors_are_located_at_field() {
+    let mut signup = Signup {
+        username: "admin".to_string(),
+        alias: Some("B!".to_string()),
+        discount: 120.,
+        ..signup()
+    };
+
+    let errors = signup.validify().unwrap_err();
+    let errors = errors.errors();
+
+    assert_eq!(errors[0].code(), "reserved");
+    assert_eq!(errors[0].location(), "/username");
+    assert_eq!(errors[0].field_name(), Some("username"));
+
+    // Both the length and the regex fail and get the overrides
+    assert_eq!(errors[1].code(), "alias");
+    assert_eq!(errors[1].location(), "/alias");
+    assert_eq!(errors[1].message().as_deref(), Some("Invalid alias"));
+    assert_eq!(errors[2].code(), "alias");
+
+    assert_eq!(errors[3].code(), "range");
+    assert_eq!(errors[3].location(), "/discount");
+    assert_eq!(errors.len(), 4);
+}
+
+#[test]
+fn rule_set_in_iter() {
+    let team = Team {
+        members: vec!["bob".to_string(), "Al".to_string()],
+    };
+    let errors = team.validate().unwrap_err();
+    assert!(errors
+        .errors()
+        .iter()
+        .all(|e| e.location() == "/members/1" && e.field_name() == Some("members")));
+}
+
+#[test]
+fn rule_set_can_be_called_directly() {
+    assert!(rules::Percentage::validate(&50.).is_ok());
+
+    let mut name = " ALICE ".to_string();
+    assert!(rules::Username::modify(&mut name).is_ok());
+    assert_eq!(name, "alice");
+
+    let errors = rules::Tags::validate(&vec![String::new()]).unwrap_err();
+    let err: &ValidationError = &errors.errors()[0];
+    assert_eq!(err.location(), "/0");
+    assert_eq!(err.field_name(), None);
+}
+
+#[test]
+fn rule_set_modifies_elements() {
+    let mut roster = Roster {
+        members: vec![" Bob ".to_string(), "ALICE".to_string()],
+        codes: vec![
+            vec![" a".to_string()],
+            vec!["b ".to_string(), "c".to_string()],
+        ],
+        roles: BTreeMap::from([(" x ".to_string(), " Carol".to_string())]),
+    };
+    assert!(roster.validify().is_ok());
+    assert_eq!(roster.members, ["bob", "alice"]);
+    assert_eq!(roster.codes, [vec!["A"], vec!["B", "C"]]);
+    assert_eq!(roster.roles["X"], "carol");
+}
+
+#[test]
+fn rule_set_element_modification_errors_are_located_at_elements() {
+    let mut roster = Roster {
+        members: vec![],
+        codes: vec![vec![], vec!["a".to_string(), "b c".to_string()]],
+        roles: BTreeMap::from([("d e".to_string(), "dave".to_string())]),
+    };
+    let errors = roster.validify().unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].code(), "spaces");
+    assert_eq!(errors[0].location(), "/codes/1/1");
+    assert_eq!(errors[0].field_name(), Some("codes"));
+    assert_eq!(errors[1].code(), "spaces");
+    assert_eq!(errors[1].location(), "/roles/d e");
+}
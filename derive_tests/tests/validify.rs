@@ -557,3 +557,75 @@ fn biggest_of_bois() {
     assert_eq!(schema_errs.len(), 2);
     assert_eq!(field_errs.len(), 9);
 }
+
+#[test]
+fn try_validify_leaves_original_on_failure() {
+    #[derive(Debug, Clone, PartialEq, Validify)]
+    struct Testor {
+        #[modify(trim, lowercase)]
+        #[validate(email)]
+        email: String,
+        #[modify(trim)]
+        #[validate(length(min = 3))]
+        name: String,
+    }
+
+    let original = Testor {
+        email: "  Foo@Bar.com ".to_string(),
+        name: " Foo ".to_string(),
+    };
+
+    let validified = original.clone().try_validify().unwrap();
+    assert_eq!(validified.email, "foo@bar.com");
+    assert_eq!(validified.name, "Foo");
+
+    let invalid = Testor {
+        email: "  Foo@Bar.com ".to_string(),
+        name: " F ".to_string(),
+    };
+
+    let (returned, errors) = invalid.clone().try_validify().unwrap_err();
+    assert_eq!(returned, invalid);
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(errors.errors()[0].location(), "/name");
+
+    let validified = original.validified().unwrap();
+    assert_eq!(validified.email, "foo@bar.com");
+    assert_eq!(original.email, "  Foo@Bar.com ");
+
+    let errors = invalid.validified().unwrap_err();
+    assert_eq!(errors.errors().len(), 1);
+    assert_eq!(invalid.name, " F ");
+}
+
+#[test]
+fn validify_top_level_containers() {
+    #[derive(Debug, Clone, Validify)]
+    struct Item {
+        #[modify(trim)]
+        #[validate(length(min = 1))]
+        name: String,
+    }
+
+    let item = |name: &str| Item {
+        name: name.to_string(),
+    };
+
+    let mut items = vec![item(" a "), item("  ")];
+    let errors = items.validify().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/1/name");
+    assert_eq!(items[0].name, "a");
+
+    let mut by_id = std::collections::HashMap::from([
+        ("b".to_string(), item(" ")),
+        ("a".to_string(), item("")),
+    ]);
+    let errors = by_id.validify().unwrap_err();
+    let locations: Vec<_> = errors.errors().iter().map(|e| e.location()).collect();
+    assert_eq!(locations, vec!["/a/name", "/b/name"]);
+
+    let mut pair = (Some(item(" b ")), Box::new(item("")));
+    let errors = pair.validify().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/1/name");
+    assert_eq!(pair.0.unwrap().name, "b");
+}
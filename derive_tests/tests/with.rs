@@ -0,0 +1,162 @@
+use validify::{Modifier, Validate, ValidationError, Validator, Validify};
+
+struct Decimals {
+    max: u32,
+}
+
+impl Decimals {
+    fn max(max: u32) -> Self {
+        Self { max }
+    }
+}
+
+impl Validator<f64> for Decimals {
+    fn validate(&self, value: &f64) -> Result<(), ValidationError> {
+        let scaled = value * 10f64.powi(self.max as i32);
+        if (scaled - scaled.round()).abs() > 1e-9 {
+            return Err(ValidationError::new_field("decimals").with_param("max", &self.max));
+        }
+        Ok(())
+    }
+}
+
+struct AllowedMime(&'static [&'static str]);
+
+impl Validator<String> for AllowedMime {
+    fn validate(&self, value: &String) -> Result<(), ValidationError> {
+        if self.0.contains(&value.as_str()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new_field("mime"))
+        }
+    }
+}
+
+struct Replace(char, char);
+
+impl Modifier<String> for Replace {
+    fn modify(&self, value: &mut String) {
+        *value = value.replace(self.0, &self.1.to_string());
+    }
+}
+
+const IMAGES: &[&str] = &["image/png", "image/jpeg"];
+
+#[derive(Debug, Validate)]
+struct Upload {
+    #[validate(with = Decimals::max(2))]
+    price: f64,
+
+    #[validate(with = Decimals::max(0))]
+    discount: Option<f64>,
+
+    #[validate(with(validator = AllowedMime(IMAGES), message = "Unsupported type"))]
+    mime: String,
+
+    #[validate(iter(with = AllowedMime(IMAGES)))]
+    previews: Vec<String>,
+
+    #[validate(with = |v: &String| if v.is_empty() {
+        Err(ValidationError::new_field_named("title_field", "empty"))
+    } else {
+        Ok(())
+    })]
+    title: String,
+}
+
+#[derive(Debug, Validify)]
+struct Slug {
+    #[modify(with = Replace(' ', '-'))]
+    #[validate(with = AllowedMime(&["a-b"]))]
+    value: String,
+
+    #[modify(with = Replace('_', '-'), with = |v: &mut String| v.make_ascii_uppercase())]
+    other: Option<String>,
+}
+
+#[test]
+fn with_validates() {
+    let upload = Upload {
+        price: 1.25,
+        discount: Some(10.),
+        mime: "image/png".to_string(),
+        previews: vec!["image/jpeg".to_string()],
+        title: "cat".to_string(),
+    };
+    assert!(upload.validate().is_ok());
+
+    let upload = Upload {
+        price: 1.255,
+        discount: Some(0.5),
+        mime: "text/html".to_string(),
+        previews: vec!["image/png".to_string(), "text/plain".to_string()],
+        title: String::new(),
+    };
+    let errors = upload.validate().unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 5);
+
+    assert_eq!(errors[0].code(), "decimals");
+    assert_eq!(errors[0].location(), "/price");
+    assert_eq!(errors[0].field_name(), Some("price"));
+    assert_eq!(errors[0].params()["max"], 2);
+
+    assert_eq!(errors[1].location(), "/discount");
+
+    assert_eq!(errors[2].code(), "mime");
+    assert_eq!(errors[2].message().as_deref(), Some("Unsupported type"));
+
+    assert_eq!(errors[3].location(), "/previews/1");
+    assert_eq!(errors[3].field_name(), Some("previews"));
+
+    assert_eq!(errors[4].code(), "empty");
+    assert_eq!(errors[4].location(), "/title_field");
+}
+
+#[test]
+fn with_modifies() {
+    let mut slug = Slug {
+        value: "a b".to_string(),
+        other: Some("c_d".to_string()),
+    };
+    assert!(slug.validify().is_ok());
+    assert_eq!(slug.value, "a-b");
+    assert_eq!(slug.other.as_deref(), Some("C-D"));
+
+    let mut slug = Slug {
+        value: "a c".to_string(),
+        other: None,
+    };
+    let errors = slug.validify().unwrap_err();
+    assert_eq!(errors.errors()[0].location(), "/value");
+}
+
+#[test]
+fn code_replaces_error_code() {
+    fn not_empty(v: &str) -> Result<(), ValidationError> {
+        if v.is_empty() {
+            return Err(ValidationError::new_field("empty"));
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, Validate)]
+    struct Thumbnail {
+        #[validate(with(validator = AllowedMime(IMAGES), code = "image"))]
+        mime: String,
+        #[validate(iter(custom(function = not_empty, code = "blank")))]
+        labels: Vec<String>,
+    }
+
+    let thumbnail = Thumbnail {
+        mime: "text/plain".to_string(),
+        labels: vec!["a".to_string(), String::new()],
+    };
+    let errors = thumbnail.validate().unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].code(), "image");
+    assert_eq!(errors[0].location(), "/mime");
+    assert_eq!(errors[1].code(), "blank");
+    assert_eq!(errors[1].location(), "/labels/1");
+}
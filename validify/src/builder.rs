@@ -0,0 +1,722 @@
+//! Programmatic validation of types which cannot be annotated, e.g. ones generated by other crates.
+//!
+//! [Rules] are built from the field getters and the [FieldRule]s of each field, and produce the same
+//! errors with the same locations as the derived validation. The rules of a field are given as an array
+//! of rules of the same kind, or as a tuple of rules of different kinds. [LengthRule]s and [RangeRule]s
+//! can also be converted into [FieldRule]s with `.into()` to mix them with other rules in an array.
+//!
+//! ```
+//! use validify::builder::{email, is_in, length, Rules};
+//! use validify::{schema_err, schema_validation, ValidationErrors};
+//!
+//! struct User {
+//!     email: String,
+//!     name: String,
+//!     role: String,
+//!     nickname: Option<String>,
+//! }
+//!
+//! #[schema_validation]
+//! fn check_user(user: &User) -> Result<(), ValidationErrors> {
+//!     if user.nickname.as_ref() == Some(&user.name) {
+//!         schema_err!("same_nickname");
+//!     }
+//! }
+//!
+//! let rules = Rules::<User>::new()
+//!     .field("email", |u| &u.email, [email(), length().max(255).into()])
+//!     .field("name", |u| &u.name, [length().min(1).max(32).message("Invalid name")])
+//!     .field("role", |u| &u.role, [is_in(["admin", "user"])])
+//!     .optional("nickname", |u| u.nickname.as_ref(), [length().min(3)])
+//!     .schema(check_user);
+//!
+//! let user = User {
+//!     email: "foo".to_string(),
+//!     name: "bob".to_string(),
+//!     role: "root".to_string(),
+//!     nickname: Some("bob".to_string()),
+//! };
+//!
+//! let errors = rules.validate(&user).unwrap_err();
+//! let codes: Vec<_> = errors.errors().iter().map(|e| e.code()).collect();
+//! assert_eq!(codes, ["email", "in", "same_nickname"]);
+//! assert_eq!(errors.errors()[0].location(), "/email");
+//! ```
+
+use crate::rules::{self, Number, Timestamp};
+use crate::{traits, Validate, ValidationError, ValidationErrors, Validator};
+use serde::Serialize;
+
+type FieldFn<T> = Box<dyn Fn(&T, &mut ValidationErrors) + Send + Sync>;
+
+type SchemaFn<T> = Box<dyn Fn(&T) -> Result<(), ValidationErrors> + Send + Sync>;
+
+/// Validates values of `T` with the rules of its fields and schema validation functions.
+pub struct Rules<T: ?Sized> {
+    fields: Vec<FieldFn<T>>,
+    schema: Vec<SchemaFn<T>>,
+}
+
+impl<T: ?Sized> Default for Rules<T> {
+    fn default() -> Self {
+        Self {
+            fields: vec![],
+            schema: vec![],
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Rules<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rules of the field obtained with `get`. Errors are named after and located at `name`.
+    pub fn field<V, F>(mut self, name: &'static str, get: F, rules: impl IntoFieldRules<V>) -> Self
+    where
+        V: ?Sized + 'static,
+        F: Fn(&T) -> &V + Send + Sync + 'static,
+    {
+        let rules = rules.into_field_rules();
+        self.fields.push(Box::new(move |value, errors| {
+            let field = get(value);
+            for rule in rules.iter() {
+                rule.apply(field, name, None, errors);
+            }
+        }));
+        self
+    }
+
+    /// Adds the rules of an optional field, which are only checked if `get` returns a value.
+    pub fn optional<V, F>(
+        mut self,
+        name: &'static str,
+        get: F,
+        rules: impl IntoFieldRules<V>,
+    ) -> Self
+    where
+        V: ?Sized + 'static,
+        F: Fn(&T) -> Option<&V> + Send + Sync + 'static,
+    {
+        let rules = rules.into_field_rules();
+        self.fields.push(Box::new(move |value, errors| {
+            let Some(field) = get(value) else {
+                return;
+            };
+            for rule in rules.iter() {
+                rule.apply(field, name, None, errors);
+            }
+        }));
+        self
+    }
+
+    /// Adds a schema validation function, which runs after the field rules.
+    pub fn schema<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), ValidationErrors> + Send + Sync + 'static,
+    {
+        self.schema.push(Box::new(f));
+        self
+    }
+
+    /// Runs the rules of all fields followed by the schema validation.
+    pub fn validate(&self, value: &T) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        for field in self.fields.iter() {
+            field(value, &mut errors);
+        }
+
+        for schema in self.schema.iter() {
+            if let Err(errs) = schema(value) {
+                errors.merge(errs);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Converts the rules into a validation function.
+    pub fn into_fn(self) -> SchemaFn<T> {
+        Box::new(move |value| self.validate(value))
+    }
+}
+
+type CheckFn<V> = Box<dyn Fn(&V) -> Result<(), ValidationError> + Send + Sync>;
+
+type IterFn<V> = Box<dyn Fn(&V, &'static str, Option<&str>, &mut ValidationErrors) + Send + Sync>;
+
+type NestedFn<V> = Box<dyn Fn(&V) -> Result<(), ValidationErrors> + Send + Sync>;
+
+enum Kind<V: ?Sized> {
+    Length(
+        rules::Length,
+        fn(&rules::Length, &V) -> Result<(), ValidationError>,
+    ),
+    Range(
+        rules::Range,
+        fn(&rules::Range, &V) -> Result<(), ValidationError>,
+    ),
+    Check(CheckFn<V>),
+    Nested(NestedFn<V>),
+    Iter(IterFn<V>),
+}
+
+/// A validator of a field of type `V`, created with the functions of this module.
+pub struct FieldRule<V: ?Sized> {
+    kind: Kind<V>,
+    code: Option<&'static str>,
+    message: Option<String>,
+}
+
+impl<V: ?Sized + 'static> FieldRule<V> {
+    fn new(kind: Kind<V>) -> Self {
+        Self {
+            kind,
+            code: None,
+            message: None,
+        }
+    }
+
+    fn check(f: impl Fn(&V) -> Result<(), ValidationError> + Send + Sync + 'static) -> Self {
+        Self::new(Kind::Check(Box::new(f)))
+    }
+
+    /// Overrides the code of the errors.
+    pub fn code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the message of the errors.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Runs the rule, naming the errors after the field and locating them at it, and at the element
+    /// index if the value is an element of the field.
+    fn apply(
+        &self,
+        value: &V,
+        field: &'static str,
+        index: Option<&str>,
+        errors: &mut ValidationErrors,
+    ) {
+        let result = match self.kind {
+            Kind::Length(ref length, check) => check(length, value),
+            Kind::Range(ref range, check) => check(range, value),
+            Kind::Check(ref check) => check(value),
+            Kind::Nested(ref validate) => {
+                if let Err(mut errs) = validate(value) {
+                    for err in errs.errors_mut() {
+                        if err.field_name().is_none() {
+                            err.set_field(field);
+                        }
+                        if let Some(index) = index {
+                            err.set_location(index);
+                        }
+                        err.set_location(field);
+                        self.override_error(err);
+                    }
+                    errors.merge(errs);
+                }
+                return;
+            }
+            Kind::Iter(ref iter) => {
+                let mut errs = ValidationErrors::new();
+                iter(value, field, index, &mut errs);
+                for err in errs.errors_mut() {
+                    self.override_error(err);
+                }
+                errors.merge(errs);
+                return;
+            }
+        };
+
+        let Err(mut err) = result else {
+            return;
+        };
+
+        self.override_error(&mut err);
+
+        // Errors named by custom validators are located at their name
+        if let Some(name) = err.field_name().map(str::to_string) {
+            err.set_location(name);
+        } else {
+            err.set_field(field);
+            if let Some(index) = index {
+                err.set_location(index);
+            }
+            err.set_location(field);
+        }
+
+        errors.add(err);
+    }
+
+    fn override_error(&self, err: &mut ValidationError) {
+        if let Some(code) = self.code {
+            err.set_code(code);
+        }
+        if let Some(ref message) = self.message {
+            err.set_message(message.clone());
+        }
+    }
+}
+
+/// Validates with [rules::Email].
+pub fn email<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::Email.check(value))
+}
+
+/// Validates with [rules::Url].
+pub fn url<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::Url.check(value))
+}
+
+/// Validates with [rules::Phone].
+pub fn phone<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::Phone.check(value))
+}
+
+/// Validates with [rules::CreditCard].
+pub fn credit_card<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::CreditCard.check(value))
+}
+
+/// Validates with [rules::NonControlChar].
+pub fn non_control_char<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::NonControlChar.check(value))
+}
+
+/// Validates with [rules::NoHtml].
+pub fn no_html<V: AsRef<str> + Serialize + ?Sized + 'static>() -> FieldRule<V> {
+    FieldRule::check(|value| rules::NoHtml.check(value))
+}
+
+/// Validates with [rules::Ip] of the given version, or of any if `None`.
+pub fn ip<V: AsRef<str> + Serialize + ?Sized + 'static>(
+    format: Option<rules::IpFormat>,
+) -> FieldRule<V> {
+    FieldRule::check(move |value| rules::Ip { format }.check(value))
+}
+
+/// Validates with [rules::Regex].
+pub fn regex<V: AsRef<str> + Serialize + ?Sized + 'static>(
+    regex: &'static regex::Regex,
+) -> FieldRule<V> {
+    FieldRule::check(move |value| rules::Regex(regex).check(value))
+}
+
+/// Validates with [rules::Length], whose bounds are set with [LengthRule::min], [LengthRule::max]
+/// and [LengthRule::equal].
+pub fn length<V: ?Sized + 'static>() -> LengthRule<V>
+where
+    for<'a> &'a V: traits::Length,
+{
+    LengthRule {
+        length: Default::default(),
+        check: |length, value| length.check(value),
+        code: None,
+        message: None,
+    }
+}
+
+/// Validates with [rules::Range], whose bounds are set with [RangeRule::min] and [RangeRule::max].
+pub fn range<V: Number + 'static>() -> RangeRule<V> {
+    RangeRule {
+        range: Default::default(),
+        check: |range, value| range.check(value),
+        code: None,
+        message: None,
+    }
+}
+
+/// Validates with [rules::Contains].
+pub fn contains<V, C>(value: C) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    C: Serialize + Send + Sync + 'static,
+    for<'a> &'a V: traits::Contains<C>,
+{
+    let rule = rules::Contains { value, not: false };
+    FieldRule::check(move |value| rule.check(value))
+}
+
+/// Validates with [rules::Contains] with `not` set.
+pub fn contains_not<V, C>(value: C) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    C: Serialize + Send + Sync + 'static,
+    for<'a> &'a V: traits::Contains<C>,
+{
+    let rule = rules::Contains { value, not: true };
+    FieldRule::check(move |value| rule.check(value))
+}
+
+/// Validates with [rules::In]. As in the derive, the collection is checked for a reference to the value.
+pub fn is_in<V, C>(collection: C) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    C: Copy + for<'a> traits::Contains<&'a V> + Send + Sync + 'static,
+{
+    let rule = rules::In {
+        collection,
+        not: false,
+    };
+    FieldRule::check(move |value| rule.check(&value))
+}
+
+/// Validates with [rules::In] with `not` set.
+pub fn not_in<V, C>(collection: C) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    C: Copy + for<'a> traits::Contains<&'a V> + Send + Sync + 'static,
+{
+    let rule = rules::In {
+        collection,
+        not: true,
+    };
+    FieldRule::check(move |value| rule.check(&value))
+}
+
+/// Validates with [rules::Time].
+pub fn time<V: Timestamp + Send + Sync + 'static>(rule: rules::Time<V>) -> FieldRule<V> {
+    FieldRule::check(move |value| rule.check(value))
+}
+
+/// Validates with [rules::Required].
+pub fn required<V: 'static>() -> FieldRule<Option<V>> {
+    FieldRule::check(|value| rules::Required.check(value))
+}
+
+/// Validates with a function. Errors with a field name are located at it, as with `custom` in the derive.
+pub fn custom<V, F>(f: F) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    F: Fn(&V) -> Result<(), ValidationError> + Send + Sync + 'static,
+{
+    FieldRule::check(f)
+}
+
+/// Validates with a [Validator].
+pub fn with<V, R>(validator: R) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    R: Validator<V> + Send + Sync + 'static,
+{
+    FieldRule::check(move |value| validator.validate(value))
+}
+
+/// Validates the field with its [Validate] implementation, like `#[validate]` in the derive.
+pub fn nested<V: Validate + 'static>() -> FieldRule<V> {
+    FieldRule::new(Kind::Nested(Box::new(V::validate)))
+}
+
+/// Validates the field with the given [Rules], for nested types which do not implement [Validate].
+/// Errors are located at the field like the ones of [nested].
+pub fn nested_with<V: ?Sized + 'static>(rules: Rules<V>) -> FieldRule<V> {
+    FieldRule::new(Kind::Nested(Box::new(move |value| rules.validate(value))))
+}
+
+/// Validates the elements of a collection, locating errors by their index.
+pub fn iter<V, E>(rules: impl IntoFieldRules<E>) -> FieldRule<V>
+where
+    V: ?Sized + 'static,
+    E: ?Sized + 'static,
+    for<'a> &'a V: IntoIterator<Item = &'a E>,
+{
+    let rules = rules.into_field_rules();
+    FieldRule::new(Kind::Iter(Box::new(move |value, field, index, errors| {
+        for (i, el) in value.into_iter().enumerate() {
+            let index = match index {
+                Some(index) => format!("{index}/{i}"),
+                None => i.to_string(),
+            };
+            for rule in rules.iter() {
+                rule.apply(el, field, Some(&index), errors);
+            }
+        }
+    })))
+}
+
+/// A [length] rule. Its bounds are only available on this type, before it is converted to a [FieldRule].
+pub struct LengthRule<V: ?Sized> {
+    length: rules::Length,
+    check: fn(&rules::Length, &V) -> Result<(), ValidationError>,
+    code: Option<&'static str>,
+    message: Option<String>,
+}
+
+impl<V: ?Sized + 'static> LengthRule<V> {
+    /// Sets the minimum length.
+    pub fn min(mut self, min: usize) -> Self {
+        self.length.min = Some(min);
+        self
+    }
+
+    /// Sets the maximum length.
+    pub fn max(mut self, max: usize) -> Self {
+        self.length.max = Some(max);
+        self
+    }
+
+    /// Sets the exact length.
+    pub fn equal(mut self, equal: usize) -> Self {
+        self.length.equal = Some(equal);
+        self
+    }
+
+    /// Overrides the code of the errors.
+    pub fn code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the message of the errors.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// A [range] rule. Its bounds are only available on this type, before it is converted to a [FieldRule].
+pub struct RangeRule<V: ?Sized> {
+    range: rules::Range,
+    check: fn(&rules::Range, &V) -> Result<(), ValidationError>,
+    code: Option<&'static str>,
+    message: Option<String>,
+}
+
+impl<V: ?Sized + 'static> RangeRule<V> {
+    /// Sets the minimum value.
+    pub fn min(mut self, min: impl Number) -> Self {
+        self.range.min = Some(min.to_f64());
+        self
+    }
+
+    /// Sets the maximum value.
+    pub fn max(mut self, max: impl Number) -> Self {
+        self.range.max = Some(max.to_f64());
+        self
+    }
+
+    /// Overrides the code of the errors.
+    pub fn code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the message of the errors.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// Rules of a field of type `V`, i.e. [FieldRule], [LengthRule] and [RangeRule].
+pub trait IntoFieldRule<V: ?Sized> {
+    fn into_field_rule(self) -> FieldRule<V>;
+}
+
+impl<V: ?Sized> IntoFieldRule<V> for FieldRule<V> {
+    fn into_field_rule(self) -> FieldRule<V> {
+        self
+    }
+}
+
+impl<V: ?Sized> From<LengthRule<V>> for FieldRule<V> {
+    fn from(rule: LengthRule<V>) -> Self {
+        FieldRule {
+            kind: Kind::Length(rule.length, rule.check),
+            code: rule.code,
+            message: rule.message,
+        }
+    }
+}
+
+impl<V: ?Sized> IntoFieldRule<V> for LengthRule<V> {
+    fn into_field_rule(self) -> FieldRule<V> {
+        self.into()
+    }
+}
+
+impl<V: ?Sized> From<RangeRule<V>> for FieldRule<V> {
+    fn from(rule: RangeRule<V>) -> Self {
+        FieldRule {
+            kind: Kind::Range(rule.range, rule.check),
+            code: rule.code,
+            message: rule.message,
+        }
+    }
+}
+
+impl<V: ?Sized> IntoFieldRule<V> for RangeRule<V> {
+    fn into_field_rule(self) -> FieldRule<V> {
+        self.into()
+    }
+}
+
+/// The rules of a field of type `V`. Implemented for arrays and vectors of rules of the same kind,
+/// and for tuples of up to 8 rules of different kinds.
+pub trait IntoFieldRules<V: ?Sized> {
+    fn into_field_rules(self) -> Vec<FieldRule<V>>;
+}
+
+impl<V: ?Sized, R: IntoFieldRule<V>, const N: usize> IntoFieldRules<V> for [R; N] {
+    fn into_field_rules(self) -> Vec<FieldRule<V>> {
+        self.into_iter()
+            .map(IntoFieldRule::into_field_rule)
+            .collect()
+    }
+}
+
+impl<V: ?Sized, R: IntoFieldRule<V>> IntoFieldRules<V> for Vec<R> {
+    fn into_field_rules(self) -> Vec<FieldRule<V>> {
+        self.into_iter()
+            .map(IntoFieldRule::into_field_rule)
+            .collect()
+    }
+}
+
+macro_rules! impl_into_field_rules {
+    ($($rule:ident),+) => {
+        impl<V: ?Sized, $($rule: IntoFieldRule<V>),+> IntoFieldRules<V> for ($($rule,)+) {
+            #[allow(non_snake_case)]
+            fn into_field_rules(self) -> Vec<FieldRule<V>> {
+                let ($($rule,)+) = self;
+                vec![$($rule.into_field_rule()),+]
+            }
+        }
+    };
+}
+
+impl_into_field_rules!(A);
+impl_into_field_rules!(A, B);
+impl_into_field_rules!(A, B, C);
+impl_into_field_rules!(A, B, C, D);
+impl_into_field_rules!(A, B, C, D, E);
+impl_into_field_rules!(A, B, C, D, E, F);
+impl_into_field_rules!(A, B, C, D, E, F, G);
+impl_into_field_rules!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        tags: Vec<String>,
+        price: f64,
+        note: Option<String>,
+    }
+
+    fn rules() -> Rules<Item> {
+        Rules::new()
+            .field(
+                "tags",
+                |item: &Item| &item.tags,
+                (length().max(2), iter([length().min(1).code("empty_tag")])),
+            )
+            .field("price", |item| &item.price, [range().min(0).max(100)])
+            .optional("note", |item| item.note.as_deref(), [contains_not("<")])
+    }
+
+    #[test]
+    fn test_field_rules() {
+        let item = Item {
+            tags: vec!["a".to_string()],
+            price: 1.,
+            note: None,
+        };
+        assert!(rules().validate(&item).is_ok());
+
+        let item = Item {
+            tags: vec!["a".to_string(), String::new(), String::new()],
+            price: -1.,
+            note: Some("<b>".to_string()),
+        };
+        let errors = rules().into_fn()(&item).unwrap_err();
+        let errors = errors.errors();
+
+        assert_eq!(errors.len(), 5);
+        assert_eq!(errors[0].code(), "length");
+        assert_eq!(errors[0].location(), "/tags");
+        assert_eq!(errors[1].code(), "empty_tag");
+        assert_eq!(errors[1].location(), "/tags/1");
+        assert_eq!(errors[1].field_name(), Some("tags"));
+        assert_eq!(errors[2].location(), "/tags/2");
+        assert_eq!(errors[3].code(), "range");
+        assert_eq!(errors[3].params()["min"], 0.);
+        assert_eq!(errors[4].code(), "contains_not");
+        assert_eq!(errors[4].location(), "/note");
+    }
+
+    #[test]
+    fn test_custom_named_errors() {
+        let rules = Rules::<Item>::new().field(
+            "price",
+            |item| &item.price,
+            [
+                custom(|_: &f64| Err(ValidationError::new_field_named("cost", "invalid")))
+                    .message("Bad price"),
+            ],
+        );
+        let item = Item {
+            tags: vec![],
+            price: 0.,
+            note: None,
+        };
+        let errors = rules.validate(&item).unwrap_err();
+        assert_eq!(errors.errors()[0].location(), "/cost");
+        assert_eq!(errors.errors()[0].message().as_deref(), Some("Bad price"));
+    }
+
+    #[test]
+    fn test_mixed_rules_array() {
+        let rules = Rules::<Item>::new()
+            .field(
+                "tags",
+                |item| &item.tags,
+                [iter([length().min(1)]), length().max(1).into()],
+            )
+            .field(
+                "price",
+                |item| &item.price,
+                [range().max(10).into(), custom(|_: &f64| Ok(()))],
+            );
+        let item = Item {
+            tags: vec![String::new(), "a".to_string()],
+            price: 11.,
+            note: None,
+        };
+        let errors = rules.validate(&item).unwrap_err();
+        let codes: Vec<_> = errors.errors().iter().map(|e| e.code()).collect();
+        assert_eq!(codes, ["length", "length", "range"]);
+        assert_eq!(errors.errors()[0].location(), "/tags/0");
+    }
+
+    #[test]
+    fn test_nested_with() {
+        struct Order {
+            item: Item,
+        }
+
+        let rules =
+            Rules::<Order>::new().field("item", |order| &order.item, [nested_with(rules())]);
+        let order = Order {
+            item: Item {
+                tags: vec![],
+                price: 101.,
+                note: None,
+            },
+        };
+        let errors = rules.validate(&order).unwrap_err();
+        assert_eq!(errors.errors()[0].code(), "range");
+        assert_eq!(errors.errors()[0].location(), "/item/price");
+        assert_eq!(errors.errors()[0].field_name(), Some("price"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Used by the derive macro to limit the depth of nested validation and modification of
+//! fields annotated with `#[validate(max_depth = N)]` and of other nested fields, which get
+//! a limit of [DEFAULT_MAX_DEPTH].
+//!
+//! The depth is tracked per thread and per type, counting the guarded nested values of the type
+//! currently being processed. Limits of different types do not affect each other, while a recursive
+//! type cannot be nested deeper than the smallest limit of its guarded fields.
+
+use std::cell::RefCell;
+
+/// The depth limit of nested fields which are not annotated with `max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+thread_local! {
+    static DEPTHS: RefCell<Vec<(&'static str, usize)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Decrements the depth of its key when dropped.
+pub struct DepthGuard(&'static str);
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTHS.with(|depths| {
+            let mut depths = depths.borrow_mut();
+            if let Some(i) = depths.iter().position(|(key, _)| *key == self.0) {
+                depths[i].1 -= 1;
+                if depths[i].1 == 0 {
+                    depths.swap_remove(i);
+                }
+            }
+        })
+    }
+}
+
+/// Enters a nested value, returning `None` if the current depth of the `key`, usually the name of
+/// the type containing the guarded field, already reached `max`.
+pub fn enter(key: &'static str, max: usize) -> Option<DepthGuard> {
+    DEPTHS.with(|depths| {
+        let mut depths = depths.borrow_mut();
+        match depths.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, depth)) if *depth >= max => return None,
+            Some((_, depth)) => *depth += 1,
+            None if max == 0 => return None,
+            None => depths.push((key, 1)),
+        }
+        Some(DepthGuard(key))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::enter;
+
+    #[test]
+    fn test_enter() {
+        let first = enter("a", 2);
+        assert!(first.is_some());
+        {
+            let _second = enter("a", 2).unwrap();
+            assert!(enter("a", 2).is_none());
+            assert!(enter("a", 3).is_some());
+        }
+        assert!(enter("a", 2).is_some());
+        drop(first);
+        assert!(enter("a", 1).is_some());
+        assert!(enter("a", 0).is_none());
+    }
+
+    #[test]
+    fn test_enter_per_key() {
+        let _a = enter("a", 1).unwrap();
+        assert!(enter("a", 1).is_none());
+
+        // Other keys have their own depth
+        let _b = enter("b", 1).unwrap();
+        assert!(enter("b", 1).is_none());
+        drop(_b);
+        assert!(enter("b", 1).is_some());
+    }
+}
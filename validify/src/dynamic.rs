@@ -0,0 +1,810 @@
+//! Validation of [serde_json::Value]s with rules defined at runtime.
+//!
+//! A [Spec] describes the fields of a document by their JSON pointers, together with their rules,
+//! nested fields and array elements. It can be deserialized from any format, with [Spec::from_json],
+//! and `Spec::from_yaml` and `Spec::from_toml` behind the `yaml` and `toml` features, for convenience.
+//! Specs are compiled once into a [Schema].
+//!
+//! The rules are checked with the validators of [rules][crate::rules], producing the same codes and
+//! parameters as the derived validation. Missing and `null` fields are only checked by `required`,
+//! while values of the wrong type fail with the `type` code and the `expected` parameter, once per field.
+//!
+//! ```
+//! use validify::dynamic::Spec;
+//! use serde_json::json;
+//!
+//! let spec = r#"{
+//!     "fields": [
+//!         { "field": "email", "rules": [{ "rule": "required" }, { "rule": "email" }] },
+//!         { "field": "age", "rules": [{ "rule": "range", "min": 18 }] },
+//!         {
+//!             "field": "tags",
+//!             "rules": [{ "rule": "length", "max": 3 }],
+//!             "items": { "rules": [{ "rule": "length", "min": 1, "code": "empty_tag" }] }
+//!         },
+//!         { "field": "address/zip", "rules": [{ "rule": "regex", "pattern": "^[0-9]{5}$" }] }
+//!     ]
+//! }"#;
+//!
+//! let schema = Spec::from_json(spec).unwrap().compile().unwrap();
+//!
+//! let document = json!({
+//!     "age": 16,
+//!     "tags": ["rust", ""],
+//!     "address": { "zip": "1234" }
+//! });
+//!
+//! let errors = schema.validate(&document).unwrap_err();
+//! let errors = errors.errors();
+//! assert_eq!(errors[0].code(), "required");
+//! assert_eq!(errors[1].params()["min"], 18.);
+//! assert_eq!(errors[2].code(), "empty_tag");
+//! assert_eq!(errors[2].location(), "/tags/1");
+//! assert_eq!(errors[3].location(), "/address/zip");
+//! assert_eq!(errors[3].field_name(), Some("zip"));
+//! ```
+//!
+//! Field names and custom codes are interned when compiling, as errors refer to them statically.
+//! Each distinct string is allocated once and never freed, so recompiling specs does not allocate
+//! them again, but every new name or code costs memory for the lifetime of the program. Specs coming
+//! from untrusted sources, e.g. user defined forms, should have their names and codes bounded
+//! before compiling, as a stream of unique names grows the process without limit.
+
+use crate::rules::{self, IpFormat, Timestamp};
+use crate::{ValidationError, ValidationErrors};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// The fields of a document and their rules.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Spec {
+    #[serde(default)]
+    pub fields: Vec<FieldSpec>,
+}
+
+/// A field of an object, located by its JSON pointer relative to the object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    /// The JSON pointer of the field, e.g. `/address/street`. The leading `/` can be omitted.
+    pub field: String,
+
+    #[serde(flatten)]
+    pub value: ValueSpec,
+}
+
+/// The rules of a value, and of its fields or elements if it is an object or array.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValueSpec {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<RuleSpec>,
+
+    /// The fields of the value if it is an object.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldSpec>,
+
+    /// The spec of the elements of the value if it is an array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<ValueSpec>>,
+}
+
+/// A rule with an optional code and message overriding the ones of its errors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleSpec {
+    #[serde(flatten)]
+    pub kind: RuleKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The validators available in specs, tagged by `rule`. The parameters follow the ones of the
+/// validation attributes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RuleKind {
+    Required,
+    Email,
+    Url,
+    Phone,
+    CreditCard,
+    NonControlChar,
+    NoHtml,
+    Ip {
+        format: Option<IpFormat>,
+    },
+    /// Applies to strings and arrays.
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+        equal: Option<usize>,
+    },
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// Checks whether a string contains a substring, or an array contains an element.
+    Contains {
+        value: Value,
+        #[serde(default)]
+        not: bool,
+    },
+    /// Checks whether the value is one of `values`.
+    In {
+        values: Vec<Value>,
+        #[serde(default)]
+        not: bool,
+    },
+    Regex {
+        pattern: String,
+    },
+    /// Checks dates, or datetimes if `time` is set, parsed with `format`. Defaults to `%Y-%m-%d`
+    /// and `%Y-%m-%dT%H:%M:%S` respectively.
+    Time {
+        op: TimeOp,
+        target: Option<String>,
+        format: Option<String>,
+        /// The duration of the `*_from_now` and `in_period` operators.
+        seconds: Option<i64>,
+        #[serde(default)]
+        inclusive: bool,
+        #[serde(default)]
+        time: bool,
+    },
+}
+
+/// The operators of the `time` rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeOp {
+    BeforeNow,
+    AfterNow,
+    Before,
+    After,
+    BeforeFromNow,
+    AfterFromNow,
+    InPeriod,
+}
+
+/// Errors occurring while loading or compiling a [Spec].
+#[derive(Debug)]
+pub enum SpecError {
+    /// The spec could not be deserialized.
+    Parse(String),
+    /// A field has an empty pointer.
+    InvalidPointer(String),
+    /// A rule has missing or invalid parameters.
+    InvalidRule {
+        field: String,
+        rule: &'static str,
+        reason: String,
+    },
+}
+
+impl Display for SpecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecError::Parse(err) => write!(f, "invalid spec: {err}"),
+            SpecError::InvalidPointer(field) => write!(f, "invalid field pointer: '{field}'"),
+            SpecError::InvalidRule {
+                field,
+                rule,
+                reason,
+            } => write!(f, "invalid rule '{rule}' of field '{field}': {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+impl Spec {
+    pub fn from_json(spec: &str) -> Result<Self, SpecError> {
+        serde_json::from_str(spec).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(spec: &str) -> Result<Self, SpecError> {
+        serde_yaml_ng::from_str(spec).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml(spec: &str) -> Result<Self, SpecError> {
+        toml::from_str(spec).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    /// Compiles the rules, checking their parameters and compiling patterns and time targets.
+    pub fn compile(&self) -> Result<Schema, SpecError> {
+        Ok(Schema {
+            fields: compile_fields(&self.fields)?,
+        })
+    }
+}
+
+/// A compiled [Spec] validating documents.
+#[derive(Debug)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn validate(&self, document: &Value) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        validate_fields(&self.fields, document, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    pointer: String,
+    name: &'static str,
+    value: CompiledValue,
+}
+
+#[derive(Debug)]
+struct CompiledValue {
+    rules: Vec<Rule>,
+    fields: Vec<Field>,
+    items: Option<Box<CompiledValue>>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    check: Check,
+    code: Option<&'static str>,
+    message: Option<String>,
+}
+
+#[derive(Debug)]
+enum Check {
+    Required,
+    Email,
+    Url,
+    Phone,
+    CreditCard,
+    NonControlChar,
+    NoHtml,
+    Ip(rules::Ip),
+    Length(rules::Length),
+    Range(rules::Range),
+    Contains { value: Value, not: bool },
+    In { values: Vec<Value>, not: bool },
+    Regex(regex::Regex),
+    Date(rules::Time<NaiveDate>, String),
+    DateTime(rules::Time<NaiveDateTime>, String),
+}
+
+/// Returns the static copy of the string, leaking it the first time it is interned.
+/// The leaked strings are never reclaimed, see the module docs.
+fn intern(s: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut interned = INTERNED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    match interned.get(s) {
+        Some(s) => s,
+        None => {
+            let s = Box::leak(Box::from(s));
+            interned.insert(s);
+            s
+        }
+    }
+}
+
+fn compile_fields(fields: &[FieldSpec]) -> Result<Vec<Field>, SpecError> {
+    fields
+        .iter()
+        .map(|field| {
+            let pointer = match field.field.as_str() {
+                "" | "/" => return Err(SpecError::InvalidPointer(field.field.clone())),
+                p if p.starts_with('/') => p.to_string(),
+                p => format!("/{p}"),
+            };
+
+            // The pointer segments are escaped, but the field name is not
+            let name = pointer
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .replace("~1", "/")
+                .replace("~0", "~");
+
+            Ok(Field {
+                value: compile_value(&field.value, &pointer)?,
+                name: intern(&name),
+                pointer,
+            })
+        })
+        .collect()
+}
+
+fn compile_value(spec: &ValueSpec, pointer: &str) -> Result<CompiledValue, SpecError> {
+    Ok(CompiledValue {
+        rules: spec
+            .rules
+            .iter()
+            .map(|rule| compile_rule(rule, pointer))
+            .collect::<Result<_, _>>()?,
+        fields: compile_fields(&spec.fields)?,
+        items: match spec.items {
+            Some(ref items) => Some(Box::new(compile_value(items, pointer)?)),
+            None => None,
+        },
+    })
+}
+
+fn compile_rule(spec: &RuleSpec, pointer: &str) -> Result<Rule, SpecError> {
+    let check = match spec.kind {
+        RuleKind::Required => Check::Required,
+        RuleKind::Email => Check::Email,
+        RuleKind::Url => Check::Url,
+        RuleKind::Phone => Check::Phone,
+        RuleKind::CreditCard => Check::CreditCard,
+        RuleKind::NonControlChar => Check::NonControlChar,
+        RuleKind::NoHtml => Check::NoHtml,
+        RuleKind::Ip { format } => Check::Ip(rules::Ip { format }),
+        RuleKind::Length { min, max, equal } => Check::Length(rules::Length { min, max, equal }),
+        RuleKind::Range { min, max } => Check::Range(rules::Range { min, max }),
+        RuleKind::Contains { ref value, not } => Check::Contains {
+            value: value.clone(),
+            not,
+        },
+        RuleKind::In { ref values, not } => Check::In {
+            values: values.clone(),
+            not,
+        },
+        RuleKind::Regex { ref pattern } => match regex::Regex::new(pattern) {
+            Ok(regex) => Check::Regex(regex),
+            Err(e) => return Err(invalid_rule(pointer, "regex", e)),
+        },
+        RuleKind::Time {
+            op,
+            ref target,
+            ref format,
+            seconds,
+            inclusive,
+            time,
+        } => {
+            let target = target.as_deref();
+            if time {
+                let format = format.clone().unwrap_or(NaiveDateTime::FORMAT.to_string());
+                let rule = time_rule(op, target, &format, seconds, inclusive, pointer)?;
+                Check::DateTime(rule, format)
+            } else {
+                let format = format.clone().unwrap_or(NaiveDate::FORMAT.to_string());
+                let rule = time_rule(op, target, &format, seconds, inclusive, pointer)?;
+                Check::Date(rule, format)
+            }
+        }
+    };
+
+    Ok(Rule {
+        check,
+        code: spec.code.as_deref().map(intern),
+        message: spec.message.clone(),
+    })
+}
+
+fn invalid_rule(pointer: &str, rule: &'static str, reason: impl Display) -> SpecError {
+    SpecError::InvalidRule {
+        field: pointer.to_string(),
+        rule,
+        reason: reason.to_string(),
+    }
+}
+
+/// Timestamps parsed from the strings of documents.
+trait Parse: Timestamp {
+    const FORMAT: &'static str;
+
+    fn parse(value: &str, format: &str) -> Option<Self>;
+}
+
+impl Parse for NaiveDate {
+    const FORMAT: &'static str = "%Y-%m-%d";
+
+    fn parse(value: &str, format: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(value, format).ok()
+    }
+}
+
+impl Parse for NaiveDateTime {
+    const FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S";
+
+    fn parse(value: &str, format: &str) -> Option<Self> {
+        NaiveDateTime::parse_from_str(value, format).ok()
+    }
+}
+
+fn time_rule<T: Parse>(
+    op: TimeOp,
+    target: Option<&str>,
+    format: &str,
+    seconds: Option<i64>,
+    inclusive: bool,
+    pointer: &str,
+) -> Result<rules::Time<T>, SpecError> {
+    let target = || match target {
+        Some(target) => T::parse(target, format).ok_or_else(|| {
+            invalid_rule(
+                pointer,
+                "time",
+                format!("target '{target}' does not match the format '{format}'"),
+            )
+        }),
+        None => Err(invalid_rule(pointer, "time", "missing target")),
+    };
+
+    let duration = || match seconds {
+        Some(seconds) => Duration::try_seconds(seconds)
+            .ok_or_else(|| invalid_rule(pointer, "time", "duration out of range")),
+        None => Err(invalid_rule(pointer, "time", "missing seconds")),
+    };
+
+    Ok(match op {
+        TimeOp::BeforeNow => rules::Time::BeforeNow { inclusive },
+        TimeOp::AfterNow => rules::Time::AfterNow { inclusive },
+        TimeOp::Before => rules::Time::Before {
+            target: target()?,
+            inclusive,
+        },
+        TimeOp::After => rules::Time::After {
+            target: target()?,
+            inclusive,
+        },
+        TimeOp::BeforeFromNow => rules::Time::BeforeFromNow {
+            duration: duration()?,
+        },
+        TimeOp::AfterFromNow => rules::Time::AfterFromNow {
+            duration: duration()?,
+        },
+        TimeOp::InPeriod => rules::Time::InPeriod {
+            start: target()?,
+            duration: duration()?,
+        },
+    })
+}
+
+/// Validates the fields of the value, locating errors at their pointers.
+fn validate_fields(fields: &[Field], value: &Value, errors: &mut ValidationErrors) {
+    for field in fields {
+        let mut errs = ValidationErrors::new();
+        field
+            .value
+            .validate(value.pointer(&field.pointer), field.name, &mut errs);
+        for err in errs.errors_mut() {
+            err.set_location(&field.pointer[1..]);
+        }
+        errors.merge(errs);
+    }
+}
+
+impl CompiledValue {
+    /// Validates the value, locating errors relative to it.
+    fn validate(&self, value: Option<&Value>, name: &'static str, errors: &mut ValidationErrors) {
+        let value = value.filter(|value| !value.is_null());
+
+        // A value of the wrong type fails all of its rules, so the mismatch is only reported once
+        let mut mismatched = false;
+
+        for rule in self.rules.iter() {
+            if let Err(mut err) = rule.check(value) {
+                if err.code() == TYPE {
+                    if mismatched {
+                        continue;
+                    }
+                    mismatched = true;
+                }
+                if let Some(code) = rule.code {
+                    err.set_code(code);
+                }
+                if let Some(ref message) = rule.message {
+                    err.set_message(message.clone());
+                }
+                err.set_field(name);
+                errors.add(err);
+            }
+        }
+
+        let Some(value) = value else {
+            return;
+        };
+
+        if !self.fields.is_empty() {
+            if value.is_object() {
+                validate_fields(&self.fields, value, errors);
+            } else if !mismatched {
+                mismatched = true;
+                let mut err = type_error("object");
+                err.set_field(name);
+                errors.add(err);
+            }
+        }
+
+        if let Some(ref items) = self.items {
+            let Value::Array(elements) = value else {
+                if !mismatched {
+                    let mut err = type_error("array");
+                    err.set_field(name);
+                    errors.add(err);
+                }
+                return;
+            };
+            for (i, element) in elements.iter().enumerate() {
+                let mut errs = ValidationErrors::new();
+                items.validate(Some(element), name, &mut errs);
+                for err in errs.errors_mut() {
+                    err.set_location(i);
+                }
+                errors.merge(errs);
+            }
+        }
+    }
+}
+
+impl Rule {
+    fn check(&self, value: Option<&Value>) -> Result<(), ValidationError> {
+        let value = match (&self.check, value) {
+            (Check::Required, value) => return rules::Required.check(&value),
+            (_, None) => return Ok(()),
+            (_, Some(value)) => value,
+        };
+
+        match self.check {
+            Check::Required => Ok(()),
+            Check::Email => rules::Email.check(as_str(value)?),
+            Check::Url => rules::Url.check(as_str(value)?),
+            Check::Phone => rules::Phone.check(as_str(value)?),
+            Check::CreditCard => rules::CreditCard.check(as_str(value)?),
+            Check::NonControlChar => rules::NonControlChar.check(as_str(value)?),
+            Check::NoHtml => rules::NoHtml.check(as_str(value)?),
+            Check::Ip(ref ip) => ip.check(as_str(value)?),
+            Check::Regex(ref regex) => rules::Regex(regex).check(as_str(value)?),
+            Check::Length(ref length) => match value {
+                Value::String(s) => length.check(s),
+                Value::Array(elements) => length.check(elements),
+                _ => Err(type_error("string or array")),
+            },
+            // Integers are kept as such so the actual value is reported like in the derive
+            Check::Range(ref range) => match value {
+                Value::Number(n) if n.is_i64() => range.check(&n.as_i64().unwrap_or_default()),
+                Value::Number(n) if n.is_u64() => range.check(&n.as_u64().unwrap_or_default()),
+                Value::Number(n) => range.check(&n.as_f64().unwrap_or_default()),
+                _ => Err(type_error("number")),
+            },
+            Check::Contains {
+                value: ref needle,
+                not,
+            } => match (value, needle) {
+                (Value::String(s), Value::String(needle)) => rules::Contains {
+                    value: needle.as_str(),
+                    not,
+                }
+                .check(s.as_str()),
+                (Value::Array(elements), needle) => {
+                    rules::Contains { value: needle, not }.check(elements)
+                }
+                _ => Err(type_error("string or array")),
+            },
+            Check::In { ref values, not } => rules::In {
+                collection: values,
+                not,
+            }
+            .check(value),
+            Check::Date(ref time, ref format) => check_time(time, format, value),
+            Check::DateTime(ref time, ref format) => check_time(time, format, value),
+        }
+    }
+}
+
+fn check_time<T: Parse>(
+    time: &rules::Time<T>,
+    format: &str,
+    value: &Value,
+) -> Result<(), ValidationError> {
+    match T::parse(as_str(value)?, format) {
+        Some(timestamp) => time.check(&timestamp),
+        None => Err(type_error("timestamp").with_param("format", &format)),
+    }
+}
+
+fn as_str(value: &Value) -> Result<&str, ValidationError> {
+    value.as_str().ok_or_else(|| type_error("string"))
+}
+
+const TYPE: &str = "type";
+
+fn type_error(expected: &'static str) -> ValidationError {
+    ValidationError::new_field(TYPE).with_param("expected", &expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(spec: Value) -> Schema {
+        serde_json::from_value::<Spec>(spec)
+            .unwrap()
+            .compile()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_missing_and_null_fields() {
+        let schema = schema(json!({
+            "fields": [
+                { "field": "a", "rules": [{ "rule": "email" }] },
+                { "field": "b", "rules": [{ "rule": "required", "message": "B is required" }] }
+            ]
+        }));
+
+        let errors = schema.validate(&json!({ "b": null })).unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "required");
+        assert_eq!(errors[0].location(), "/b");
+        assert_eq!(errors[0].message().as_deref(), Some("B is required"));
+
+        assert!(schema.validate(&json!({ "a": "a@b.c", "b": 1 })).is_ok());
+    }
+
+    #[test]
+    fn test_type_errors() {
+        let schema = schema(json!({
+            "fields": [
+                { "field": "a", "rules": [{ "rule": "url" }] },
+                { "field": "b", "fields": [{ "field": "c", "rules": [{ "rule": "required" }] }] },
+                { "field": "d", "rules": [{ "rule": "range", "max": 1 }] }
+            ]
+        }));
+
+        let errors = schema
+            .validate(&json!({ "a": 1, "b": [], "d": "1" }))
+            .unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|err| err.code() == "type"));
+        assert_eq!(errors[0].params()["expected"], "string");
+        assert_eq!(errors[1].params()["expected"], "object");
+        assert_eq!(errors[1].location(), "/b");
+        assert_eq!(errors[2].params()["expected"], "number");
+    }
+
+    #[test]
+    fn test_type_errors_once_per_field() {
+        let schema = schema(json!({
+            "fields": [
+                {
+                    "field": "a",
+                    "rules": [{ "rule": "email" }, { "rule": "length", "max": 1 }, { "rule": "url" }],
+                    "items": { "rules": [{ "rule": "email" }] }
+                },
+                { "field": "b", "rules": [{ "rule": "required" }, { "rule": "email" }] }
+            ]
+        }));
+
+        let errors = schema.validate(&json!({ "a": 1, "b": 2 })).unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].location(), "/a");
+        assert_eq!(errors[0].params()["expected"], "string");
+        assert_eq!(errors[1].location(), "/b");
+    }
+
+    #[test]
+    fn test_interned_strings() {
+        let spec = json!({
+            "fields": [{ "field": "name", "rules": [{ "rule": "email", "code": "contact" }] }]
+        });
+
+        let first = schema(spec.clone());
+        let second = schema(spec);
+        assert!(std::ptr::eq(first.fields[0].name, second.fields[0].name));
+        assert!(std::ptr::eq(
+            first.fields[0].value.rules[0].code.unwrap(),
+            second.fields[0].value.rules[0].code.unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_collections() {
+        let schema = schema(json!({
+            "fields": [
+                { "field": "role", "rules": [{ "rule": "in", "values": ["admin", "user"] }] },
+                { "field": "bio", "rules": [{ "rule": "contains", "value": "<", "not": true }] },
+                {
+                    "field": "matrix",
+                    "items": { "items": { "rules": [{ "rule": "range", "min": 0 }] } }
+                },
+                {
+                    "field": "addresses",
+                    "items": { "fields": [{ "field": "zip", "rules": [{ "rule": "length", "equal": 5 }] }] }
+                }
+            ]
+        }));
+
+        let document = json!({
+            "role": "root",
+            "bio": "<b>",
+            "matrix": [[1], [2, -1]],
+            "addresses": [{ "zip": "12345" }, { "zip": "1" }]
+        });
+
+        let errors = schema.validate(&document).unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 4);
+        assert_eq!(errors[0].code(), "in");
+        assert_eq!(errors[1].code(), "contains_not");
+        assert_eq!(errors[1].params()["target"], "<");
+        assert_eq!(errors[2].location(), "/matrix/1/1");
+        assert_eq!(errors[2].field_name(), Some("matrix"));
+        assert_eq!(errors[3].location(), "/addresses/1/zip");
+        assert_eq!(errors[3].field_name(), Some("zip"));
+    }
+
+    #[test]
+    fn test_time() {
+        let schema = schema(json!({
+            "fields": [
+                {
+                    "field": "date",
+                    "rules": [{ "rule": "time", "op": "after", "target": "2020-01-01", "inclusive": true }]
+                },
+                {
+                    "field": "datetime",
+                    "rules": [{ "rule": "time", "op": "before_now", "time": true }]
+                }
+            ]
+        }));
+
+        assert!(schema
+            .validate(&json!({ "date": "2020-01-01", "datetime": "2020-01-01T00:00:00" }))
+            .is_ok());
+
+        let errors = schema
+            .validate(&json!({ "date": "2019-12-31", "datetime": "tomorrow" }))
+            .unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors[0].code(), "after_or_equal");
+        assert_eq!(errors[0].params()["target"], "2020-01-01");
+        assert_eq!(errors[1].code(), "type");
+        assert_eq!(errors[1].params()["format"], "%Y-%m-%dT%H:%M:%S");
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let invalid = [
+            json!({ "fields": [{ "field": "" }] }),
+            json!({ "fields": [{ "field": "a", "rules": [{ "rule": "regex", "pattern": "(" }] }] }),
+            json!({ "fields": [{ "field": "a", "rules": [{ "rule": "time", "op": "before" }] }] }),
+            json!({ "fields": [{ "field": "a", "rules": [{ "rule": "time", "op": "after", "target": "01.01.2020" }] }] }),
+            json!({ "fields": [{ "field": "a", "rules": [{ "rule": "time", "op": "in_period", "target": "2020-01-01" }] }] }),
+        ];
+
+        for spec in invalid {
+            let spec: Spec = serde_json::from_value(spec).unwrap();
+            assert!(spec.compile().is_err());
+        }
+
+        assert!(Spec::from_json(
+            r#"{ "fields": [{ "field": "a", "rules": [{ "rule": "nope" }] }] }"#
+        )
+        .is_err());
+    }
+}
@@ -196,6 +196,17 @@ impl ValidationError {
             } => *message = Some(msg),
         }
     }
+
+    pub fn set_code(&mut self, code: &'static str) {
+        match self {
+            ValidationError::Schema {
+                code: ref mut c, ..
+            } => *c = code,
+            ValidationError::Field {
+                code: ref mut c, ..
+            } => *c = code,
+        }
+    }
 }
 
 impl std::error::Error for ValidationError {
@@ -211,6 +222,12 @@ impl std::error::Error for ValidationError {
     }
 }
 
+/// Escapes a location segment, such as a map key, as specified by [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901#section-3),
+/// i.e. `~` becomes `~0` and `/` becomes `~1`.
+pub fn escape_location<T: Display>(segment: T) -> String {
+    segment.to_string().replace('~', "~0").replace('/', "~1")
+}
+
 #[derive(Default, Debug, Serialize, Clone, PartialEq)]
 pub struct ValidationErrors(Vec<ValidationError>);
 
@@ -230,6 +247,30 @@ impl ValidationErrors {
         )
     }
 
+    /// Merge validation errors from the given arg to the calling errors, skipping any error located
+    /// at or below the location of an error already present. Used by [Validify][crate::Validify]
+    /// to skip the validations of fields whose modifiers failed.
+    pub fn merge_skip_failed(&mut self, errors: ValidationErrors) {
+        let failed: Vec<String> = self
+            .0
+            .iter()
+            .map(|err| err.location())
+            .filter(|location| !location.is_empty())
+            .map(|location| format!("{location}/"))
+            .collect();
+
+        let errors = errors
+            .0
+            .into_iter()
+            .filter(|err| {
+                let location = format!("{}/", err.location());
+                !failed.iter().any(|failed| location.starts_with(failed))
+            })
+            .collect();
+
+        self.merge(ValidationErrors(errors))
+    }
+
     /// Returns a slice of all the errors that ocurred during validation
     pub fn errors(&self) -> &[ValidationError] {
         &self.0
@@ -281,7 +322,7 @@ impl std::fmt::Display for ValidationError {
                 ref message,
                 ref location,
             } => {
-                let message = message.as_ref().map_or_else(|| "", |f| f);
+                let message = message.as_deref().unwrap_or_default();
                 write!(
                     fmt,
                     "Schema validation error: {{ code: {code} message: {message}, location: {location} }}"
@@ -294,10 +335,11 @@ impl std::fmt::Display for ValidationError {
                 params,
                 location,
             } => {
-                let message = message.as_ref().map_or_else(|| "", |f| f);
+                let message = message.as_deref().unwrap_or_default();
+                let field = field.unwrap_or_default();
                 write!(
                     fmt,
-                    "Validation error: {{ code: {code} location: {location}, field: {}, message: {message}, params: {params:?} }}", field.unwrap()
+                    "Validation error: {{ code: {code} location: {location}, field: {field}, message: {message}, params: {params:?} }}"
                 )
             }
         }
@@ -0,0 +1,374 @@
+//! Implementations of [Validate], [Modify] and [Validify] for standard containers.
+//!
+//! Errors and modifications of elements are located by their index, tuple position or
+//! escaped map key, the same way the derive macro locates nested fields.
+//! `Option`s and `Box`es are transparent.
+
+use crate::{escape_location, ModificationReport, Modify, Validate, ValidationErrors, Validify};
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+/// Merges the errors of the value at the given location into `errors`.
+fn prefix_errors(
+    errors: &mut ValidationErrors,
+    location: impl Display,
+    result: Result<(), ValidationErrors>,
+) {
+    if let Err(mut errs) = result {
+        let location = escape_location(location);
+        errs.errors_mut()
+            .iter_mut()
+            .for_each(|err| err.set_location(&location));
+        errors.merge(errs);
+    }
+}
+
+/// Merges the modifications of the value at the given location into `report`.
+fn prefix_report(
+    report: &mut ModificationReport,
+    location: impl Display,
+    mut rep: ModificationReport,
+) {
+    let location = escape_location(location);
+    rep.modifications_mut()
+        .iter_mut()
+        .for_each(|m| m.set_location(&location));
+    report.merge(rep);
+}
+
+fn into_result(errors: ValidationErrors) -> Result<(), ValidationErrors> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn collect_errors<L: Display, V>(
+    entries: impl IntoIterator<Item = (L, V)>,
+    mut f: impl FnMut(V) -> Result<(), ValidationErrors>,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    for (location, value) in entries {
+        prefix_errors(&mut errors, location, f(value));
+    }
+    into_result(errors)
+}
+
+fn collect_reports<L: Display, V>(
+    entries: impl IntoIterator<Item = (L, V)>,
+    mut f: impl FnMut(V) -> ModificationReport,
+) -> ModificationReport {
+    let mut report = ModificationReport::new();
+    for (location, value) in entries {
+        prefix_report(&mut report, location, f(value));
+    }
+    report
+}
+
+/// Hashed maps are iterated in the order of their keys so error locations are stable.
+fn sorted_entries<'a, K: Ord, V>(entries: impl Iterator<Item = (&'a K, V)>) -> Vec<(&'a K, V)> {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Implements the traits for a collection given the expressions returning its entries,
+/// i.e. pairs of locations and elements.
+macro_rules! impl_collection {
+    ($(
+        [$($gen:tt)*] $ty:ty, $el:ident $(where [$($bound:tt)*])?;
+        |$this:ident| $entries:expr, |$this_mut:ident| $entries_mut:expr;
+    )*) => {
+        $(
+            impl<$($gen)*> Validate for $ty
+            where
+                $el: Validate,
+                $($($bound)*)?
+            {
+                fn validate(&self) -> Result<(), ValidationErrors> {
+                    let $this = self;
+                    collect_errors($entries, |el| el.validate())
+                }
+            }
+
+            impl<$($gen)*> Modify for $ty
+            where
+                $el: Modify,
+                $($($bound)*)?
+            {
+                fn modify(&mut self) {
+                    let $this_mut = self;
+                    for (_, el) in $entries_mut {
+                        el.modify();
+                    }
+                }
+
+                fn try_modify(&mut self) -> Result<(), ValidationErrors> {
+                    let $this_mut = self;
+                    collect_errors($entries_mut, |el| el.try_modify())
+                }
+
+                fn modify_after_validate(&mut self) -> Result<(), ValidationErrors> {
+                    let $this_mut = self;
+                    collect_errors($entries_mut, |el| el.modify_after_validate())
+                }
+
+                fn modify_with_report(&mut self) -> ModificationReport {
+                    let $this_mut = self;
+                    collect_reports($entries_mut, |el| el.modify_with_report())
+                }
+            }
+
+            impl<$($gen)*> Validify for $ty
+            where
+                $el: Validify,
+                $($($bound)*)?
+            {
+                fn validify(&mut self) -> Result<(), ValidationErrors> {
+                    let $this_mut = self;
+                    collect_errors($entries_mut, |el| el.validify())
+                }
+            }
+        )*
+    };
+}
+
+impl_collection! {
+    [T] [T], T;
+    |this| this.iter().enumerate(), |this| this.iter_mut().enumerate();
+
+    [T] Vec<T>, T;
+    |this| this.iter().enumerate(), |this| this.iter_mut().enumerate();
+
+    [T, const N: usize] [T; N], T;
+    |this| this.iter().enumerate(), |this| this.iter_mut().enumerate();
+
+    [K, V, S] HashMap<K, V, S>, V where [K: Display + Ord];
+    |this| sorted_entries(this.iter()), |this| sorted_entries(this.iter_mut());
+
+    [K, V] BTreeMap<K, V>, V where [K: Display];
+    |this| this.iter(), |this| this.iter_mut();
+
+    [K, V, S] IndexMap<K, V, S>, V where [K: Display];
+    |this| this.iter(), |this| this.iter_mut();
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Modify> Modify for Option<T> {
+    fn modify(&mut self) {
+        if let Some(value) = self {
+            value.modify()
+        }
+    }
+
+    fn try_modify(&mut self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.try_modify(),
+            None => Ok(()),
+        }
+    }
+
+    fn modify_after_validate(&mut self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.modify_after_validate(),
+            None => Ok(()),
+        }
+    }
+
+    fn modify_with_report(&mut self) -> ModificationReport {
+        match self {
+            Some(value) => value.modify_with_report(),
+            None => ModificationReport::new(),
+        }
+    }
+}
+
+impl<T: Validify> Validify for Option<T> {
+    fn validify(&mut self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.validify(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for Box<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        (**self).validate()
+    }
+}
+
+impl<T: Modify + ?Sized> Modify for Box<T> {
+    fn modify(&mut self) {
+        (**self).modify()
+    }
+
+    fn try_modify(&mut self) -> Result<(), ValidationErrors> {
+        (**self).try_modify()
+    }
+
+    fn modify_after_validate(&mut self) -> Result<(), ValidationErrors> {
+        (**self).modify_after_validate()
+    }
+
+    fn modify_with_report(&mut self) -> ModificationReport {
+        (**self).modify_with_report()
+    }
+}
+
+impl<T: Validify + ?Sized> Validify for Box<T> {
+    fn validify(&mut self) -> Result<(), ValidationErrors> {
+        (**self).validify()
+    }
+}
+
+/// Implements the traits for tuples, locating errors by the position of the element.
+macro_rules! impl_tuple {
+    ($(($($idx:tt $name:ident),+))*) => {
+        $(
+            impl<$($name: Validate),+> Validate for ($($name,)+) {
+                fn validate(&self) -> Result<(), ValidationErrors> {
+                    let mut errors = ValidationErrors::new();
+                    $(prefix_errors(&mut errors, $idx, self.$idx.validate());)+
+                    into_result(errors)
+                }
+            }
+
+            impl<$($name: Modify),+> Modify for ($($name,)+) {
+                fn modify(&mut self) {
+                    $(self.$idx.modify();)+
+                }
+
+                fn try_modify(&mut self) -> Result<(), ValidationErrors> {
+                    let mut errors = ValidationErrors::new();
+                    $(prefix_errors(&mut errors, $idx, self.$idx.try_modify());)+
+                    into_result(errors)
+                }
+
+                fn modify_after_validate(&mut self) -> Result<(), ValidationErrors> {
+                    let mut errors = ValidationErrors::new();
+                    $(prefix_errors(&mut errors, $idx, self.$idx.modify_after_validate());)+
+                    into_result(errors)
+                }
+
+                fn modify_with_report(&mut self) -> ModificationReport {
+                    let mut report = ModificationReport::new();
+                    $(prefix_report(&mut report, $idx, self.$idx.modify_with_report());)+
+                    report
+                }
+            }
+
+            impl<$($name: Validify),+> Validify for ($($name,)+) {
+                fn validify(&mut self) -> Result<(), ValidationErrors> {
+                    let mut errors = ValidationErrors::new();
+                    $(prefix_errors(&mut errors, $idx, self.$idx.validify());)+
+                    into_result(errors)
+                }
+            }
+        )*
+    };
+}
+
+impl_tuple! {
+    (0 A)
+    (0 A, 1 B)
+    (0 A, 1 B, 2 C)
+    (0 A, 1 B, 2 C, 3 D)
+    (0 A, 1 B, 2 C, 3 D, 4 E)
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F)
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G)
+    (0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Modify, Validate, ValidationError, ValidationErrors, Validify};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Item(i32);
+
+    impl Validate for Item {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.0 < 0 {
+                let mut err = ValidationError::new_field("negative");
+                err.set_location("value");
+                errors.add(err);
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+    }
+
+    impl Modify for Item {
+        fn modify(&mut self) {
+            self.0 = self.0.min(10);
+        }
+    }
+
+    impl Validify for Item {
+        fn validify(&mut self) -> Result<(), ValidationErrors> {
+            self.modify();
+            self.validate()
+        }
+    }
+
+    fn locations(errors: ValidationErrors) -> Vec<String> {
+        errors
+            .errors()
+            .iter()
+            .map(|e| e.location().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_containers() {
+        let items = vec![Item(1), Item(-1), Item(-2)];
+        assert_eq!(
+            locations(items.validate().unwrap_err()),
+            ["/1/value", "/2/value"]
+        );
+        assert_eq!(
+            locations(items[1..].validate().unwrap_err()),
+            ["/0/value", "/1/value"]
+        );
+        assert!([Item(1)].validate().is_ok());
+
+        let map = HashMap::from([("b/c", Item(-1)), ("a", Item(-1)), ("d", Item(1))]);
+        assert_eq!(
+            locations(map.validate().unwrap_err()),
+            ["/a/value", "/b~1c/value"]
+        );
+
+        let nested = BTreeMap::from([("a", vec![Some(Box::new(Item(-1)))])]);
+        assert_eq!(locations(nested.validate().unwrap_err()), ["/a/0/value"]);
+
+        let tuple = (Item(1), None::<Item>, Item(-1));
+        assert_eq!(locations(tuple.validate().unwrap_err()), ["/2/value"]);
+    }
+
+    #[test]
+    fn test_container_modify() {
+        let mut items = vec![Some(Item(20)), None];
+        assert!(items.validify().is_ok());
+        assert_eq!(items, vec![Some(Item(10)), None]);
+
+        let mut tuple = (Item(20), vec![Item(-1)]);
+        assert_eq!(locations(tuple.validify().unwrap_err()), ["/1/0/value"]);
+        assert_eq!(tuple.0, Item(10));
+    }
+}
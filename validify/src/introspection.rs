@@ -0,0 +1,126 @@
+//! Static descriptions of the validators of a type, obtained with [Validate::rules](crate::Validate::rules).
+//!
+//! The descriptions are generated by the derive macros from the same annotations the validations are
+//! generated from, making them useful for exposing validation rules to clients, e.g. to mirror
+//! them in forms.
+
+/// Describes the validators of a single field.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldRules {
+    /// The name of the field as it appears in errors, i.e. after serde renames.
+    /// The position of the field for tuple structs and empty for the values of validated newtypes.
+    pub name: &'static str,
+
+    /// The variant containing the field if the validated type is an enum.
+    pub variant: Option<&'static str>,
+
+    /// The kind of the field type. Options, references and smart pointers are not taken into account.
+    pub kind: TypeKind,
+
+    /// Whether the field is an `Option`.
+    pub optional: bool,
+
+    /// The validators of the field in the order they are declared.
+    pub rules: &'static [Rule],
+
+    /// Returns the fields of the nested type if the field is annotated with `#[validate]`.
+    /// For collections, this is the type of their elements.
+    pub nested: Option<fn() -> &'static [FieldRules]>,
+}
+
+impl FieldRules {
+    /// Returns the first rule with the given validator name.
+    pub fn rule(&self, name: &str) -> Option<&'static Rule> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Returns the fields of the nested type, or an empty slice if the field is not nested.
+    ///
+    /// Nested types implementing [Validate][crate::Validate] by hand describe no fields, and
+    /// therefore return an empty slice, unless they override [Validate::rules][crate::Validate::rules].
+    pub fn nested_rules(&self) -> &'static [FieldRules] {
+        self.nested.map(|rules| rules()).unwrap_or_default()
+    }
+}
+
+/// The kind of a field type, as written in the type definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    /// `String`, `str` and `Cow<str>`
+    String,
+    /// Signed and unsigned integers
+    Integer,
+    /// `f32` and `f64`
+    Float,
+    Bool,
+    Char,
+    /// `NaiveDate`
+    Date,
+    /// `NaiveDateTime` and `DateTime`
+    DateTime,
+    /// Vectors, sets, arrays and slices
+    List,
+    /// `HashMap`, `BTreeMap` and `IndexMap`
+    Map,
+    /// Any other type, usually a nested struct
+    Other,
+}
+
+/// Describes a single validator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    /// The name of the validator as used in the `validate` attribute, e.g. `length` or `is_in`.
+    pub name: &'static str,
+
+    /// The code of the errors the validator produces.
+    pub code: &'static str,
+
+    /// The custom message of the errors, if any.
+    pub message: Option<&'static str>,
+
+    /// The parameters of the validator, e.g. `min` and `max` for `length`.
+    pub params: &'static [(&'static str, RuleParam)],
+
+    /// The rules applied to elements by `iter`, to keys by `keys` and to values by `values`,
+    /// or the rules of the rule set for `rules`.
+    pub rules: &'static [Rule],
+}
+
+impl Rule {
+    /// Returns the value of the parameter with the given name.
+    pub fn param(&self, name: &str) -> Option<&'static RuleParam> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// The value of a validator parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleParam {
+    Number(f64),
+    Str(&'static str),
+    Bool(bool),
+    /// A path or an expression which is only known at runtime, as written in the attribute,
+    /// e.g. the function of `custom` or a constant used for `length(max = MAX)`.
+    Expr(&'static str),
+}
+
+impl RuleParam {
+    /// Returns the number if the parameter is a numeric literal.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the parameter is a string literal.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
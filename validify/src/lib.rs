@@ -1,15 +1,56 @@
 #![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
 
+pub mod builder;
+mod depth;
+pub mod dynamic;
 mod error;
+mod impls;
+mod introspection;
+mod modification;
+mod report;
+mod root;
+pub mod rules;
+mod sort;
 pub mod traits;
 mod validation;
 
-pub use error::{ValidationError, ValidationErrors};
+pub use depth::DEFAULT_MAX_DEPTH;
+pub use error::{escape_location, ValidationError, ValidationErrors};
+pub use introspection::{FieldRules, Rule, RuleParam, TypeKind};
+#[cfg(feature = "html")]
+pub use modification::html::{sanitize_html, strip_html};
+pub use modification::{
+    canonical::{email_canonical, phone_e164, url_normalize},
+    case::{
+        slugify, to_camel_case, to_kebab_case, to_pascal_case, to_screaming_snake_case,
+        to_snake_case,
+    },
+    collection::{dedup, retain_non_empty, sort, sort_by_key, truncate, Collection, Truncate},
+    numeric::{abs, ceil, clamp, floor, round, Numeric},
+    text::{truncate_string, TextUnit},
+    time::{to_utc, truncate_time, TimeUnit, ToUtc},
+};
+#[doc(hidden)]
+pub use report::capture as __capture;
+#[doc(hidden)]
+pub mod __depth {
+    pub use crate::depth::{enter, DepthGuard};
+}
+#[doc(hidden)]
+pub mod __root {
+    pub use crate::root::into_root;
+}
+#[doc(hidden)]
+pub mod __sort {
+    pub use crate::sort::{Items, SortedItems, UnsortedItems};
+}
+pub use report::{Modification, ModificationReport};
 pub use traits::{Contains, Length};
 pub use validation::{
     cards::validate_credit_card,
     contains::validate_contains,
     email::validate_email,
+    html::validate_no_html,
     ip::{validate_ip, validate_ip_v4, validate_ip_v6},
     length::validate_length,
     non_control_char::validate_non_control_character,
@@ -19,7 +60,9 @@ pub use validation::{
     time,
     urls::validate_url,
 };
-pub use validify_derive::{schema_err, schema_validation, Payload, Validate, Validify};
+pub use validify_derive::{
+    rule_set, schema_err, schema_validation, Payload, Validate, ValidatedNewtype, Validify,
+};
 
 /// Validates the struct/enum based on the provided `#[validate]` attributes.
 /// Deriving [Validate] allows you to specify schema and field validation on structs using the `#[validate]` attribute.
@@ -27,6 +70,16 @@ pub use validify_derive::{schema_err, schema_validation, Payload, Validate, Vali
 pub trait Validate {
     /// Apply the provided validations to self
     fn validate(&self) -> Result<(), ValidationErrors>;
+
+    /// Returns a description of the validators of each field.
+    /// The default implementation returns no fields, so hand-written impls used as nested
+    /// fields of derived types have no [FieldRules::nested_rules].
+    fn rules() -> &'static [FieldRules]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
 }
 
 /// Modifies the struct/enum based on the provided `#[modify]` attributes.
@@ -35,6 +88,31 @@ pub trait Validate {
 pub trait Modify {
     /// Apply the provided modifiers to self
     fn modify(&mut self);
+
+    /// Apply the provided modifiers to self, returning the errors of any fallible modifiers.
+    /// The default implementation calls [Modify::modify] and never fails.
+    fn try_modify(&mut self) -> Result<(), ValidationErrors> {
+        self.modify();
+        Ok(())
+    }
+
+    /// Apply the modifiers specified with `#[modify(after_validate, ...)]` to self.
+    /// Called by [Validify::validify] only if validation succeeded.
+    /// The default implementation does nothing.
+    ///
+    /// The phase is not transactional. When a fallible modifier fails, the modifiers of the other fields and
+    /// nested values are still applied. Use [Validify::try_validify] to keep the value untouched on failure.
+    fn modify_after_validate(&mut self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+
+    /// Apply the provided modifiers to self, returning a report of the changes they made.
+    /// Errors of fallible modifiers are ignored, as in [Modify::modify].
+    /// The default implementation calls [Modify::modify] and returns an empty report.
+    fn modify_with_report(&mut self) -> ModificationReport {
+        self.modify();
+        ModificationReport::new()
+    }
 }
 
 /// Validates and modifies the struct/enum based on the provided `#[validate]` and `#[modify]` attributes.
@@ -103,6 +181,33 @@ pub trait Modify {
 pub trait Validify: Modify + Validate {
     /// Apply the provided modifiers to self and run validations.
     fn validify(&mut self) -> Result<(), ValidationErrors>;
+
+    /// Transactional version of [Validify::validify]. Returns the modified value if all validations pass,
+    /// otherwise returns the original, unmodified value along with the errors.
+    ///
+    /// Requires `Clone` even though `self` is owned. Modifiers change the value in place and cannot be undone,
+    /// so handing back the original on failure means modifying a copy of it. Use [Validify::validify] to avoid
+    /// the copy when the partially modified value can be discarded.
+    fn try_validify(self) -> Result<Self, (Self, ValidationErrors)>
+    where
+        Self: Sized + Clone,
+    {
+        let mut modified = self.clone();
+        match modified.validify() {
+            Ok(()) => Ok(modified),
+            Err(errors) => Err((self, errors)),
+        }
+    }
+
+    /// Returns a modified copy of self if all validations pass, leaving self untouched.
+    fn validified(&self) -> Result<Self, ValidationErrors>
+    where
+        Self: Sized + Clone,
+    {
+        let mut modified = self.clone();
+        modified.validify()?;
+        Ok(modified)
+    }
 }
 
 /// Exposes validify functionality on generated [Payload] structs.
@@ -118,6 +223,66 @@ pub trait ValidifyPayload: Sized {
     fn validify_from(payload: Self::Payload) -> Result<Self, ValidationErrors>;
 }
 
+/// A reusable, configurable validator of values of type `T`, used with `#[validate(with = expr)]`.
+///
+/// Errors are handled the same way as the ones returned from `custom` functions; unless the error
+/// already has a field name, it is set to the validated field's.
+///
+/// ```
+/// use validify::{Validate, ValidationError, Validator};
+///
+/// struct Decimals(u32);
+///
+/// impl Validator<f64> for Decimals {
+///     fn validate(&self, value: &f64) -> Result<(), ValidationError> {
+///         let scaled = value * 10f64.powi(self.0 as i32);
+///         if scaled.fract() != 0. {
+///             return Err(ValidationError::new_field("decimals").with_param("max", &self.0));
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(Validate)]
+/// struct Price {
+///     #[validate(with = Decimals(2))]
+///     amount: f64,
+/// }
+///
+/// assert!(Price { amount: 1.25 }.validate().is_ok());
+/// assert!(Price { amount: 1.255 }.validate().is_err());
+/// ```
+///
+/// Implemented for closures taking a reference to the value.
+pub trait Validator<T: ?Sized> {
+    fn validate(&self, value: &T) -> Result<(), ValidationError>;
+}
+
+impl<T: ?Sized, F> Validator<T> for F
+where
+    F: Fn(&T) -> Result<(), ValidationError>,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self(value)
+    }
+}
+
+/// A reusable, configurable modifier of values of type `T`, used with `#[modify(with = expr)]`.
+///
+/// Implemented for closures taking a mutable reference to the value.
+pub trait Modifier<T: ?Sized> {
+    fn modify(&self, value: &mut T);
+}
+
+impl<T: ?Sized, F> Modifier<T> for F
+where
+    F: Fn(&mut T),
+{
+    fn modify(&self, value: &mut T) {
+        self(value)
+    }
+}
+
 /// Creates a new field validation error.
 /// Serves as a shorthand for writing out errors for custom functions
 /// and schema validations.
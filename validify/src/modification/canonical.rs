@@ -0,0 +1,145 @@
+use idna::domain_to_ascii;
+use phonenumber::{country, Mode};
+use url::Url;
+
+/// Canonicalizes the given email by lowercasing its domain and converting
+/// [IDN](https://en.wikipedia.org/wiki/Internationalized_domain_name)s to punycode.
+/// The local part is kept as is since it is case sensitive per the spec.
+///
+/// If the value is not an email or the domain cannot be converted, it is returned unchanged.
+#[must_use]
+pub fn email_canonical(value: &str) -> String {
+    let Some((user_part, domain_part)) = value.rsplit_once('@') else {
+        return value.to_string();
+    };
+
+    if user_part.is_empty() || domain_part.is_empty() {
+        return value.to_string();
+    }
+
+    // IP literals, i.e. `[127.0.0.1]`, cannot be converted
+    if domain_part.starts_with('[') {
+        return format!("{user_part}@{}", domain_part.to_lowercase());
+    }
+
+    match domain_to_ascii(domain_part) {
+        Ok(domain) if !domain.is_empty() => format!("{user_part}@{domain}"),
+        _ => value.to_string(),
+    }
+}
+
+/// Formats the given phone number in the [E.164](https://en.wikipedia.org/wiki/E.164) format, i.e. `+14152370800`.
+///
+/// `default_region` is the ISO 3166-1 alpha-2 code of the region used for numbers
+/// not written in the international format.
+///
+/// If the number cannot be parsed or is not valid, it is returned unchanged.
+#[must_use]
+pub fn phone_e164(value: &str, default_region: Option<&str>) -> String {
+    let region = match default_region {
+        Some(region) => match region.to_uppercase().parse::<country::Id>() {
+            Ok(id) => Some(id),
+            Err(_) => return value.to_string(),
+        },
+        None => None,
+    };
+
+    match phonenumber::parse(region, value) {
+        Ok(number) if phonenumber::is_valid(&number) => {
+            number.format().mode(Mode::E164).to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Normalizes the given URL by lowercasing its scheme and host, removing the port if it is
+/// the default one for the scheme and sorting the query parameters by their name. Parameters with the same
+/// name keep their relative order and the parameters are otherwise kept as written, i.e. `?flag` stays `?flag`.
+///
+/// If the value is not a URL, it is returned unchanged.
+#[must_use]
+pub fn url_normalize(value: &str) -> String {
+    // The parser already lowercases the scheme, as well as the host and
+    // removes default ports for special schemes such as `http`
+    let Ok(mut url) = Url::parse(value) else {
+        return value.to_string();
+    };
+
+    if let Some(host) = url.host_str() {
+        let host = host.to_lowercase();
+        if url.set_host(Some(&host)).is_err() {
+            return value.to_string();
+        }
+    }
+
+    if let Some(query) = url.query().filter(|query| !query.is_empty()) {
+        let mut pairs: Vec<&str> = query.split('&').filter(|pair| !pair.is_empty()).collect();
+        // Stable, so the order of repeated parameters is kept
+        pairs.sort_by_key(|pair| pair.split_once('=').map_or(*pair, |(name, _)| name));
+        let query = pairs.join("&");
+        url.set_query(Some(&query));
+    }
+
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_canonical() {
+        let tests = vec![
+            ("John.Doe@EXAMPLE.com", "John.Doe@example.com"),
+            ("user@Bücher.example", "user@xn--bcher-kva.example"),
+            ("user@[IPv6:2001:DB8::1]", "user@[ipv6:2001:db8::1]"),
+            ("not an email", "not an email"),
+            ("@example.com", "@example.com"),
+            ("user@", "user@"),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(email_canonical(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_phone_e164() {
+        let tests = vec![
+            ("+1 (415) 237-0800", None, "+14152370800"),
+            ("(415) 237-0800", Some("US"), "+14152370800"),
+            ("06 42 92 68 29", Some("fr"), "+33642926829"),
+            ("(415) 237-0800", None, "(415) 237-0800"),
+            ("(415) 237-0800", Some("NOT A REGION"), "(415) 237-0800"),
+            ("TEXT", Some("US"), "TEXT"),
+        ];
+
+        for (input, region, expected) in tests {
+            assert_eq!(phone_e164(input, region), expected);
+        }
+    }
+
+    #[test]
+    fn test_url_normalize() {
+        let tests = vec![
+            ("HTTPS://Example.COM:443/Path", "https://example.com/Path"),
+            ("http://example.com:80", "http://example.com/"),
+            ("http://example.com:8080/", "http://example.com:8080/"),
+            (
+                "https://example.com/?b=2&a=1&a=0",
+                "https://example.com/?a=1&a=0&b=2",
+            ),
+            (
+                "https://example.com/?flag&b=x%20y&a=",
+                "https://example.com/?a=&b=x%20y&flag",
+            ),
+            ("https://example.com/?", "https://example.com/?"),
+            ("foo://Example.com/x", "foo://example.com/x"),
+            ("not a url", "not a url"),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(url_normalize(input), expected);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use deunicode::deunicode;
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+
+/// Converts the given string to `snake_case`.
+#[must_use]
+pub fn to_snake_case(value: &str) -> String {
+    value.to_snake_case()
+}
+
+/// Converts the given string to `kebab-case`.
+#[must_use]
+pub fn to_kebab_case(value: &str) -> String {
+    value.to_kebab_case()
+}
+
+/// Converts the given string to `camelCase`.
+#[must_use]
+pub fn to_camel_case(value: &str) -> String {
+    value.to_lower_camel_case()
+}
+
+/// Converts the given string to `PascalCase`.
+#[must_use]
+pub fn to_pascal_case(value: &str) -> String {
+    value.to_upper_camel_case()
+}
+
+/// Converts the given string to `SCREAMING_SNAKE_CASE`.
+#[must_use]
+pub fn to_screaming_snake_case(value: &str) -> String {
+    value.to_shouty_snake_case()
+}
+
+/// Creates a URL friendly slug from the given string.
+///
+/// Non-ASCII characters are transliterated to their closest ASCII representation,
+/// the result is lowercased and any run of non alphanumeric characters is replaced
+/// with a single `separator`. Leading and trailing separators are removed.
+///
+/// If `max_len` is provided, the slug is cut to at most `max_len` characters, after which
+/// any dangling separator is removed.
+#[must_use]
+pub fn slugify(value: &str, separator: &str, max_len: Option<usize>) -> String {
+    let mut slug = String::with_capacity(value.len());
+    let mut pending_separator = false;
+
+    for ch in deunicode(value).chars() {
+        if ch.is_ascii_alphanumeric() {
+            if pending_separator && !slug.is_empty() {
+                slug.push_str(separator);
+            }
+            pending_separator = false;
+            slug.push(ch.to_ascii_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+
+    let Some(max_len) = max_len else {
+        return slug;
+    };
+
+    // The slug is ASCII at this point, but the separator need not be
+    if let Some((idx, _)) = slug.char_indices().nth(max_len) {
+        slug.truncate(idx);
+    }
+
+    while !separator.is_empty() && slug.ends_with(separator) {
+        slug.truncate(slug.len() - separator.len());
+    }
+
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_conversions() {
+        let tests = vec![
+            (
+                "userId", "user_id", "user-id", "userId", "UserId", "USER_ID",
+            ),
+            (
+                "HTTPServer error",
+                "http_server_error",
+                "http-server-error",
+                "httpServerError",
+                "HttpServerError",
+                "HTTP_SERVER_ERROR",
+            ),
+            (
+                "  some-kebab_snake Mix ",
+                "some_kebab_snake_mix",
+                "some-kebab-snake-mix",
+                "someKebabSnakeMix",
+                "SomeKebabSnakeMix",
+                "SOME_KEBAB_SNAKE_MIX",
+            ),
+            ("", "", "", "", "", ""),
+        ];
+
+        for (input, snake, kebab, camel, pascal, screaming) in tests {
+            assert_eq!(to_snake_case(input), snake);
+            assert_eq!(to_kebab_case(input), kebab);
+            assert_eq!(to_camel_case(input), camel);
+            assert_eq!(to_pascal_case(input), pascal);
+            assert_eq!(to_screaming_snake_case(input), screaming);
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        let tests = vec![
+            ("Hello World", "-", None, "hello-world"),
+            ("  Hello,   World!  ", "-", None, "hello-world"),
+            ("Čćžšđ Über straße", "-", None, "cczsd-uber-strasse"),
+            ("日本語", "-", None, "ri-ben-yu"),
+            ("Hello World", "_", None, "hello_world"),
+            ("Hello World", "-", Some(6), "hello"),
+            ("Hello World", "-", Some(7), "hello-w"),
+            ("Hello World", "--", Some(7), "hello"),
+            ("---", "-", None, ""),
+        ];
+
+        for (input, separator, max_len, expected) in tests {
+            assert_eq!(slugify(input, separator, max_len), expected);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::traits::Length;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Used by the collection modifiers, i.e. `sort`, `dedup`, `sort_by_key`, `retain_non_empty`
+/// and `truncate`.
+///
+/// Implemented for `Vec` and `VecDeque` and can be implemented on any other list-like
+/// collection that needs to be modified.
+pub trait Collection {
+    type Item;
+
+    /// Returns the items of the collection as a contiguous mutable slice.
+    fn items_mut(&mut self) -> &mut [Self::Item];
+
+    /// Keeps only the items for which `f` returns `true`, preserving their order.
+    fn retain_items<F: FnMut(&Self::Item) -> bool>(&mut self, f: F);
+
+    /// Removes the item at the given index, shifting all items after it.
+    fn remove_item(&mut self, index: usize);
+
+    /// Shortens the collection to the given amount of items.
+    fn truncate_items(&mut self, len: usize);
+}
+
+impl<T> Collection for Vec<T> {
+    type Item = T;
+
+    fn items_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+
+    fn retain_items<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain(f)
+    }
+
+    fn remove_item(&mut self, index: usize) {
+        self.remove(index);
+    }
+
+    fn truncate_items(&mut self, len: usize) {
+        self.truncate(len)
+    }
+}
+
+impl<T> Collection for VecDeque<T> {
+    type Item = T;
+
+    fn items_mut(&mut self) -> &mut [T] {
+        self.make_contiguous()
+    }
+
+    fn retain_items<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain(f)
+    }
+
+    fn remove_item(&mut self, index: usize) {
+        self.remove(index);
+    }
+
+    fn truncate_items(&mut self, len: usize) {
+        self.truncate(len)
+    }
+}
+
+/// Used by the `truncate(max = N)` modifier to shorten the value to at most `max` elements.
+pub trait Truncate {
+    fn truncate_to(&mut self, max: usize);
+}
+
+impl<T> Truncate for Vec<T> {
+    fn truncate_to(&mut self, max: usize) {
+        self.truncate_items(max)
+    }
+}
+
+impl<T> Truncate for VecDeque<T> {
+    fn truncate_to(&mut self, max: usize) {
+        self.truncate_items(max)
+    }
+}
+
+/// Sorts the collection.
+pub fn sort<C>(collection: &mut C)
+where
+    C: Collection,
+    C::Item: Ord,
+{
+    collection.items_mut().sort()
+}
+
+/// Sorts the collection with the key extraction function.
+pub fn sort_by_key<C, K, F>(collection: &mut C, f: F)
+where
+    C: Collection,
+    K: Ord,
+    F: FnMut(&C::Item) -> K,
+{
+    collection.items_mut().sort_by_key(f)
+}
+
+/// Removes all duplicate items from the collection, keeping the first occurrence of each.
+///
+/// Unlike `Vec::dedup`, the items do not need to be sorted beforehand.
+pub fn dedup<C>(collection: &mut C)
+where
+    C: Collection,
+    C::Item: Hash + Eq,
+{
+    let mut seen = HashSet::new();
+    let mut keep = collection
+        .items_mut()
+        .iter()
+        .map(|item| seen.insert(item))
+        .collect::<Vec<_>>()
+        .into_iter();
+    collection.retain_items(|_| keep.next().unwrap_or(true));
+}
+
+/// Removes all items whose [Length] is 0, e.g. empty strings.
+pub fn retain_non_empty<C>(collection: &mut C)
+where
+    C: Collection,
+    C::Item: Length,
+{
+    collection.retain_items(|item| item.length() > 0)
+}
+
+/// Shortens the value to at most `max` elements. See [Truncate].
+pub fn truncate<T: Truncate + ?Sized>(value: &mut T, max: usize) {
+    value.truncate_to(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_dedup() {
+        let mut test = vec![3, 1, 2, 3, 1];
+        dedup(&mut test);
+        assert_eq!(test, vec![3, 1, 2]);
+        sort(&mut test);
+        assert_eq!(test, vec![1, 2, 3]);
+
+        let mut test = VecDeque::from(vec!["b", "a", "b"]);
+        test.push_front("c");
+        sort(&mut test);
+        dedup(&mut test);
+        assert_eq!(test, VecDeque::from(vec!["a", "b", "c"]));
+
+        let mut test: Vec<_> = (0..10_000).chain(0..10_000).rev().collect();
+        dedup(&mut test);
+        assert_eq!(test, (0..10_000).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_sort_by_key() {
+        let mut test = vec!["ccc", "a", "bb"];
+        sort_by_key(&mut test, |s| s.len());
+        assert_eq!(test, vec!["a", "bb", "ccc"]);
+    }
+
+    #[test]
+    fn test_retain_non_empty() {
+        let mut test = vec![String::from("a"), String::new(), String::from("b")];
+        retain_non_empty(&mut test);
+        assert_eq!(test, vec!["a", "b"]);
+
+        let mut test = VecDeque::from(vec![vec![1], vec![]]);
+        retain_non_empty(&mut test);
+        assert_eq!(test, VecDeque::from(vec![vec![1]]));
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut test = vec![1, 2, 3];
+        truncate(&mut test, 2);
+        assert_eq!(test, vec![1, 2]);
+        truncate(&mut test, 5);
+        assert_eq!(test, vec![1, 2]);
+
+        let mut test = VecDeque::from(vec![1, 2, 3]);
+        truncate(&mut test, 0);
+        assert!(test.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+use ammonia::Builder;
+use std::collections::HashSet;
+
+/// Sanitizes the given HTML string using [ammonia].
+///
+/// If no `tags` and `attributes` are provided, ammonia's defaults are used. When `tags` are
+/// provided, only those tags are kept. When `attributes` are provided, only those attributes
+/// are kept on any of the allowed tags. Any disallowed markup is removed while
+/// its text content is kept.
+///
+/// Allowing `script` or `style` keeps their contents, which are otherwise removed entirely.
+/// Allowing the `rel` attribute keeps its value instead of setting it to `noopener noreferrer`.
+#[must_use]
+pub fn sanitize_html(value: &str, tags: Option<&[&str]>, attributes: Option<&[&str]>) -> String {
+    let mut builder = Builder::default();
+
+    // Ammonia panics if a tag is both allowed and has its content removed
+    if let Some(tags) = tags {
+        let clean_content = builder
+            .clone_clean_content_tags()
+            .into_iter()
+            .filter(|tag| !tags.contains(tag))
+            .collect::<HashSet<_>>();
+        builder
+            .clean_content_tags(clean_content)
+            .tags(tags.iter().copied().collect::<HashSet<_>>());
+    }
+
+    // Ammonia panics if `rel` is allowed while it also sets it on links
+    if let Some(attributes) = attributes {
+        if attributes.contains(&"rel") {
+            builder.link_rel(None);
+        }
+        builder
+            .tag_attributes(Default::default())
+            .generic_attributes(attributes.iter().copied().collect::<HashSet<_>>());
+    }
+
+    builder.clean(value).to_string()
+}
+
+/// Removes all HTML markup from the string, keeping only its text content.
+///
+/// The contents of `script` and `style` elements are removed entirely.
+/// The resulting text stays HTML escaped, e.g. `&lt;b&gt;` is not turned into markup.
+#[must_use]
+pub fn strip_html(value: &str) -> String {
+    Builder::empty()
+        .clean_content_tags(HashSet::from(["script", "style"]))
+        .clean(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_html_default() {
+        let test = sanitize_html("<b>bold</b><script>alert(1)</script>", None, None);
+        assert_eq!(test, "<b>bold</b>");
+
+        let test = sanitize_html("<img src=x onerror=alert(1)>", None, None);
+        assert_eq!(test, r#"<img src="x">"#);
+    }
+
+    #[test]
+    fn test_sanitize_html_allowlist() {
+        let test = sanitize_html(
+            r#"<p class="x"><b>bold</b> <i title="y">italic</i></p>"#,
+            Some(&["b", "i"]),
+            None,
+        );
+        // Default attributes are kept when not specified
+        assert_eq!(test, r#"<b>bold</b> <i title="y">italic</i>"#);
+
+        let test = sanitize_html(
+            r#"<p class="x" title="y"><b>bold</b></p>"#,
+            Some(&["p"]),
+            Some(&["title"]),
+        );
+        assert_eq!(test, r#"<p title="y">bold</p>"#);
+    }
+
+    #[test]
+    fn test_sanitize_html_rel() {
+        let test = sanitize_html(
+            r#"<a href="/x" rel="nofollow">x</a>"#,
+            Some(&["a"]),
+            Some(&["href", "rel"]),
+        );
+        assert_eq!(test, r#"<a href="/x" rel="nofollow">x</a>"#);
+    }
+
+    #[test]
+    fn test_sanitize_html_clean_content_tags() {
+        let test = sanitize_html("<style>b { x: y }</style><b>b</b>", Some(&["style"]), None);
+        assert_eq!(test, "<style>b { x: y }</style>b");
+
+        let test = sanitize_html(
+            "<script>alert(1)</script><style>b</style>",
+            Some(&["script"]),
+            None,
+        );
+        assert_eq!(test, "<script>alert(1)</script>");
+    }
+
+    #[test]
+    fn test_strip_html() {
+        let test = strip_html("<p>Hello <b>world</b></p><script>alert(1)</script>");
+        assert_eq!(test, "Hello world");
+
+        let test = strip_html("a &lt; b & c");
+        assert_eq!(test, "a &lt; b &amp; c");
+
+        let test = strip_html("&lt;script&gt;alert(1)&lt;/script&gt;");
+        assert_eq!(test, "&lt;script&gt;alert(1)&lt;/script&gt;");
+    }
+}
@@ -0,0 +1,8 @@
+pub mod canonical;
+pub mod case;
+pub mod collection;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod numeric;
+pub mod text;
+pub mod time;
@@ -0,0 +1,156 @@
+/// Used by the numeric modifiers, i.e. `round`, `floor`, `ceil` and `abs`.
+///
+/// Implemented for all primitive integer and float types. Rounding integers
+/// leaves them unchanged and `abs` saturates on signed integer overflow.
+pub trait Numeric: Copy + PartialOrd {
+    fn round_to(self, decimals: u32) -> Self;
+
+    fn floor_to(self) -> Self;
+
+    fn ceil_to(self) -> Self;
+
+    fn abs_to(self) -> Self;
+}
+
+macro_rules! impl_numeric_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn round_to(self, _: u32) -> Self { self }
+                fn floor_to(self) -> Self { self }
+                fn ceil_to(self) -> Self { self }
+                fn abs_to(self) -> Self { self.saturating_abs() }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn round_to(self, _: u32) -> Self { self }
+                fn floor_to(self) -> Self { self }
+                fn ceil_to(self) -> Self { self }
+                fn abs_to(self) -> Self { self }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn round_to(self, decimals: u32) -> Self {
+                    let factor = (10 as $t).powi(decimals as i32);
+                    let scaled = self * factor;
+                    // Large values or decimals overflow, in which case there is nothing to round
+                    if !scaled.is_finite() {
+                        return self;
+                    }
+                    scaled.round() / factor
+                }
+                fn floor_to(self) -> Self { self.floor() }
+                fn ceil_to(self) -> Self { self.ceil() }
+                fn abs_to(self) -> Self { self.abs() }
+            }
+        )*
+    };
+}
+
+impl_numeric_signed!(i8, i16, i32, i64, i128, isize);
+impl_numeric_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_numeric_float!(f32, f64);
+
+/// Clamps the value to the given bounds. The `min` and `max` parameters are optional
+/// and will only be applied if they are not `None`.
+#[must_use]
+pub fn clamp<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> T {
+    if let Some(min) = min {
+        if value < min {
+            return min;
+        }
+    }
+
+    if let Some(max) = max {
+        if value > max {
+            return max;
+        }
+    }
+
+    value
+}
+
+/// Rounds the value to the given amount of decimal places.
+#[must_use]
+pub fn round<T: Numeric>(value: T, decimals: u32) -> T {
+    value.round_to(decimals)
+}
+
+/// Returns the largest integer less than or equal to the value.
+#[must_use]
+pub fn floor<T: Numeric>(value: T) -> T {
+    value.floor_to()
+}
+
+/// Returns the smallest integer greater than or equal to the value.
+#[must_use]
+pub fn ceil<T: Numeric>(value: T) -> T {
+    value.ceil_to()
+}
+
+/// Returns the absolute value.
+#[must_use]
+pub fn abs<T: Numeric>(value: T) -> T {
+    value.abs_to()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(clamp(5, Some(0), Some(10)), 5);
+        assert_eq!(clamp(-5, Some(0), Some(10)), 0);
+        assert_eq!(clamp(15, Some(0), Some(10)), 10);
+        assert_eq!(clamp(15, None, Some(10)), 10);
+        assert_eq!(clamp(-15, Some(-10), None), -10);
+        assert_eq!(clamp(1.5, Some(0.), Some(1.)), 1.);
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(1.23456, 2), 1.23);
+        assert_eq!(round(1.235f32, 1), 1.2);
+        assert_eq!(round(2.5, 0), 3.);
+        assert_eq!(round(42u8, 2), 42);
+    }
+
+    #[test]
+    fn test_round_overflow() {
+        assert_eq!(round(f64::MAX, 2), f64::MAX);
+        assert_eq!(round(1.5, 400), 1.5);
+        assert_eq!(round(0., 400), 0.);
+        assert_eq!(round(3e38f32, 1), 3e38);
+    }
+
+    #[test]
+    fn test_floor_ceil() {
+        assert_eq!(floor(1.7), 1.);
+        assert_eq!(floor(-1.2), -2.);
+        assert_eq!(ceil(1.2), 2.);
+        assert_eq!(ceil(-1.7f32), -1.);
+        assert_eq!(floor(3), 3);
+        assert_eq!(ceil(3u64), 3);
+    }
+
+    #[test]
+    fn test_abs() {
+        assert_eq!(abs(-1.5), 1.5);
+        assert_eq!(abs(-3), 3);
+        assert_eq!(abs(i32::MIN), i32::MAX);
+        assert_eq!(abs(3u16), 3);
+    }
+}
@@ -0,0 +1,131 @@
+use super::collection::Truncate;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The unit in which the `truncate` modifier measures strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextUnit {
+    /// Count the `char`s of the string, the same way the [Length][crate::Length] trait does.
+    #[default]
+    Chars,
+    /// Count the UTF-8 bytes of the string.
+    Bytes,
+    /// Count the extended grapheme clusters of the string.
+    Graphemes,
+}
+
+impl TextUnit {
+    fn measure(self, value: &str) -> usize {
+        match self {
+            TextUnit::Chars => value.chars().count(),
+            TextUnit::Bytes => value.len(),
+            TextUnit::Graphemes => value.graphemes(true).count(),
+        }
+    }
+}
+
+impl Truncate for String {
+    fn truncate_to(&mut self, max: usize) {
+        truncate_string(self, max, TextUnit::Chars, None)
+    }
+}
+
+/// Shortens the string so that its size measured in `unit` is at most `max`.
+///
+/// The string is always cut on a grapheme cluster boundary so no characters or clusters
+/// are ever split. If an `ellipsis` is provided it is appended to truncated strings and
+/// counts towards `max`. If the ellipsis alone does not fit in `max`, it is omitted.
+///
+/// Strings already within `max` are left untouched.
+pub fn truncate_string(value: &mut String, max: usize, unit: TextUnit, ellipsis: Option<&str>) {
+    if unit.measure(value) <= max {
+        return;
+    }
+
+    let ellipsis = ellipsis
+        .filter(|ellipsis| unit.measure(ellipsis) <= max)
+        .unwrap_or_default();
+
+    let limit = max - unit.measure(ellipsis);
+
+    let mut end = 0;
+    let mut size = 0;
+
+    for grapheme in value.graphemes(true) {
+        let grapheme_size = unit.measure(grapheme);
+        if size + grapheme_size > limit {
+            break;
+        }
+        size += grapheme_size;
+        end += grapheme.len();
+    }
+
+    value.truncate(end);
+    value.push_str(ellipsis);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{truncate, validate_length};
+
+    #[test]
+    fn test_truncate_string_chars() {
+        let mut test = String::from("Hello world");
+        truncate_string(&mut test, 5, TextUnit::Chars, None);
+        assert_eq!(test, "Hello");
+
+        let mut test = String::from("Hello world");
+        truncate_string(&mut test, 8, TextUnit::Chars, Some("…"));
+        assert_eq!(test, "Hello w…");
+        assert!(validate_length(&test, None, Some(8), None));
+
+        let mut test = String::from("日本語のテキスト");
+        truncate_string(&mut test, 4, TextUnit::Chars, Some("..."));
+        assert_eq!(test, "日...");
+
+        let mut test = String::from("short");
+        truncate_string(&mut test, 10, TextUnit::Chars, Some("…"));
+        assert_eq!(test, "short");
+
+        let mut test = String::from("Hello world");
+        truncate(&mut test, 3);
+        assert_eq!(test, "Hel");
+    }
+
+    #[test]
+    fn test_truncate_string_bytes() {
+        // Each char is 3 bytes
+        let mut test = String::from("日本語");
+        truncate_string(&mut test, 7, TextUnit::Bytes, None);
+        assert_eq!(test, "日本");
+
+        let mut test = String::from("日本語");
+        truncate_string(&mut test, 7, TextUnit::Bytes, Some("…"));
+        assert_eq!(test, "日…");
+        assert!(test.len() <= 7);
+    }
+
+    #[test]
+    fn test_truncate_string_graphemes() {
+        // `e` followed by a combining acute accent is a single grapheme of 2 chars
+        let mut test = String::from("e\u{301}e\u{301}e\u{301}");
+        truncate_string(&mut test, 2, TextUnit::Graphemes, None);
+        assert_eq!(test, "e\u{301}e\u{301}");
+
+        // Never splits the cluster, even when counting chars
+        let mut test = String::from("e\u{301}e\u{301}e\u{301}");
+        truncate_string(&mut test, 3, TextUnit::Chars, None);
+        assert_eq!(test, "e\u{301}");
+
+        let mut test = String::from("👨‍👩‍👧‍👦👨‍👩‍👧‍👦");
+        truncate_string(&mut test, 1, TextUnit::Graphemes, None);
+        assert_eq!(test, "👨‍👩‍👧‍👦");
+    }
+
+    #[test]
+    fn test_truncate_string_ellipsis_too_long() {
+        let mut test = String::from("Hello");
+        truncate_string(&mut test, 2, TextUnit::Chars, Some("..."));
+        assert_eq!(test, "He");
+    }
+}
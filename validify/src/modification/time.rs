@@ -0,0 +1,103 @@
+use chrono::{DateTime, DurationRound, FixedOffset, TimeDelta, Utc};
+
+/// The unit to which a timestamp is truncated by the `truncate` modifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl TimeUnit {
+    fn delta(self) -> TimeDelta {
+        match self {
+            TimeUnit::Seconds => TimeDelta::seconds(1),
+            TimeUnit::Minutes => TimeDelta::minutes(1),
+            TimeUnit::Hours => TimeDelta::hours(1),
+            TimeUnit::Days => TimeDelta::days(1),
+        }
+    }
+}
+
+/// Used by the `to_utc` modifier to normalize timestamps to UTC while keeping their type.
+///
+/// For `DateTime<FixedOffset>` the offset is set to `+00:00`, keeping the same instant.
+/// Types that are already in UTC are left unchanged.
+pub trait ToUtc {
+    fn to_utc(self) -> Self;
+}
+
+impl ToUtc for DateTime<FixedOffset> {
+    fn to_utc(self) -> Self {
+        self.with_timezone(&Utc).fixed_offset()
+    }
+}
+
+impl ToUtc for DateTime<Utc> {
+    fn to_utc(self) -> Self {
+        self
+    }
+}
+
+/// Truncates the timestamp to the given unit, e.g. `TimeUnit::Minutes` sets the seconds and
+/// any subsecond values to 0. If the timestamp cannot be truncated it is returned unchanged.
+#[must_use]
+pub fn truncate_time<T>(value: T, unit: TimeUnit) -> T
+where
+    T: DurationRound + Copy,
+{
+    value.duration_trunc(unit.delta()).unwrap_or(value)
+}
+
+/// Normalizes the timestamp to UTC. See [ToUtc].
+#[must_use]
+pub fn to_utc<T: ToUtc>(value: T) -> T {
+    value.to_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap()
+    }
+
+    #[test]
+    fn test_truncate_time() {
+        let time = dt("2024-03-15 13:45:30.123");
+        assert_eq!(
+            truncate_time(time, TimeUnit::Seconds),
+            dt("2024-03-15 13:45:30.0")
+        );
+        assert_eq!(
+            truncate_time(time, TimeUnit::Minutes),
+            dt("2024-03-15 13:45:00.0")
+        );
+        assert_eq!(
+            truncate_time(time, TimeUnit::Hours),
+            dt("2024-03-15 13:00:00.0")
+        );
+        assert_eq!(
+            truncate_time(time, TimeUnit::Days),
+            dt("2024-03-15 00:00:00.0")
+        );
+
+        let time = time.and_utc();
+        assert_eq!(
+            truncate_time(time, TimeUnit::Days),
+            dt("2024-03-15 00:00:00.0").and_utc()
+        );
+    }
+
+    #[test]
+    fn test_to_utc() {
+        let time = DateTime::parse_from_rfc3339("2024-03-15T13:45:30+02:00").unwrap();
+        let utc = to_utc(time);
+        assert_eq!(utc, time);
+        assert_eq!(utc.offset().local_minus_utc(), 0);
+        assert_eq!(utc.to_rfc3339(), "2024-03-15T11:45:30+00:00");
+    }
+}
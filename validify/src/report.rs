@@ -0,0 +1,325 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Display;
+
+/// A single change made by a modifier.
+///
+/// The location follows the same scheme as [ValidationError::location][crate::ValidationError::location].
+/// The values are only available if the modified type implements `Serialize` and the
+/// modification has not been redacted.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Modification {
+    location: String,
+    modifier: &'static str,
+    before: Option<Value>,
+    after: Option<Value>,
+    redacted: bool,
+    unknown: bool,
+}
+
+impl Modification {
+    pub fn new(
+        location: String,
+        modifier: &'static str,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) -> Self {
+        Self {
+            location,
+            modifier,
+            before,
+            after,
+            redacted: false,
+            unknown: false,
+        }
+    }
+
+    /// Returns the absolute location of the modified value.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the name of the modifier, i.e. `trim`.
+    pub fn modifier(&self) -> &'static str {
+        self.modifier
+    }
+
+    /// Returns the value before the modification.
+    pub fn before(&self) -> Option<&Value> {
+        self.before.as_ref()
+    }
+
+    /// Returns the value after the modification.
+    pub fn after(&self) -> Option<&Value> {
+        self.after.as_ref()
+    }
+
+    pub fn is_redacted(&self) -> bool {
+        self.redacted
+    }
+
+    /// Returns true if the value could not be captured, i.e. it does not implement `Serialize`,
+    /// in which case the modifier ran but it is not known whether it changed the value.
+    pub fn is_unknown(&self) -> bool {
+        self.unknown
+    }
+
+    /// Removes the values from the modification, keeping only its location and modifier.
+    pub fn redact(&mut self) {
+        self.before = None;
+        self.after = None;
+        self.redacted = true;
+    }
+
+    /// Insert the provided parent to the 0th position of the current location
+    pub fn set_location<T>(&mut self, parent: T)
+    where
+        T: Display,
+    {
+        self.location.insert_str(0, &format!("/{parent}"))
+    }
+
+    /// Used when the modified struct is nested in collections. It will concat the index
+    /// to the parent so as to follow the location.
+    pub fn set_location_idx<T: Display>(&mut self, idx: T, parent: &str) {
+        self.location.insert_str(0, &format!("/{parent}/{idx}"))
+    }
+}
+
+/// Lists the changes made by [Modify::modify_with_report][crate::Modify::modify_with_report]
+/// in the order they were applied. Modifiers which left the value unchanged are not listed, unless the
+/// value could not be captured.
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+pub struct ModificationReport(Vec<Modification>);
+
+impl ModificationReport {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns a slice of all the modifications
+    pub fn modifications(&self) -> &[Modification] {
+        &self.0
+    }
+
+    pub fn modifications_mut(&mut self) -> &mut [Modification] {
+        &mut self.0
+    }
+
+    pub fn add(&mut self, modification: Modification) {
+        self.0.push(modification)
+    }
+
+    /// Append the modifications from the given report.
+    pub fn merge(&mut self, report: ModificationReport) {
+        self.0.extend(report.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Redacts the values of all modifications at or below the given location,
+    /// i.e. `/password` redacts both `/password` and `/password/0`.
+    pub fn redact(&mut self, location: &str) {
+        let prefix = format!("{location}/");
+        self.0
+            .iter_mut()
+            .filter(|m| m.location == location || m.location.starts_with(&prefix))
+            .for_each(Modification::redact)
+    }
+
+    /// Used by the derive macro to record the result of a single modifier.
+    ///
+    /// Nothing is recorded if the values are known to be equal. If `element_wise` is true and both values
+    /// are arrays or maps of the same length, each changed element is recorded separately under its index or key.
+    /// Values which could not be captured are recorded as [unknown][Modification::is_unknown].
+    #[doc(hidden)]
+    pub fn record(
+        &mut self,
+        location: &str,
+        modifier: &'static str,
+        before: Option<Value>,
+        after: Option<Value>,
+        element_wise: bool,
+    ) {
+        if before.is_some() && before == after {
+            return;
+        }
+
+        match (before, after) {
+            (Some(Value::Array(before)), Some(Value::Array(after)))
+                if element_wise && before.len() == after.len() =>
+            {
+                for (i, (before, after)) in before.into_iter().zip(after).enumerate() {
+                    if before != after {
+                        self.add(Modification::new(
+                            format!("{location}/{i}"),
+                            modifier,
+                            Some(before),
+                            Some(after),
+                        ));
+                    }
+                }
+            }
+            (Some(Value::Object(before)), Some(Value::Object(mut after)))
+                if element_wise && before.len() == after.len() =>
+            {
+                for (key, before) in before {
+                    let after = after.remove(&key);
+                    if Some(&before) != after.as_ref() {
+                        self.add(Modification::new(
+                            format!("{location}/{}", crate::escape_location(key)),
+                            modifier,
+                            Some(before),
+                            after,
+                        ));
+                    }
+                }
+            }
+            (before, after) => {
+                let unknown = before.is_none() || after.is_none();
+                self.add(Modification {
+                    unknown,
+                    ..Modification::new(location.to_string(), modifier, before, after)
+                });
+            }
+        }
+    }
+}
+
+/// Used by the derive macro to capture values for reports without requiring `Serialize`.
+///
+/// `(&Capture(&value)).capture()` resolves to [CaptureSerialize] if the value is serializable
+/// and falls back to [CaptureFallback] otherwise.
+#[doc(hidden)]
+pub mod capture {
+    use serde::Serialize;
+    use serde_json::Value;
+
+    pub struct Capture<'a, T: ?Sized>(pub &'a T);
+
+    pub trait CaptureSerialize {
+        fn capture(&self) -> Option<Value>;
+    }
+
+    impl<T: Serialize + ?Sized> CaptureSerialize for Capture<'_, T> {
+        fn capture(&self) -> Option<Value> {
+            serde_json::to_value(self.0).ok()
+        }
+    }
+
+    pub trait CaptureFallback {
+        fn capture(&self) -> Option<Value>;
+    }
+
+    impl<T: ?Sized> CaptureFallback for &Capture<'_, T> {
+        fn capture(&self) -> Option<Value> {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::capture::{Capture, CaptureFallback as _, CaptureSerialize as _};
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record() {
+        let mut report = ModificationReport::new();
+
+        report.record("/a", "trim", Some(json!("a")), Some(json!("a")), false);
+        assert!(report.is_empty());
+
+        report.record("/a", "trim", Some(json!(" a")), Some(json!("a")), false);
+        report.record(
+            "/b",
+            "trim",
+            Some(json!([" a", "b", " c"])),
+            Some(json!(["a", "b", "c"])),
+            true,
+        );
+        report.record("/c", "dedup", Some(json!([1, 1])), Some(json!([1])), true);
+        report.record("/d", "custom", None, None, false);
+        report.record(
+            "/e",
+            "trim",
+            Some(json!({ "a": " a", "b": "b" })),
+            Some(json!({ "a": "a", "b": "b" })),
+            true,
+        );
+
+        let locations: Vec<_> = report
+            .modifications()
+            .iter()
+            .map(|m| (m.location(), m.modifier()))
+            .collect();
+
+        assert_eq!(
+            locations,
+            vec![
+                ("/a", "trim"),
+                ("/b/0", "trim"),
+                ("/b/2", "trim"),
+                ("/c", "dedup"),
+                ("/d", "custom"),
+                ("/e/a", "trim")
+            ]
+        );
+        assert_eq!(report.modifications()[1].before(), Some(&json!(" a")));
+        assert_eq!(report.modifications()[1].after(), Some(&json!("a")));
+
+        let unknown: Vec<_> = report
+            .modifications()
+            .iter()
+            .filter(|m| m.is_unknown())
+            .map(|m| m.location())
+            .collect();
+        assert_eq!(unknown, vec!["/d"]);
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut report = ModificationReport::new();
+        report.record("/pw", "trim", Some(json!(" a")), Some(json!("a")), false);
+        report.record(
+            "/pws",
+            "trim",
+            Some(json!([" a"])),
+            Some(json!(["a"])),
+            true,
+        );
+        report.record("/pwx", "trim", Some(json!(" a")), Some(json!("a")), false);
+
+        report.redact("/pw");
+        report.redact("/pws");
+
+        let redacted: Vec<_> = report
+            .modifications()
+            .iter()
+            .map(|m| (m.location(), m.is_redacted(), m.before().is_some()))
+            .collect();
+
+        assert_eq!(
+            redacted,
+            vec![
+                ("/pw", true, false),
+                ("/pws/0", true, false),
+                ("/pwx", false, true)
+            ]
+        );
+    }
+
+    #[test]
+    // The borrow is what selects the fallback
+    #[allow(clippy::needless_borrow)]
+    fn test_capture() {
+        struct NotSerialize;
+
+        assert_eq!((&Capture(&"foo")).capture(), Some(json!("foo")));
+        assert_eq!((&Capture(&vec![1, 2])).capture(), Some(json!([1, 2])));
+        assert_eq!((&Capture(&NotSerialize)).capture(), None);
+    }
+}
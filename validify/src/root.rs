@@ -0,0 +1,66 @@
+//! Used by `ValidatedNewtype` and rule sets to locate the errors of the wrapped value at the
+//! value itself, so they end up at the outer field when the value is nested.
+
+use crate::{ValidationError, ValidationErrors};
+
+/// Removes the inner field's name and location segment from the errors.
+pub fn into_root(errors: &mut ValidationErrors, field: &str) {
+    let segment = format!("/{field}");
+
+    for err in errors.errors_mut() {
+        let (name, location) = match err {
+            ValidationError::Field {
+                field: ref mut name,
+                ref mut location,
+                ..
+            } => (Some(name), location),
+            ValidationError::Schema {
+                ref mut location, ..
+            } => (None, location),
+        };
+
+        if let Some(name) = name {
+            if *name == Some(field) {
+                *name = None;
+            }
+        }
+
+        let is_inner = location
+            .strip_prefix(&segment)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+        if is_inner {
+            location.replace_range(..segment.len(), "");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::into_root;
+    use crate::{ValidationError, ValidationErrors};
+
+    #[test]
+    fn test_into_root() {
+        let mut errors = ValidationErrors::new();
+
+        let mut err = ValidationError::new_field_named("0", "length");
+        err.set_location("0");
+        errors.add(err);
+
+        let mut err = ValidationError::new_field_named("0", "email");
+        err.set_location_idx(1, "0");
+        errors.add(err);
+
+        into_root(&mut errors, "0");
+
+        let errors = errors.errors();
+        assert_eq!(errors[0].field_name(), None);
+        assert_eq!(errors[0].location(), "");
+        assert_eq!(errors[1].location(), "/1");
+        assert_eq!(
+            errors[0].to_string(),
+            "Validation error: { code: length location: , field: , message: , params: {} }"
+        );
+    }
+}
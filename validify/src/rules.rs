@@ -0,0 +1,672 @@
+//! Validators producing the same errors as the ones generated by the derive macros.
+//!
+//! Each rule checks a value and returns a [ValidationError] with the rule's code and parameters,
+//! i.e. `min`, `max` and `actual` for [Length]. The derive macros expand to these, only adding the
+//! field name, location and any custom code or message, so they can be used for validating values
+//! manually, e.g. in schema validation functions, with identical results.
+//!
+//! The rules also implement [Validator] and can be used with `#[validate(with = ...)]`.
+//!
+//! ```
+//! use validify::rules::{Length, Range};
+//!
+//! let err = Length { min: Some(3), ..Default::default() }.check("ab").unwrap_err();
+//! assert_eq!(err.code(), "length");
+//! assert_eq!(err.params()["min"], 3);
+//! assert_eq!(err.params()["actual"], 2);
+//!
+//! assert!(Range { min: Some(1.), max: Some(10.) }.check(&5).is_ok());
+//! ```
+
+use crate::validation::{
+    cards::validate_credit_card,
+    contains::validate_contains,
+    email::validate_email,
+    html::validate_no_html,
+    ip::{validate_ip, validate_ip_v4, validate_ip_v6},
+    length::validate_length,
+    non_control_char::validate_non_control_character,
+    phone::validate_phone,
+    range::validate_range,
+    required::validate_required,
+    time,
+    urls::validate_url,
+};
+use crate::{traits, ValidationError, Validator};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Generates rules for string validators taking no parameters. The errors contain the `actual` value.
+macro_rules! string_rule {
+    ($($(#[$attr:meta])* $id:ident : $code:literal => $validate:path;)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct $id;
+
+            impl $id {
+                pub const CODE: &'static str = $code;
+
+                pub fn check<T>(&self, value: &T) -> Result<(), ValidationError>
+                where
+                    T: AsRef<str> + Serialize + ?Sized,
+                {
+                    if $validate(value) {
+                        return Ok(());
+                    }
+                    Err(ValidationError::new_field(Self::CODE).with_param("actual", &value))
+                }
+            }
+
+            impl<T> Validator<T> for $id
+            where
+                T: AsRef<str> + Serialize + ?Sized,
+            {
+                fn validate(&self, value: &T) -> Result<(), ValidationError> {
+                    self.check(value)
+                }
+            }
+        )*
+    };
+}
+
+string_rule! {
+    /// Checks whether the string is a valid email address.
+    Email: "email" => validate_email;
+    /// Checks whether the string is a valid URL.
+    Url: "url" => validate_url;
+    /// Checks whether the string is a valid phone number.
+    Phone: "phone" => validate_phone;
+    /// Checks whether the string is a valid credit card number.
+    CreditCard: "credit_card" => validate_credit_card;
+    /// Checks whether the string contains no control characters.
+    NonControlChar: "non_control_char" => validate_non_control_character;
+    /// Checks whether the string contains no HTML.
+    NoHtml: "no_html" => validate_no_html;
+}
+
+/// Checks whether the [length](traits::Length) of the value is within bounds or equal to `equal`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Length {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+    pub equal: Option<usize>,
+}
+
+impl Length {
+    pub const CODE: &'static str = "length";
+
+    pub fn check<T: traits::Length>(&self, value: T) -> Result<(), ValidationError> {
+        let actual = value.length();
+        if validate_length(value, self.min, self.max, self.equal) {
+            return Ok(());
+        }
+
+        let mut err = ValidationError::new_field(Self::CODE);
+        if let Some(ref min) = self.min {
+            err.add_param("min", min);
+        }
+        if let Some(ref max) = self.max {
+            err.add_param("max", max);
+        }
+        if let Some(ref equal) = self.equal {
+            err.add_param("equal", equal);
+        }
+        err.add_param("actual", &actual);
+        Err(err)
+    }
+}
+
+impl<T: ?Sized> Validator<T> for Length
+where
+    for<'a> &'a T: traits::Length,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Primitive numbers which can be validated with [Range].
+pub trait Number: Copy + Serialize {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Checks whether the number is within the inclusive bounds.
+///
+/// The bounds are compared as floats, but keep their own type in the error params, so an integer
+/// bound is reported as an integer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Range<Min = f64, Max = f64> {
+    pub min: Option<Min>,
+    pub max: Option<Max>,
+}
+
+impl<Min: Number, Max: Number> Range<Min, Max> {
+    pub const CODE: &'static str = "range";
+
+    pub fn check<T: Number>(&self, value: &T) -> Result<(), ValidationError> {
+        if validate_range(
+            value.to_f64(),
+            self.min.map(Number::to_f64),
+            self.max.map(Number::to_f64),
+        ) {
+            return Ok(());
+        }
+
+        let mut err = ValidationError::new_field(Self::CODE);
+        if let Some(ref min) = self.min {
+            err.add_param("min", min);
+        }
+        if let Some(ref max) = self.max {
+            err.add_param("max", max);
+        }
+        err.add_param("actual", value);
+        Err(err)
+    }
+}
+
+impl<T: Number, Min: Number, Max: Number> Validator<T> for Range<Min, Max> {
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Checks whether the value [contains](traits::Contains) the target `value`, or does not if `not` is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Contains<C> {
+    pub value: C,
+    pub not: bool,
+}
+
+impl<C> Contains<C> {
+    pub fn code(&self) -> &'static str {
+        if self.not {
+            "contains_not"
+        } else {
+            "contains"
+        }
+    }
+
+    /// Same as [Contains::check], but the error does not contain the target, so it does not
+    /// have to be serializable.
+    pub fn check_without_target<T>(&self, value: T) -> Result<(), ValidationError>
+    where
+        T: traits::Contains<C>,
+    {
+        if validate_contains(value, &self.value, self.not) {
+            return Ok(());
+        }
+        Err(ValidationError::new_field(self.code()))
+    }
+}
+
+impl<C: Serialize> Contains<C> {
+    pub fn check<T: traits::Contains<C>>(&self, value: T) -> Result<(), ValidationError> {
+        self.check_without_target(value)
+            .map_err(|err| err.with_param("target", &self.value))
+    }
+}
+
+impl<T: ?Sized, C: Serialize> Validator<T> for Contains<C>
+where
+    for<'a> &'a T: traits::Contains<C>,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Checks whether the value is an element of the collection, or is not if `not` is set.
+/// The collection is usually a reference.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct In<C> {
+    pub collection: C,
+    pub not: bool,
+}
+
+impl<C: Copy> In<C> {
+    pub fn code(&self) -> &'static str {
+        if self.not {
+            "not_in"
+        } else {
+            "in"
+        }
+    }
+
+    pub fn check<T>(&self, value: &T) -> Result<(), ValidationError>
+    where
+        C: traits::Contains<T>,
+    {
+        if validate_contains(self.collection, value, self.not) {
+            return Ok(());
+        }
+        Err(ValidationError::new_field(self.code()))
+    }
+}
+
+impl<T, C> Validator<T> for In<C>
+where
+    C: Copy + traits::Contains<T>,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// The IP version checked by [Ip].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFormat {
+    V4,
+    V6,
+}
+
+/// Checks whether the string is an IP address of the given version, or of any if unspecified.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ip {
+    pub format: Option<IpFormat>,
+}
+
+impl Ip {
+    pub const CODE: &'static str = "ip";
+
+    pub fn check<T>(&self, value: &T) -> Result<(), ValidationError>
+    where
+        T: AsRef<str> + Serialize + ?Sized,
+    {
+        let valid = match self.format {
+            Some(IpFormat::V4) => validate_ip_v4(value),
+            Some(IpFormat::V6) => validate_ip_v6(value),
+            None => validate_ip(value),
+        };
+        if valid {
+            return Ok(());
+        }
+        Err(ValidationError::new_field(Self::CODE).with_param("actual", &value))
+    }
+}
+
+impl<T> Validator<T> for Ip
+where
+    T: AsRef<str> + Serialize + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Checks whether the option is `Some`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Required;
+
+impl Required {
+    pub const CODE: &'static str = "required";
+
+    pub fn check<T>(&self, value: &Option<T>) -> Result<(), ValidationError> {
+        if validate_required(value) {
+            return Ok(());
+        }
+        Err(ValidationError::new_field(Self::CODE))
+    }
+}
+
+impl<T> Validator<Option<T>> for Required {
+    fn validate(&self, value: &Option<T>) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Checks whether the string matches the regular expression.
+#[derive(Debug, Clone, Copy)]
+pub struct Regex<'a>(pub &'a regex::Regex);
+
+impl Regex<'_> {
+    pub const CODE: &'static str = "regex";
+
+    pub fn check<T>(&self, value: &T) -> Result<(), ValidationError>
+    where
+        T: AsRef<str> + Serialize + ?Sized,
+    {
+        if self.0.is_match(value.as_ref()) {
+            return Ok(());
+        }
+        Err(Self::error(value))
+    }
+
+    /// The error of a value not matching the expression. Used by the derive, which calls `is_match`
+    /// on the annotated path directly so any type with that method, e.g. a `RegexSet`, can be used.
+    pub fn error<T>(value: &T) -> ValidationError
+    where
+        T: Serialize + ?Sized,
+    {
+        ValidationError::new_field(Self::CODE).with_param("actual", &value)
+    }
+}
+
+impl<T> Validator<T> for Regex<'_>
+where
+    T: AsRef<str> + Serialize + ?Sized,
+{
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+/// Dates and datetimes which can be validated with [Time].
+pub trait Timestamp: Copy + Ord {
+    /// Formats the value for error params the way chrono serializes it, without
+    /// requiring its `serde` feature.
+    fn to_param(&self) -> String;
+
+    fn before_now(&self, inclusive: bool) -> bool;
+
+    fn after_now(&self, inclusive: bool) -> bool;
+
+    fn before_from_now(&self, duration: Duration) -> bool;
+
+    fn after_from_now(&self, duration: Duration) -> bool;
+
+    fn in_period(&self, start: &Self, duration: Duration) -> bool;
+
+    fn checked_add(&self, duration: Duration) -> Option<Self>;
+}
+
+impl Timestamp for NaiveDate {
+    fn to_param(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn before_now(&self, inclusive: bool) -> bool {
+        time::before_today(self, inclusive)
+    }
+
+    fn after_now(&self, inclusive: bool) -> bool {
+        time::after_today(self, inclusive)
+    }
+
+    fn before_from_now(&self, duration: Duration) -> bool {
+        time::before_from_now_date(self, duration)
+    }
+
+    fn after_from_now(&self, duration: Duration) -> bool {
+        time::after_from_now_date(self, duration)
+    }
+
+    fn in_period(&self, start: &Self, duration: Duration) -> bool {
+        time::in_period_date(self, start, duration)
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(duration)
+    }
+}
+
+impl Timestamp for NaiveDateTime {
+    fn to_param(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn before_now(&self, inclusive: bool) -> bool {
+        time::before_now(self, inclusive)
+    }
+
+    fn after_now(&self, inclusive: bool) -> bool {
+        time::after_now(self, inclusive)
+    }
+
+    fn before_from_now(&self, duration: Duration) -> bool {
+        time::before_from_now(self, duration)
+    }
+
+    fn after_from_now(&self, duration: Duration) -> bool {
+        time::after_from_now(self, duration)
+    }
+
+    fn in_period(&self, start: &Self, duration: Duration) -> bool {
+        time::in_period(self, start, duration)
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(duration)
+    }
+}
+
+/// Checks a date or datetime against the current time or a target. For dates, now is today.
+#[derive(Debug, Clone, Copy)]
+pub enum Time<T> {
+    BeforeNow {
+        inclusive: bool,
+    },
+    AfterNow {
+        inclusive: bool,
+    },
+    Before {
+        target: T,
+        inclusive: bool,
+    },
+    After {
+        target: T,
+        inclusive: bool,
+    },
+    /// The value must be at least `duration` before now.
+    BeforeFromNow {
+        duration: Duration,
+    },
+    /// The value must be at least `duration` after now.
+    AfterFromNow {
+        duration: Duration,
+    },
+    /// The value must be between `start` and `start + duration`. The duration can be negative.
+    InPeriod {
+        start: T,
+        duration: Duration,
+    },
+}
+
+impl<T: Timestamp> Time<T> {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BeforeNow { .. } => "before_now",
+            Self::AfterNow { .. } => "after_now",
+            Self::Before {
+                inclusive: true, ..
+            } => "before_or_equal",
+            Self::Before { .. } => "before",
+            Self::After {
+                inclusive: true, ..
+            } => "after_or_equal",
+            Self::After { .. } => "after",
+            Self::BeforeFromNow { .. } => "before_from_now",
+            Self::AfterFromNow { .. } => "after_from_now",
+            Self::InPeriod { .. } => "in_period",
+        }
+    }
+
+    pub fn check(&self, value: &T) -> Result<(), ValidationError> {
+        let valid = match *self {
+            Self::BeforeNow { inclusive } => value.before_now(inclusive),
+            Self::AfterNow { inclusive } => value.after_now(inclusive),
+            Self::Before { target, inclusive } => *value < target || inclusive && *value == target,
+            Self::After { target, inclusive } => *value > target || inclusive && *value == target,
+            Self::BeforeFromNow { duration } => value.before_from_now(duration),
+            Self::AfterFromNow { duration } => value.after_from_now(duration),
+            Self::InPeriod { start, duration } => value.in_period(&start, duration),
+        };
+
+        if valid {
+            return Ok(());
+        }
+
+        let mut err = ValidationError::new_field(self.code());
+        err.add_param("actual", &value.to_param());
+
+        match *self {
+            Self::Before { ref target, .. } | Self::After { ref target, .. } => {
+                err.add_param("target", &target.to_param());
+            }
+            Self::InPeriod {
+                ref start,
+                duration,
+            } => {
+                if let Some(end) = start.checked_add(duration) {
+                    let (from, to) = if end < *start {
+                        (end, *start)
+                    } else {
+                        (*start, end)
+                    };
+                    err.add_param("from", &from.to_param());
+                    err.add_param("to", &to.to_param());
+                }
+            }
+            _ => {}
+        }
+
+        Err(err)
+    }
+}
+
+impl<T: Timestamp> Validator<T> for Time<T> {
+    fn validate(&self, value: &T) -> Result<(), ValidationError> {
+        self.check(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Days, Utc};
+
+    #[test]
+    fn test_string_rules() {
+        assert!(Email.check("foo@bar.com").is_ok());
+        let err = Email.check(&String::from("foo")).unwrap_err();
+        assert_eq!(err.code(), "email");
+        assert_eq!(err.params()["actual"], "foo");
+
+        assert!(Ip { format: None }.check("::1").is_ok());
+        let err = Ip {
+            format: Some(IpFormat::V4),
+        }
+        .check("::1")
+        .unwrap_err();
+        assert_eq!(err.code(), "ip");
+
+        let re = regex::Regex::new("^a+$").unwrap();
+        assert!(Regex(&re).check("aa").is_ok());
+        assert_eq!(Regex(&re).check("b").unwrap_err().code(), "regex");
+    }
+
+    #[test]
+    fn test_length_and_range() {
+        let err = Length {
+            max: Some(1),
+            ..Default::default()
+        }
+        .check(vec![1, 2])
+        .unwrap_err();
+        assert_eq!(err.params()["max"], 1);
+        assert_eq!(err.params()["actual"], 2);
+        assert!(!err.params().contains_key("min"));
+
+        let err = Range {
+            min: Some(1.),
+            max: None::<f64>,
+        }
+        .check(&0u8)
+        .unwrap_err();
+        assert_eq!(err.code(), "range");
+        assert_eq!(err.params()["min"], 1.);
+        assert_eq!(err.params()["actual"], 0);
+
+        let err = Range {
+            min: Some(1u8),
+            max: Some(2.5),
+        }
+        .check(&3)
+        .unwrap_err();
+        assert_eq!(err.params()["min"], serde_json::json!(1));
+        assert_eq!(err.params()["max"], 2.5);
+    }
+
+    #[test]
+    fn test_contains_and_in() {
+        let contains = Contains {
+            value: "bar",
+            not: true,
+        };
+        let err = contains.check("foobar").unwrap_err();
+        assert_eq!(err.code(), "contains_not");
+        assert_eq!(err.params()["target"], "bar");
+
+        let roles = vec!["admin", "user"];
+        assert!(In {
+            collection: &roles,
+            not: false
+        }
+        .check(&"user")
+        .is_ok());
+        let err = In {
+            collection: &roles,
+            not: true,
+        }
+        .check(&"user")
+        .unwrap_err();
+        assert_eq!(err.code(), "not_in");
+
+        assert!(Validator::validate(
+            &In {
+                collection: &roles,
+                not: false
+            },
+            &"guest"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        let today = Utc::now().date_naive();
+        let tomorrow = today.checked_add_days(Days::new(1)).unwrap();
+
+        assert!(Time::BeforeNow { inclusive: true }.check(&today).is_ok());
+        assert!(Time::BeforeNow { inclusive: false }.check(&today).is_err());
+
+        let err = Time::After {
+            target: tomorrow,
+            inclusive: true,
+        }
+        .check(&today)
+        .unwrap_err();
+        assert_eq!(err.code(), "after_or_equal");
+        assert_eq!(err.params()["target"], tomorrow.to_string());
+
+        let err = Time::InPeriod {
+            start: tomorrow,
+            duration: Duration::days(-1),
+        }
+        .check(&tomorrow.checked_add_days(Days::new(1)).unwrap())
+        .unwrap_err();
+        assert_eq!(err.params()["from"], today.to_string());
+        assert_eq!(err.params()["to"], tomorrow.to_string());
+    }
+
+    #[test]
+    fn test_required() {
+        assert!(Required.check(&Some(1)).is_ok());
+        assert_eq!(Required.check(&None::<u8>).unwrap_err().code(), "required");
+    }
+}
@@ -0,0 +1,86 @@
+//! Used by the derive macro to iterate over hashed collections in a deterministic order.
+//!
+//! `(&Items::new(collection.iter())).sorted()` resolves to [SortedItems] if the elements,
+//! or the keys in case of maps, implement `Ord` and falls back to [UnsortedItems] otherwise,
+//! keeping the iteration order of the collection. Map entries are
+//! sorted by their keys, so both `iter` and `iter_mut` can be used.
+
+use std::cell::Cell;
+
+pub struct Items<T>(Cell<Vec<T>>);
+
+impl<T> Items<T> {
+    pub fn new(items: impl Iterator<Item = T>) -> Self {
+        Self(Cell::new(items.collect()))
+    }
+}
+
+pub trait SortedItems {
+    type Item;
+    fn sorted(&self) -> Vec<Self::Item>;
+}
+
+impl<'a, T: Ord + ?Sized> SortedItems for Items<&'a T> {
+    type Item = &'a T;
+
+    fn sorted(&self) -> Vec<&'a T> {
+        let mut items = self.0.take();
+        items.sort();
+        items
+    }
+}
+
+impl<'a, K: Ord + ?Sized, V> SortedItems for Items<(&'a K, V)> {
+    type Item = (&'a K, V);
+
+    fn sorted(&self) -> Vec<(&'a K, V)> {
+        let mut items = self.0.take();
+        items.sort_by(|a, b| a.0.cmp(b.0));
+        items
+    }
+}
+
+pub trait UnsortedItems {
+    type Item;
+    fn sorted(&self) -> Vec<Self::Item>;
+}
+
+impl<T> UnsortedItems for &Items<T> {
+    type Item = T;
+
+    fn sorted(&self) -> Vec<T> {
+        self.0.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Items, SortedItems as _, UnsortedItems as _};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    // The borrow is what selects the fallback
+    #[allow(clippy::needless_borrow)]
+    fn test_sorted() {
+        let set = HashSet::from([5, 3, 9, 1]);
+        let items = (&Items::new(set.iter())).sorted();
+        assert_eq!(items, vec![&1, &3, &5, &9]);
+
+        let map = HashMap::from([("b", 1), ("c", 0), ("a", 2)]);
+        let items = (&Items::new(map.iter())).sorted();
+        assert_eq!(items, vec![(&"a", &2), (&"b", &1), (&"c", &0)]);
+
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Unordered(u8);
+
+        let set = HashSet::from([Unordered(1)]);
+        let items = (&Items::new(set.iter())).sorted();
+        assert_eq!(items, vec![&Unordered(1)]);
+
+        let mut map = HashMap::from([(2, 'b'), (1, 'a')]);
+        for (_, v) in (&Items::new(map.iter_mut())).sorted() {
+            v.make_ascii_uppercase();
+        }
+        assert_eq!(map[&1], 'A');
+    }
+}
@@ -1,6 +1,6 @@
 use indexmap::{IndexMap, IndexSet};
 use std::borrow::Cow;
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 /// Used by the [validate_length][crate::validation::length::validate_length]
@@ -47,6 +47,12 @@ impl<T> Length for Vec<T> {
     }
 }
 
+impl<T> Length for VecDeque<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
 impl<T> Length for &[T] {
     fn length(&self) -> usize {
         self.len()
@@ -0,0 +1,52 @@
+/// Validates that the given string does not contain any HTML markup.
+///
+/// Follows the HTML tokenizer rules, i.e. a `<` only starts markup when it is followed by
+/// an ASCII letter (start tag), `/` (end tag), `!` (comments, doctypes) or `?` (processing
+/// instructions). This means strings like `a < b` are valid.
+#[must_use]
+pub fn validate_no_html<T>(val: T) -> bool
+where
+    T: AsRef<str>,
+{
+    let mut chars = val.as_ref().chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '<' {
+            continue;
+        }
+
+        if let Some(next) = chars.peek() {
+            if next.is_ascii_alphabetic() || matches!(next, '/' | '!' | '?') {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_no_html;
+
+    #[test]
+    fn test_no_html() {
+        let tests = vec![
+            ("Plain text", true),
+            ("a < b && b > c", true),
+            ("1 <2", true),
+            ("ends with <", true),
+            ("<b>bold</b>", false),
+            ("</div>", false),
+            ("<!-- comment -->", false),
+            ("<!DOCTYPE html>", false),
+            ("<?xml version=\"1.0\"?>", false),
+            ("text <script>alert(1)</script>", false),
+            ("<img src=x onerror=alert(1)>", false),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(validate_no_html(input), expected, "{input}");
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub mod cards;
 pub mod contains;
 pub mod email;
+pub mod html;
 pub mod ip;
 pub mod length;
 pub mod non_control_char;
@@ -0,0 +1,240 @@
+//! Trait bounds of the generated impls.
+//!
+//! Like serde, bounds are inferred only for the type parameters appearing in nested fields,
+//! since those are the only values the generated code calls the traits on. Associated types
+//! of parameters, i.e. `T::Item`, are bounded instead of the parameter itself.
+//! Inferred bounds are replaced with the ones from `#[validate(bound = "...")]` if present, or with the ones
+//! of a single impl from `#[validate(bound(validate = "...", modify = "...", validify = "...", payload = "..."))]`.
+
+use crate::fields::FieldInfo;
+use proc_macro_error::abort;
+use syn::{punctuated::Punctuated, spanned::Spanned, Token};
+
+const VALIDATE: &str = "validate";
+pub const BOUND: &str = "bound";
+
+/// The impls whose bounds can be specified separately with `bound(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundTarget {
+    Validate,
+    Modify,
+    Validify,
+    /// The payload struct and its conversions, including `ValidifyPayload`.
+    Payload,
+}
+
+impl BoundTarget {
+    const ALL: [BoundTarget; 4] = [
+        BoundTarget::Validate,
+        BoundTarget::Modify,
+        BoundTarget::Validify,
+        BoundTarget::Payload,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            BoundTarget::Validate => "validate",
+            BoundTarget::Modify => "modify",
+            BoundTarget::Validify => "validify",
+            BoundTarget::Payload => "payload",
+        }
+    }
+}
+
+/// Returns the generics extended with `bound` for every type parameter used in the fields
+/// matching `filter`.
+pub fn with_bound<'a>(
+    generics: &syn::Generics,
+    attrs: &[syn::Attribute],
+    target: BoundTarget,
+    fields: impl IntoIterator<Item = &'a FieldInfo>,
+    filter: impl Fn(&FieldInfo) -> bool,
+    bound: proc_macro2::TokenStream,
+) -> syn::Generics {
+    let bounded = bounded_types(
+        generics,
+        fields
+            .into_iter()
+            .filter(|field| filter(field))
+            .map(|field| &field.field.ty),
+    );
+
+    let predicates = bounded
+        .iter()
+        .map(|ty| syn::parse_quote!(#ty: #bound))
+        .collect();
+
+    with_predicates(generics, attrs, target, predicates)
+}
+
+/// Returns the generics extended with the given predicates, or the custom ones of the target
+/// if they are specified on the container.
+pub fn with_predicates(
+    generics: &syn::Generics,
+    attrs: &[syn::Attribute],
+    target: BoundTarget,
+    predicates: Vec<syn::WherePredicate>,
+) -> syn::Generics {
+    let mut generics = generics.clone();
+    let predicates = find_custom_bound(attrs, target).unwrap_or(predicates);
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Returns the types in need of a bound, i.e. the type parameters and the associated types
+/// of type parameters used in the given types.
+pub fn bounded_types<'a>(
+    generics: &syn::Generics,
+    types: impl IntoIterator<Item = &'a syn::Type>,
+) -> Vec<syn::Type> {
+    let params = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    let mut bounded = vec![];
+
+    if params.is_empty() {
+        return bounded;
+    }
+
+    for ty in types {
+        collect_bounded_types(ty, &params, &mut bounded);
+    }
+
+    bounded
+}
+
+/// Returns true if the type is one of the type parameters.
+pub fn is_type_param(generics: &syn::Generics, ty: &syn::TypePath) -> bool {
+    ty.qself.is_none()
+        && generics
+            .type_params()
+            .any(|param| ty.path.is_ident(&param.ident))
+}
+
+fn collect_bounded_types(ty: &syn::Type, params: &[&syn::Ident], bounded: &mut Vec<syn::Type>) {
+    match ty {
+        syn::Type::Path(path) => {
+            if let Some(ref qself) = path.qself {
+                collect_bounded_types(&qself.ty, params, bounded);
+            } else if path.path.leading_colon.is_none() {
+                let first = &path.path.segments[0];
+                if first.arguments.is_none() && params.contains(&&first.ident) {
+                    if !bounded.contains(ty) {
+                        bounded.push(ty.clone());
+                    }
+                    return;
+                }
+            }
+
+            for segment in path.path.segments.iter() {
+                match segment.arguments {
+                    syn::PathArguments::AngleBracketed(ref args) => {
+                        for arg in args.args.iter() {
+                            match arg {
+                                syn::GenericArgument::Type(ty) => {
+                                    collect_bounded_types(ty, params, bounded)
+                                }
+                                syn::GenericArgument::AssocType(assoc) => {
+                                    collect_bounded_types(&assoc.ty, params, bounded)
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    syn::PathArguments::Parenthesized(ref args) => {
+                        for ty in args.inputs.iter() {
+                            collect_bounded_types(ty, params, bounded)
+                        }
+                        if let syn::ReturnType::Type(_, ref ty) = args.output {
+                            collect_bounded_types(ty, params, bounded)
+                        }
+                    }
+                    syn::PathArguments::None => {}
+                }
+            }
+        }
+        syn::Type::Reference(r) => collect_bounded_types(&r.elem, params, bounded),
+        syn::Type::Slice(s) => collect_bounded_types(&s.elem, params, bounded),
+        syn::Type::Array(a) => collect_bounded_types(&a.elem, params, bounded),
+        syn::Type::Ptr(p) => collect_bounded_types(&p.elem, params, bounded),
+        syn::Type::Paren(p) => collect_bounded_types(&p.elem, params, bounded),
+        syn::Type::Group(g) => collect_bounded_types(&g.elem, params, bounded),
+        syn::Type::Tuple(t) => t
+            .elems
+            .iter()
+            .for_each(|ty| collect_bounded_types(ty, params, bounded)),
+        _ => {}
+    }
+}
+
+/// Parses the predicates of the target from `#[validate(bound(target = "..."))]`, falling back
+/// to the ones of `#[validate(bound = "...")]`.
+fn find_custom_bound(
+    attrs: &[syn::Attribute],
+    target: BoundTarget,
+) -> Option<Vec<syn::WherePredicate>> {
+    let mut bound = None;
+    let mut target_bound = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(VALIDATE)) {
+        let syn::Meta::List(ref list) = attr.meta else {
+            continue;
+        };
+
+        let res = list.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(BOUND) {
+                // Other arguments, e.g. `remote`, are handled by the derives
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<syn::LitStr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::Group>()?;
+                }
+                return Ok(());
+            }
+
+            if meta.input.peek(Token![=]) {
+                bound = Some(parse_predicates(&meta.value()?.parse::<syn::LitStr>()?));
+                return Ok(());
+            }
+
+            meta.parse_nested_meta(|meta| {
+                let Some(found) = BoundTarget::ALL
+                    .into_iter()
+                    .find(|target| meta.path.is_ident(target.name()))
+                else {
+                    return Err(meta.error(
+                        "Unrecognized bound, accepted are: validate, modify, validify, payload",
+                    ));
+                };
+
+                let predicates = parse_predicates(&meta.value()?.parse::<syn::LitStr>()?);
+                if found == target {
+                    target_bound = Some(predicates);
+                }
+
+                Ok(())
+            })
+        });
+
+        if let Err(e) = res {
+            abort!(attr.span(), "{}", e)
+        }
+    }
+
+    target_bound.or(bound)
+}
+
+fn parse_predicates(lit: &syn::LitStr) -> Vec<syn::WherePredicate> {
+    lit.parse_with(Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated)
+        .unwrap_or_else(|e| {
+            abort!(
+                lit.span(),
+                "Invalid bound, expected where predicates: {}",
+                e
+            )
+        })
+        .into_iter()
+        .collect()
+}
@@ -41,6 +41,13 @@ impl Variants {
         Self(variants)
     }
 
+    /// See [Fields::limit_recursion].
+    pub fn limit_recursion(&mut self) {
+        for variant in self.0.iter_mut() {
+            variant.fields.limit_recursion();
+        }
+    }
+
     /// Output the necessary tokens for variant, and in turn field
     /// validation when implementing `Validate`.
     pub fn to_validate_tokens(&self) -> proc_macro2::TokenStream {
@@ -78,7 +85,41 @@ impl Variants {
         quote!(match self { #field_validation })
     }
 
+    /// Returns the fields of all variants.
+    pub fn fields(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.0.iter().flat_map(|variant| variant.fields.0.iter())
+    }
+
+    /// Returns the fields of all variants paired with the variant name.
+    pub fn variant_fields(&self) -> impl Iterator<Item = (String, &FieldInfo)> {
+        self.0.iter().flat_map(|variant| {
+            let name = variant.ident.to_string();
+            variant
+                .fields
+                .0
+                .iter()
+                .map(move |field| (name.clone(), field))
+        })
+    }
+
     pub fn to_modify_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        self.to_variant_modify_tokens(Fields::to_modify_tokens)
+    }
+
+    /// Output the match arms of the modifiers for `modify_with_report`.
+    pub fn to_report_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        self.to_variant_modify_tokens(Fields::to_report_tokens)
+    }
+
+    /// Output the match arms of the modifiers running only after successful validation.
+    pub fn to_after_validate_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        self.to_variant_modify_tokens(Fields::to_after_validate_tokens)
+    }
+
+    fn to_variant_modify_tokens(
+        &self,
+        field_tokens: impl Fn(&Fields) -> Vec<proc_macro2::TokenStream>,
+    ) -> Vec<proc_macro2::TokenStream> {
         let mut modifiers = vec![];
 
         for variant in self.0.iter() {
@@ -95,7 +136,7 @@ impl Variants {
 
             let variant_field_tokens = quote!(#(ref mut #variant_fields),*);
 
-            let field_modifiers = fields.to_modify_tokens();
+            let field_modifiers = field_tokens(fields);
 
             if *named {
                 let tokens =
@@ -150,7 +191,10 @@ impl Fields {
                         .unwrap_or(NameOrIndex::Index(i));
 
                     let validations = collect_validation(field);
-                    let modifiers = collect_modifiers(field);
+                    let (mut modifiers, after_validate_modifiers) = collect_modifiers(field);
+
+                    // Modifiers of rule sets run before the field's own
+                    modifiers.splice(0..0, rule_set_modifiers(&validations));
 
                     // The original name refers to the field name set with serde rename.
                     let original_name = crate::serde::find_rename(field);
@@ -161,6 +205,7 @@ impl Fields {
                         original_name,
                         validations,
                         modifiers,
+                        after_validate_modifiers,
                         rename_rule,
                     )
                 }
@@ -170,6 +215,18 @@ impl Fields {
         Self(fields)
     }
 
+    /// Limits the depth of nested fields to `validify::DEFAULT_MAX_DEPTH` unless they are annotated
+    /// with `max_depth`, since untrusted input of recursive types could otherwise overflow the stack.
+    /// All nested fields are limited, as recursion can go through other types, i.e. `A` containing
+    /// `B` containing `A`.
+    pub fn limit_recursion(&mut self) {
+        for field_info in self.0.iter_mut() {
+            if field_info.is_nested() && field_info.max_depth().is_none() {
+                field_info.validations.push(Validator::MaxDepth(None));
+            }
+        }
+    }
+
     /// Output the necessary tokens for field validation when implementing `Validate`.
     pub fn to_validate_tokens(&self) -> Vec<proc_macro2::TokenStream> {
         let mut validations = vec![];
@@ -193,6 +250,30 @@ impl Fields {
 
         modifications
     }
+
+    /// Output the modification tokens for `modify_with_report`.
+    pub fn to_report_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut modifications = vec![];
+
+        for field_info in self.0.iter() {
+            let modification = field_info.to_report_tokens();
+            modifications.extend(modification);
+        }
+
+        modifications
+    }
+
+    /// Output the tokens of the modifiers running only after successful validation.
+    pub fn to_after_validate_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut modifications = vec![];
+
+        for field_info in self.0.iter() {
+            let modification = field_info.to_after_validate_tokens();
+            modifications.extend(modification);
+        }
+
+        modifications
+    }
 }
 
 /// Holds the combined validations and modifiers for one field.
@@ -214,6 +295,9 @@ pub struct FieldInfo {
     /// Modifier annotations
     pub modifiers: Vec<Modifier>,
 
+    /// Modifier annotations which run only after successful validation
+    pub after_validate_modifiers: Vec<Modifier>,
+
     /// Obtained from `serde(rename_all)`
     pub rename_rule: Option<RenameRule>,
 
@@ -228,6 +312,7 @@ impl FieldInfo {
         original_name: Option<String>,
         validations: Vec<Validator>,
         modifiers: Vec<Modifier>,
+        after_validate_modifiers: Vec<Modifier>,
         rename_rule: Option<RenameRule>,
     ) -> Self {
         FieldInfo {
@@ -236,6 +321,7 @@ impl FieldInfo {
             original_name,
             validations,
             modifiers,
+            after_validate_modifiers,
             rename_rule,
             ident_override: None,
         }
@@ -280,7 +366,7 @@ impl FieldInfo {
         child_validation
     }
 
-    /// Returns the modification tokens as the first element and any nested validifes as the second.
+    /// Returns the modification tokens for the `impl Modify` block.
     pub fn to_modify_tokens(&self) -> Vec<proc_macro2::TokenStream> {
         let mut modifications = vec![];
 
@@ -292,6 +378,69 @@ impl FieldInfo {
         modifications
     }
 
+    /// Returns the modification tokens for `modify_with_report`.
+    pub fn to_report_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut modifications = vec![];
+
+        for modifier in self.modifiers.iter() {
+            let tokens = modifier.to_report_tokens(self);
+            modifications.push(tokens);
+        }
+
+        modifications
+    }
+
+    /// Returns the tokens of the modifiers running only after successful validation.
+    pub fn to_after_validate_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut modifications = vec![];
+
+        for modifier in self.after_validate_modifiers.iter() {
+            let tokens = modifier.to_after_validate_tokens(self);
+            modifications.push(tokens);
+        }
+
+        modifications
+    }
+
+    /// Generates the tokens used to iterate over collection fields. Options are unwrapped
+    /// beforehand so their ident is used directly.
+    pub fn collection_param_tokens(&self) -> proc_macro2::TokenStream {
+        if let Some(ref ident) = self.ident_override {
+            return quote!(#ident);
+        }
+
+        let ident = self.field.ident.as_ref();
+        if self.is_wrapped() {
+            return quote!(#ident);
+        }
+        quote!(self.#ident)
+    }
+
+    /// Generates the call to the given iterator method of a collection field, sorting
+    /// the elements of hashed collections.
+    pub fn collection_iter_tokens(
+        &self,
+        method: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let param = self.collection_param_tokens();
+        if is_hashed(unwrap_type(&self.field.ty)) {
+            sorted_tokens(quote!(#param.#method()))
+        } else {
+            quote!(#param.#method())
+        }
+    }
+
+    /// Generates a reference to the whole field, including any `Option`, used to capture
+    /// its value in modification reports.
+    pub fn capture_param_tokens(&self) -> proc_macro2::TokenStream {
+        if let Some(ref ident) = self.ident_override {
+            return quote!(&*#ident);
+        }
+
+        let ident = &self.field.ident;
+        quote!(&self.#ident)
+    }
+
     /// Generates the tokens that get passed to validation functions.
     ///
     /// If the field has an ident override in case of enums, quote it directly.
@@ -310,7 +459,7 @@ impl FieldInfo {
 
         let ident = &self.field.ident;
 
-        if self.is_option() {
+        if self.is_wrapped() {
             return quote!(#ident);
         }
 
@@ -351,86 +500,110 @@ impl FieldInfo {
         }
     }
 
-    /// Wrap the provided tokens in an `if let Some` block if the field is an option.
+    /// Wrap the provided tokens in `if let Some` blocks and dereferences of smart pointers if the field
+    /// is wrapped in any, binding the field ident to a reference of the innermost value.
     pub fn wrap_tokens_if_option(
         &self,
         tokens: proc_macro2::TokenStream,
     ) -> proc_macro2::TokenStream {
-        if !self.is_option() {
+        if !self.is_wrapped() {
             return tokens;
         }
 
-        let field_ident = self
-            .ident_override
-            .as_ref()
-            .map(|i| quote!(#i))
-            .unwrap_or_else(|| {
-                let i = self.field.ident.as_ref().unwrap();
-                quote!(#i)
-            });
-
-        let mut this = if self.is_reference() {
-            quote!(#field_ident)
-        } else {
-            quote!(ref #field_ident)
-        };
-
-        let field_ident = if self.ident_override.is_some() {
-            field_ident
-        } else {
-            quote!(self.#field_ident)
+        let field_ident = match self.ident_override {
+            Some(ref ident) => ident,
+            None => self.field.ident.as_ref().unwrap(),
         };
 
-        let mut ty = &self.field.ty;
+        let unwrapped = unwrap_type_tokens(&self.field.ty, &quote!(#field_ident), false, &|_| {
+            tokens.clone()
+        });
 
-        while let Some(typ) = try_extract_option(ty) {
-            this = quote!(Some(#this));
-            ty = typ;
+        if self.ident_override.is_some() {
+            return unwrapped;
         }
 
         quote!(
-            if let #this = #field_ident {
-                #tokens
+            {
+                let #field_ident = &self.#field_ident;
+                #unwrapped
             }
         )
     }
 
-    /// Wrap the quoted output of a validation with a for loop if
-    /// the field type is a collection.
-    pub fn wrap_validator_if_collection(
+    /// Returns the depth limit of nested validation and modification, if the field is limited.
+    pub fn max_depth(&self) -> Option<proc_macro2::TokenStream> {
+        self.validations.iter().find_map(|v| match v {
+            Validator::MaxDepth(Some(depth)) => Some(quote!(#depth)),
+            Validator::MaxDepth(None) => Some(quote!(::validify::DEFAULT_MAX_DEPTH)),
+            _ => None,
+        })
+    }
+
+    /// Runs the nested tokens only if the depth limit of the field has not been reached,
+    /// otherwise runs the `exceeded` tokens.
+    pub fn wrap_depth_guard(
         &self,
-        param: proc_macro2::TokenStream,
         tokens: proc_macro2::TokenStream,
+        exceeded: proc_macro2::TokenStream,
     ) -> proc_macro2::TokenStream {
-        let field_name = self.name();
+        let Some(max_depth) = self.max_depth() else {
+            return tokens;
+        };
 
-        // When we're using an option, we'll have the field unwrapped, so we should not access it
-        // through `self`.
-        let prefix = (!self.is_option() && self.ident_override.is_none()).then(|| quote! { self. });
-
-        // When iterating over a list, the iterator has Item=T, while a map yields Item=(K, V), and
-        // we're only interested in V.
-        if self.is_list() {
-            quote!(
-                for (i, item) in #prefix #param.iter().enumerate() {
-                    if let Err(mut errs) = item.validate() {
-                        errs.errors_mut().iter_mut().for_each(|err| err.set_location_idx(i, #field_name));
-                        errors.merge(errs);
-                    }
+        quote!(
+            match ::validify::__depth::enter(::core::any::type_name::<Self>(), #max_depth) {
+                Some(_guard) => {
+                    #tokens
                 }
-            )
-        } else if self.is_map() {
-            quote!(
-                for (key, item) in #prefix #param.iter() {
-                    if let Err(mut errs) = item.validate() {
-                        errs.errors_mut().iter_mut().for_each(|err| err.set_location_idx(key, #field_name));
-                        errors.merge(errs);
-                    }
+                None => {
+                    #exceeded
                 }
-            )
-        } else {
-            tokens
-        }
+            }
+        )
+    }
+
+    /// Generates the tokens for a nested field by walking through any options, lists and maps
+    /// in its type down to the nested value. Hashed collections are iterated in a sorted order
+    /// if their elements or keys implement `Ord`.
+    ///
+    /// `leaf` receives the reference to the nested value and the segments of its location,
+    /// starting with the field name and followed by the index or escaped key of each level.
+    pub fn nested_tokens(
+        &self,
+        mutable: bool,
+        leaf: impl Fn(proc_macro2::TokenStream, &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let root = match (&self.ident_override, mutable) {
+            (Some(ident), true) => quote!(&mut *#ident),
+            (Some(ident), false) => quote!(#ident),
+            (None, true) => {
+                let ident = &self.field.ident;
+                quote!(&mut self.#ident)
+            }
+            (None, false) => {
+                let ident = &self.field.ident;
+                quote!(&self.#ident)
+            }
+        };
+
+        let field_name = self.name();
+        let mut segments = vec![quote!(#field_name)];
+        let tokens = nested_type_tokens(
+            &self.field.ty,
+            quote!(__v),
+            mutable,
+            0,
+            &mut segments,
+            &leaf,
+        );
+
+        quote!(
+            {
+                let __v = #root;
+                #tokens
+            }
+        )
     }
 
     pub fn wrap_modifier_if_option(
@@ -451,31 +624,27 @@ impl FieldInfo {
         tokens
     }
 
-    /// Wrap the quoted output of a modification in a for loop if
-    /// the field type is a collection.
+    /// Applies the modification of a single element, bound to `el`, to the field. Walks through any
+    /// options, smart pointers and lists in the field's type so the modification is applied to all
+    /// of the innermost values.
     pub fn wrap_modifier_if_collection(
         &self,
-        param: proc_macro2::TokenStream,
-        tokens: proc_macro2::TokenStream,
-        modifier: &Modifier,
+        modified: proc_macro2::TokenStream,
     ) -> proc_macro2::TokenStream {
-        if !self.is_list() {
-            return tokens;
-        }
-
-        let modified = match modifier {
-            Modifier::Trim => quote!(el.trim().to_string()),
-            Modifier::Uppercase => quote!(el.to_uppercase()),
-            Modifier::Lowercase => quote!(el.to_lowercase()),
-            Modifier::Capitalize => {
-                quote!(::std::format!("{}{}", &el[0..1].to_uppercase(), &el[1..]))
+        let root = match self.ident_override {
+            Some(ref ident) => quote!(&mut *#ident),
+            None => {
+                let ident = &self.field.ident;
+                quote!(&mut self.#ident)
             }
-            _ => unreachable!("modifier is never wrapped"),
         };
 
+        let tokens = modify_element_tokens(&self.field.ty, &modified);
+
         quote!(
-            for el in #param.iter_mut() {
-                *el = #modified
+            {
+                let el = #root;
+                #tokens
             }
         )
     }
@@ -512,6 +681,12 @@ impl FieldInfo {
             .is_some_and(|seg| seg.ident == "Option")
     }
 
+    /// Returns true if the field is an option or a smart pointer.
+    pub fn is_wrapped(&self) -> bool {
+        try_extract_option(&self.field.ty).is_some()
+            || try_extract_pointer(&self.field.ty).is_some()
+    }
+
     /// Returns true if the field is &'_ T, or Option<&'_ T>.
     pub fn is_reference(&self) -> bool {
         is_reference(&self.field.ty)
@@ -525,6 +700,13 @@ impl FieldInfo {
         is_map(&self.field.ty)
     }
 
+    /// Returns true if the field is validated as a nested value.
+    pub fn is_nested(&self) -> bool {
+        self.validations
+            .iter()
+            .any(|v| matches!(v, Validator::Nested | Validator::MaxDepth(_)))
+    }
+
     /// Returns true if the field is annotated with `#[validify]`
     pub fn is_nested_validify(&self) -> bool {
         self.field
@@ -564,10 +746,11 @@ fn is_reference(ty: &syn::Type) -> bool {
 }
 
 fn is_list(ty: &syn::Type) -> bool {
-    if let Some(ty) = try_extract_option(ty) {
-        return is_list(ty);
-    }
+    is_list_type(unwrap_type(ty))
+}
 
+/// Returns true if the type itself is a list, without unwrapping it.
+fn is_list_type(ty: &syn::Type) -> bool {
     // We consider arrays lists
     if let syn::Type::Array(_) = ty {
         return true;
@@ -584,6 +767,7 @@ fn is_list(ty: &syn::Type) -> bool {
     };
 
     seg.ident == "Vec"
+        || seg.ident == "VecDeque"
         || seg.ident == "HashSet"
         || seg.ident == "BTreeSet"
         || seg.ident == "IndexSet"
@@ -597,10 +781,11 @@ pub enum NameOrIndex {
 }
 
 fn is_map(ty: &syn::Type) -> bool {
-    if let Some(ty) = try_extract_option(ty) {
-        return is_map(ty);
-    }
+    is_map_type(unwrap_type(ty))
+}
 
+/// Returns true if the type itself is a map, without unwrapping it.
+fn is_map_type(ty: &syn::Type) -> bool {
     let syn::Type::Path(p) = ty else {
         return false;
     };
@@ -613,6 +798,330 @@ fn is_map(ty: &syn::Type) -> bool {
     seg.ident == "HashMap" || seg.ident == "BTreeMap" || seg.ident == "IndexMap"
 }
 
+/// Recursively generates the tokens of [FieldInfo::nested_tokens]. `param` is always a reference.
+fn nested_type_tokens(
+    ty: &syn::Type,
+    param: proc_macro2::TokenStream,
+    mutable: bool,
+    depth: usize,
+    segments: &mut Vec<proc_macro2::TokenStream>,
+    leaf: &impl Fn(proc_macro2::TokenStream, &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let value = format_ident!("__v{depth}");
+
+    if let syn::Type::Reference(reference) = ty {
+        return nested_type_tokens(&reference.elem, param, mutable, depth, segments, leaf);
+    }
+
+    if let Some((pointer, ty)) = try_extract_pointer(ty) {
+        let unwrapped = pointer_deref_tokens(pointer, ty, &param, mutable);
+        let tokens = nested_type_tokens(ty, quote!(#value), mutable, depth + 1, segments, leaf);
+        return quote!(
+            {
+                let #value = #unwrapped;
+                #tokens
+            }
+        );
+    }
+
+    if let Some(ty) = try_extract_option(ty) {
+        let tokens = nested_type_tokens(ty, quote!(#value), mutable, depth + 1, segments, leaf);
+        return quote!(
+            if let Some(#value) = #param {
+                #tokens
+            }
+        );
+    }
+
+    if let Some(element) = try_extract_list_element(ty) {
+        if mutable && is_set(ty) {
+            abort!(
+                ty.span(),
+                "Elements of sets cannot be modified, use `#[validate]` instead."
+            )
+        }
+
+        let index = format_ident!("__i{depth}");
+        let iter = match (mutable, is_hashed(ty)) {
+            (true, _) => quote!(#param.iter_mut()),
+            (false, true) => sorted_tokens(quote!(#param.iter())),
+            (false, false) => quote!(#param.iter()),
+        };
+
+        segments.push(quote!(#index));
+        let tokens =
+            nested_type_tokens(element, quote!(#value), mutable, depth + 1, segments, leaf);
+        segments.pop();
+
+        return quote!(
+            for (#index, #value) in #iter.enumerate() {
+                #tokens
+            }
+        );
+    }
+
+    if let Some(map_value) = try_extract_map_value(ty) {
+        let key = format_ident!("__k{depth}");
+        let escaped = format_ident!("__key{depth}");
+        let method = if mutable {
+            quote!(iter_mut)
+        } else {
+            quote!(iter)
+        };
+        let iter = if is_hashed(ty) {
+            sorted_tokens(quote!(#param.#method()))
+        } else {
+            quote!(#param.#method())
+        };
+
+        segments.push(quote!(&#escaped));
+        let tokens = nested_type_tokens(
+            map_value,
+            quote!(#value),
+            mutable,
+            depth + 1,
+            segments,
+            leaf,
+        );
+        segments.pop();
+
+        return quote!(
+            for (#key, #value) in #iter {
+                let #escaped = ::validify::escape_location(#key);
+                #tokens
+            }
+        );
+    }
+
+    leaf(param, segments)
+}
+
+/// Recursively unwraps options, references and smart pointers. `param` must be an ident bound to
+/// a reference to a value of type `ty` and is rebound to a reference to the unwrapped value.
+/// `leaf` receives the innermost type.
+pub fn unwrap_type_tokens(
+    ty: &syn::Type,
+    param: &proc_macro2::TokenStream,
+    mutable: bool,
+    leaf: &impl Fn(&syn::Type) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if let Some(ty) = try_extract_option(ty) {
+        let tokens = unwrap_type_tokens(ty, param, mutable, leaf);
+        return quote!(
+            if let Some(#param) = #param {
+                #tokens
+            }
+        );
+    }
+
+    if let syn::Type::Reference(reference) = ty {
+        if mutable {
+            abort!(
+                ty.span(),
+                "Fields containing modifiers must contain owned data"
+            )
+        }
+        let tokens = unwrap_type_tokens(&reference.elem, param, mutable, leaf);
+        return quote!(
+            {
+                let #param = *#param;
+                #tokens
+            }
+        );
+    }
+
+    if let Some((pointer, inner)) = try_extract_pointer(ty) {
+        let unwrapped = pointer_deref_tokens(pointer, ty, param, mutable);
+        let tokens = unwrap_type_tokens(inner, param, mutable, leaf);
+        return quote!(
+            {
+                let #param = #unwrapped;
+                #tokens
+            }
+        );
+    }
+
+    leaf(ty)
+}
+
+/// Dereferences the smart pointer bound to `param`. Only `Box` and `Cow` can be dereferenced mutably.
+fn pointer_deref_tokens(
+    pointer: &str,
+    ty: &syn::Type,
+    param: &proc_macro2::TokenStream,
+    mutable: bool,
+) -> proc_macro2::TokenStream {
+    match (pointer, mutable) {
+        (_, false) => quote!(&**#param),
+        ("Box", true) => quote!(&mut **#param),
+        ("Cow", true) => quote!(#param.to_mut()),
+        _ => abort!(
+            ty.span(),
+            "`Rc` and `Arc` can only be validated, use `Box` for fields containing modifiers"
+        ),
+    }
+}
+
+/// Generates the assignment of the modified element bound to `el`, walking through any options,
+/// smart pointers and lists of `ty`.
+pub fn modify_element_tokens(
+    ty: &syn::Type,
+    modified: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let el = quote!(el);
+    unwrap_type_tokens(ty, &el, true, &|ty| {
+        let Some(element) = try_extract_list_element(ty) else {
+            return quote!(*el = #modified;);
+        };
+
+        if is_set(ty) {
+            abort!(
+                ty.span(),
+                "Elements of sets cannot be modified, use `#[validate]` instead."
+            )
+        }
+
+        let tokens = modify_element_tokens(element, modified);
+        quote!(
+            for el in el.iter_mut() {
+                #tokens
+            }
+        )
+    })
+}
+
+/// Returns the modifiers of the rule sets used in the validators, applied to the same values
+/// as the rule set's validators, e.g. to each element of a list with `iter(rules(...))`.
+fn rule_set_modifiers(validators: &[Validator]) -> Vec<Modifier> {
+    validators
+        .iter()
+        .flat_map(|validator| {
+            let (validators, wrap): (_, fn(Box<Modifier>) -> Modifier) = match validator {
+                Validator::Rules(rules) => {
+                    return vec![Modifier::Rules {
+                        path: rules.path.clone(),
+                    }]
+                }
+                Validator::Iter(v) => (v, Modifier::Iter),
+                Validator::Keys(v) => (v, Modifier::Keys),
+                Validator::Values(v) => (v, Modifier::Values),
+                _ => return vec![],
+            };
+            rule_set_modifiers(validators)
+                .into_iter()
+                .map(|modifier| wrap(Box::new(modifier)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the type of the innermost value wrapped in options, references and smart pointers.
+pub fn unwrap_type(ty: &syn::Type) -> &syn::Type {
+    if let Some(ty) = try_extract_option(ty) {
+        return unwrap_type(ty);
+    }
+    if let syn::Type::Reference(reference) = ty {
+        return unwrap_type(&reference.elem);
+    }
+    if let Some((_, ty)) = try_extract_pointer(ty) {
+        return unwrap_type(ty);
+    }
+    ty
+}
+
+/// Returns the name and the pointee type of `Box`, `Rc`, `Arc` and `Cow` types.
+fn try_extract_pointer(ty: &syn::Type) -> Option<(&'static str, &syn::Type)> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+
+    let seg = p.path.segments.last()?;
+
+    let pointer = ["Box", "Rc", "Arc", "Cow"]
+        .into_iter()
+        .find(|pointer| seg.ident == pointer)?;
+
+    // Cow's first argument is a lifetime which is skipped
+    Some((pointer, generic_type_arg(ty, 0)?))
+}
+
+/// Collects the iterator into a `Vec` and iterates over it, sorted if the elements or keys in case of maps implement `Ord`.
+pub fn sorted_tokens(iter: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote!(
+        {
+            use ::validify::__sort::{SortedItems as _, UnsortedItems as _};
+            (&::validify::__sort::Items::new(#iter)).sorted().into_iter()
+        }
+    )
+}
+
+/// Returns true if the type is a `HashSet` or `HashMap`.
+pub fn is_hashed(ty: &syn::Type) -> bool {
+    let syn::Type::Path(p) = ty else {
+        return false;
+    };
+
+    p.path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "HashSet" || seg.ident == "HashMap")
+}
+
+pub fn is_set(ty: &syn::Type) -> bool {
+    let syn::Type::Path(p) = ty else {
+        return false;
+    };
+
+    p.path.segments.last().is_some_and(|seg| {
+        seg.ident == "HashSet" || seg.ident == "BTreeSet" || seg.ident == "IndexSet"
+    })
+}
+
+/// Returns the element type of a list, i.e. `T` in `Vec<T>` or `[T; N]`.
+pub fn try_extract_list_element(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Array(array) => Some(&array.elem),
+        syn::Type::Slice(slice) => Some(&slice.elem),
+        syn::Type::Path(_) if is_list_type(ty) => generic_type_arg(ty, 0),
+        _ => None,
+    }
+}
+
+/// Returns the key type of a map, i.e. `K` in `HashMap<K, V>`.
+pub fn try_extract_map_key(ty: &syn::Type) -> Option<&syn::Type> {
+    if !is_map_type(ty) {
+        return None;
+    }
+    generic_type_arg(ty, 0)
+}
+
+/// Returns the value type of a map, i.e. `V` in `HashMap<K, V>`.
+pub fn try_extract_map_value(ty: &syn::Type) -> Option<&syn::Type> {
+    if !is_map_type(ty) {
+        return None;
+    }
+    generic_type_arg(ty, 1)
+}
+
+/// Returns the n-th generic type argument of the last path segment.
+fn generic_type_arg(ty: &syn::Type, n: usize) -> Option<&syn::Type> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+
+    let syn::PathArguments::AngleBracketed(ref ab) = p.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    ab.args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .nth(n)
+}
+
 fn try_extract_option(ty: &syn::Type) -> Option<&syn::Type> {
     // Struct definitions always contain paths if they're options
     let syn::Type::Path(p) = ty else {
@@ -0,0 +1,300 @@
+//! Generates the descriptions of validators returned by `Validate::rules`.
+
+use crate::fields::{try_extract_list_element, try_extract_map_value, unwrap_type, FieldInfo};
+use crate::validate::parser::ValueOrPath;
+use crate::validate::validation::{Describe, IpFormat, TimeMultiplier, TimeOp, Validator};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+/// Generates the `rules` function of `Validate` for the fields. Each field is paired with the
+/// variant containing it in case of enums.
+pub fn rules_fn_tokens<'a>(
+    fields: impl IntoIterator<Item = (Option<String>, &'a FieldInfo)>,
+) -> TokenStream {
+    let fields = fields
+        .into_iter()
+        .map(|(variant, field)| field_rules_tokens(field, field.name(), variant));
+
+    quote!(
+        fn rules() -> &'static [::validify::FieldRules] {
+            &[#(#fields),*]
+        }
+    )
+}
+
+/// Generates a `FieldRules` describing the field.
+pub fn field_rules_tokens(field: &FieldInfo, name: String, variant: Option<String>) -> TokenStream {
+    let variant = option_tokens(variant);
+    let kind = type_kind_tokens(unwrap_type(&field.field.ty));
+    let optional = field.is_option();
+    let rules = rules_tokens(&field.validations);
+
+    let nested = if field.is_nested() {
+        let ty = nested_type(&field.field.ty);
+        quote!(Some(<#ty as ::validify::Validate>::rules))
+    } else {
+        quote!(None)
+    };
+
+    quote!(
+        ::validify::FieldRules {
+            name: #name,
+            variant: #variant,
+            kind: #kind,
+            optional: #optional,
+            rules: #rules,
+            nested: #nested,
+        }
+    )
+}
+
+/// Generates a slice of `Rule`s describing the validators.
+pub fn rules_tokens(validators: &[Validator]) -> TokenStream {
+    let rules = validators.iter().filter_map(rule_tokens);
+    quote!(&[#(#rules),*])
+}
+
+fn rule_tokens(validator: &Validator) -> Option<TokenStream> {
+    let no_rules = quote!(&[]);
+
+    let (name, describe, params, rules): (&str, &dyn Describe, Vec<_>, _) = match validator {
+        Validator::Iter(validators) => return Some(collection_rule_tokens("iter", validators)),
+        Validator::Keys(validators) => return Some(collection_rule_tokens("keys", validators)),
+        Validator::Values(validators) => return Some(collection_rule_tokens("values", validators)),
+        // Described by the nested fields
+        Validator::Nested => return None,
+        // Remote definitions have no `rules`, as they do not implement `Validate`
+        Validator::Remote(_) => return None,
+        // The default limit of nested fields is not declared
+        Validator::MaxDepth(None) => return None,
+        Validator::MaxDepth(Some(max)) => {
+            let max = *max as f64;
+            return Some(quote!(
+                ::validify::Rule {
+                    name: "max_depth",
+                    code: "max_depth",
+                    message: None,
+                    params: &[("max", ::validify::RuleParam::Number(#max))],
+                    rules: &[],
+                }
+            ));
+        }
+        Validator::Email(v) => ("email", v, vec![], no_rules),
+        Validator::Url(v) => ("url", v, vec![], no_rules),
+        Validator::CreditCard(v) => ("credit_card", v, vec![], no_rules),
+        Validator::Phone(v) => ("phone", v, vec![], no_rules),
+        Validator::NonControlCharacter(v) => ("non_control_char", v, vec![], no_rules),
+        Validator::NoHtml(v) => ("no_html", v, vec![], no_rules),
+        Validator::Required(v) => ("required", v, vec![], no_rules),
+        Validator::Custom(v) => ("custom", v, vec![("function", expr(&v.path))], no_rules),
+        Validator::With(v) => ("with", v, vec![("validator", expr(&v.validator))], no_rules),
+        Validator::Regex(v) => ("regex", v, vec![("path", expr(&v.path))], no_rules),
+        Validator::Rules(v) => {
+            let path = &v.path;
+            (
+                "rules",
+                v,
+                vec![("rule_set", expr(path))],
+                quote!(#path::RULES),
+            )
+        }
+        Validator::Length(v) => {
+            let params = [("min", &v.min), ("max", &v.max), ("equal", &v.equal)]
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, number_or_expr(value.as_ref()?))))
+                .collect();
+            ("length", v, params, no_rules)
+        }
+        Validator::Range(v) => {
+            let params = [("min", &v.min), ("max", &v.max)]
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, number_or_expr(value.as_ref()?))))
+                .collect();
+            ("range", v, params, no_rules)
+        }
+        Validator::Contains(v) => {
+            let name = if v.not { "contains_not" } else { "contains" };
+            let params = match v.value {
+                Some(ValueOrPath::Value(ref lit)) => vec![("value", lit_tokens(lit))],
+                Some(ValueOrPath::Path(ref path)) => vec![("value", expr(path))],
+                None => vec![],
+            };
+            (name, v, params, no_rules)
+        }
+        Validator::In(v) => {
+            let name = if v.not { "not_in" } else { "is_in" };
+            let params = v.expr.iter().map(|e| ("collection", expr(e))).collect();
+            (name, v, params, no_rules)
+        }
+        Validator::Ip(v) => {
+            let params = match v.format {
+                Some(IpFormat::V4) => vec![("format", str_tokens("v4"))],
+                Some(IpFormat::V6) => vec![("format", str_tokens("v6"))],
+                None => vec![],
+            };
+            ("ip", v, params, no_rules)
+        }
+        Validator::Time(v) => {
+            let op = match v.op {
+                TimeOp::BeforeNow => "before_now",
+                TimeOp::AfterNow => "after_now",
+                TimeOp::Before => "before",
+                TimeOp::After => "after",
+                TimeOp::BeforeFromNow => "before_from_now",
+                TimeOp::AfterFromNow => "after_from_now",
+                TimeOp::InPeriod => "in_period",
+                TimeOp::None => unreachable!(),
+            };
+
+            let mut params = vec![("op", str_tokens(op))];
+
+            match v.target {
+                Some(ValueOrPath::Value(ref target)) => params.push(("target", str_tokens(target))),
+                Some(ValueOrPath::Path(ref path)) => params.push(("target", expr(path))),
+                None => {}
+            }
+
+            if let Some(ref format) = v.format {
+                params.push(("format", str_tokens(format)));
+            }
+
+            // Literal durations are parsed to seconds
+            match v.duration {
+                Some(ValueOrPath::Value(seconds)) => {
+                    let seconds = seconds as f64;
+                    params.push(("seconds", quote!(::validify::RuleParam::Number(#seconds))));
+                }
+                Some(ValueOrPath::Path(ref path)) => {
+                    let unit = match v.multiplier {
+                        TimeMultiplier::Seconds => "seconds",
+                        TimeMultiplier::Minutes => "minutes",
+                        TimeMultiplier::Hours => "hours",
+                        TimeMultiplier::Days => "days",
+                        TimeMultiplier::Weeks => "weeks",
+                        TimeMultiplier::None => unreachable!(),
+                    };
+                    params.push((unit, expr(path)));
+                }
+                None => {}
+            }
+
+            let inclusive = v.inclusive;
+            let has_time = v.has_time;
+            params.push(("inclusive", quote!(::validify::RuleParam::Bool(#inclusive))));
+            params.push(("time", quote!(::validify::RuleParam::Bool(#has_time))));
+
+            ("time", v, params, no_rules)
+        }
+    };
+
+    let code = describe.code();
+    let message = option_tokens(describe.message());
+    let params = params
+        .into_iter()
+        .map(|(name, value)| quote!((#name, #value)));
+
+    Some(quote!(
+        ::validify::Rule {
+            name: #name,
+            code: #code,
+            message: #message,
+            params: &[#(#params),*],
+            rules: #rules,
+        }
+    ))
+}
+
+fn collection_rule_tokens(name: &str, validators: &[Validator]) -> TokenStream {
+    let rules = rules_tokens(validators);
+    quote!(
+        ::validify::Rule {
+            name: #name,
+            code: #name,
+            message: None,
+            params: &[],
+            rules: #rules,
+        }
+    )
+}
+
+/// Returns the type whose rules describe a nested field, i.e. the innermost element or value of
+/// any options, smart pointers, lists and maps.
+fn nested_type(ty: &syn::Type) -> &syn::Type {
+    let ty = unwrap_type(ty);
+    if let Some(element) = try_extract_list_element(ty) {
+        return nested_type(element);
+    }
+    if let Some(value) = try_extract_map_value(ty) {
+        return nested_type(value);
+    }
+    ty
+}
+
+fn type_kind_tokens(ty: &syn::Type) -> TokenStream {
+    let kind = match ty {
+        syn::Type::Array(_) | syn::Type::Slice(_) => quote!(List),
+        syn::Type::Path(p) => match p.path.segments.last() {
+            Some(seg) => match seg.ident.to_string().as_str() {
+                "String" | "str" => quote!(String),
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" => quote!(Integer),
+                "f32" | "f64" => quote!(Float),
+                "bool" => quote!(Bool),
+                "char" => quote!(Char),
+                "NaiveDate" => quote!(Date),
+                "NaiveDateTime" | "DateTime" => quote!(DateTime),
+                _ if try_extract_list_element(ty).is_some() => quote!(List),
+                _ if try_extract_map_value(ty).is_some() => quote!(Map),
+                _ => quote!(Other),
+            },
+            None => quote!(Other),
+        },
+        _ => quote!(Other),
+    };
+    quote!(::validify::TypeKind::#kind)
+}
+
+fn option_tokens(value: Option<impl ToTokens>) -> TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+fn expr(expr: &impl ToTokens) -> TokenStream {
+    let expr = expr.to_token_stream().to_string();
+    quote!(::validify::RuleParam::Expr(#expr))
+}
+
+fn str_tokens(value: &str) -> TokenStream {
+    quote!(::validify::RuleParam::Str(#value))
+}
+
+fn number_or_expr<T: ToString>(value: &ValueOrPath<T>) -> TokenStream {
+    match value {
+        ValueOrPath::Value(value) => match value.to_string().parse::<f64>() {
+            Ok(value) => quote!(::validify::RuleParam::Number(#value)),
+            Err(_) => expr(&value.to_string()),
+        },
+        ValueOrPath::Path(path) => expr(path),
+    }
+}
+
+fn lit_tokens(lit: &syn::Lit) -> TokenStream {
+    let number = match lit {
+        syn::Lit::Str(s) => return str_tokens(&s.value()),
+        syn::Lit::Char(c) => return str_tokens(&c.value().to_string()),
+        syn::Lit::Bool(b) => {
+            let value = b.value;
+            return quote!(::validify::RuleParam::Bool(#value));
+        }
+        syn::Lit::Int(int) => int.base10_parse::<f64>(),
+        syn::Lit::Float(float) => float.base10_parse::<f64>(),
+        _ => return expr(lit),
+    };
+
+    match number {
+        Ok(number) => quote!(::validify::RuleParam::Number(#number)),
+        Err(_) => expr(lit),
+    }
+}
@@ -2,8 +2,12 @@ use proc_macro_error::proc_macro_error;
 use quote::{quote, ToTokens};
 use syn::{parse::Parse, ItemFn, LitStr, Token};
 
+mod bound;
 mod fields;
+mod introspection;
+mod newtype;
 mod payload;
+mod rule_set;
 mod serde;
 mod tokens;
 mod validate;
@@ -148,6 +152,71 @@ pub fn derive_validate(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     validate::r#impl::impl_validate(&input).into()
 }
 
+/// Implements a newtype which cannot be constructed from an invalid value.
+///
+/// Can only be used on tuple structs with a single field. The `#[modify]` and `#[validate]` annotations
+/// of the field run when constructing the newtype with any of the generated impls:
+///
+/// - `TryFrom<Inner>` returning `ValidationErrors`,
+/// - `FromStr`, parsing the inner value first,
+/// - `serde::Deserialize`, failing with the validation errors,
+///
+/// along with `AsRef<Inner>`, `Deref<Target = Inner>`, `Display` and `Validate`.
+///
+/// `FromStr` and `Display` require the inner type to implement them and can be skipped for types which do not,
+/// i.e. `Vec<String>`, with `#[newtype(skip(from_str, display))]` on the struct.
+///
+/// The errors are located at the newtype, so they get located at the field of the struct
+/// containing it when validated as nested.
+///
+/// ```ignore
+/// #[derive(Debug, validify::ValidatedNewtype)]
+/// struct Email(
+///     #[modify(trim, lowercase)]
+///     #[validate(email)]
+///     String,
+/// );
+///
+/// let email: Email = "  Foo@Bar.com ".parse().unwrap();
+/// assert_eq!(&*email, "foo@bar.com");
+/// assert!(Email::try_from(String::from("foo")).is_err());
+/// ```
+#[proc_macro_derive(ValidatedNewtype, attributes(modify, validate, newtype))]
+#[proc_macro_error]
+pub fn derive_validated_newtype(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse(input).unwrap();
+    newtype::impl_validated_newtype(&input).into()
+}
+
+/// Defines a named set of validators, and optionally modifiers, applied to fields with
+/// `#[validate(rules(Name))]`.
+///
+/// The rule set applies to values of the type given after its name, `String` by default.
+/// Any paths used in the rules are resolved where the rule set is defined.
+///
+/// ```ignore
+/// validify::rule_set!(
+///     pub Username = [length(min = 3, max = 32), regex(USERNAME_RE), non_control_char],
+///     modify = [trim, lowercase]
+/// );
+///
+/// validify::rule_set!(pub Percentage: f64 = [range(min = 0., max = 100.)]);
+///
+/// #[derive(validify::Validify)]
+/// struct Signup {
+///     #[validate(rules(Username, code = "username", message = "Invalid username"))]
+///     username: String,
+/// }
+/// ```
+///
+/// Expands to a unit struct with `validate` and `modify` functions taking the value.
+#[proc_macro]
+#[proc_macro_error]
+pub fn rule_set(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let rule_set = syn::parse_macro_input!(input as rule_set::RuleSet);
+    rule_set::impl_rule_set(rule_set).into()
+}
+
 /// A shortcut for ergonomic error creation in custom schema validaton functions.
 ///
 /// Prepends a `let mut errors = ValidationErrors::new()` to the beginning of the function block,
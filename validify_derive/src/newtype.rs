@@ -0,0 +1,204 @@
+use crate::fields::Fields;
+use crate::introspection::field_rules_tokens;
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+const NEWTYPE: &str = "newtype";
+const SKIP: &str = "skip";
+const FROM_STR: &str = "from_str";
+const DISPLAY: &str = "display";
+
+/// Entrypoint for `#[derive(ValidatedNewtype)]`.
+pub fn impl_validated_newtype(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+
+    let syn::Data::Struct(syn::DataStruct {
+        fields: ref struct_fields @ syn::Fields::Unnamed(ref unnamed),
+        ..
+    }) = input.data
+    else {
+        abort!(
+            input.span(),
+            "#[derive(ValidatedNewtype)] can only be used on tuple structs with a single field"
+        )
+    };
+
+    if unnamed.unnamed.len() != 1 {
+        abort!(
+            unnamed.span(),
+            "#[derive(ValidatedNewtype)] can only be used on tuple structs with a single field"
+        )
+    }
+
+    let inner = &unnamed.unnamed[0].ty;
+    let skipped = find_skipped_impls(&input.attrs);
+
+    // The inner value is processed before it is wrapped, the same way enum variant fields are
+    let value = format_ident!("value");
+    let mut fields = Fields::collect(&input.attrs, struct_fields);
+    let field = &mut fields.0[0];
+    field.ident_override = Some(value.clone());
+    let field_name = field.name();
+
+    let modifiers = fields.to_modify_tokens();
+    let after_validate_modifiers = fields.to_after_validate_tokens();
+    let validations = fields.to_validate_tokens();
+    // The value is unnamed, as are its errors
+    let rules = field_rules_tokens(&fields.0[0], String::new(), None);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let from_str_impl = (!skipped.contains(&FROM_STR)).then(|| {
+        quote!(
+            impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = ::validify::ValidationErrors;
+
+                fn from_str(s: &str) -> Result<Self, ::validify::ValidationErrors> {
+                    match <#inner as ::std::str::FromStr>::from_str(s) {
+                        Ok(#value) => <Self as ::std::convert::TryFrom<#inner>>::try_from(#value),
+                        // Located at the value like any other error of the newtype
+                        Err(e) => {
+                            let mut errors = ::validify::ValidationErrors::new();
+                            let mut err = ::validify::ValidationError::new_field_named(#field_name, "parse")
+                                .with_message(e.to_string())
+                                .with_param("actual", &s);
+                            err.set_location(#field_name);
+                            errors.add(err);
+                            ::validify::__root::into_root(&mut errors, #field_name);
+                            Err(errors)
+                        }
+                    }
+                }
+            }
+        )
+    });
+
+    let display_impl = (!skipped.contains(&DISPLAY)).then(|| {
+        quote!(
+            impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        )
+    });
+
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics ::std::convert::TryFrom<#inner> for #ident #ty_generics #where_clause {
+            type Error = ::validify::ValidationErrors;
+
+            fn try_from(mut #value: #inner) -> Result<Self, ::validify::ValidationErrors> {
+                let mut errors = ::validify::ValidationErrors::new();
+
+                {
+                    let #value = &mut #value;
+                    #(#modifiers)*
+                }
+
+                // The value is not validated if its modifiers failed
+                if errors.is_empty() {
+                    let #value = &#value;
+                    #(#validations)*
+                }
+
+                if errors.is_empty() {
+                    let #value = &mut #value;
+                    #(#after_validate_modifiers)*
+                }
+
+                if errors.is_empty() {
+                    Ok(Self(#value))
+                } else {
+                    ::validify::__root::into_root(&mut errors, #field_name);
+                    Err(errors)
+                }
+            }
+        }
+
+        impl #impl_generics ::validify::Validate for #ident #ty_generics #where_clause {
+            fn validate(&self) -> Result<(), ::validify::ValidationErrors> {
+                let mut errors = ::validify::ValidationErrors::new();
+
+                let #value = &self.0;
+                #(#validations)*
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    ::validify::__root::into_root(&mut errors, #field_name);
+                    Err(errors)
+                }
+            }
+
+            fn rules() -> &'static [::validify::FieldRules] {
+                const { &[#rules] }
+            }
+        }
+
+        #from_str_impl
+
+        impl #de_impl_generics serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let #value = <#inner as serde::Deserialize<'de>>::deserialize(deserializer)?;
+                <Self as ::std::convert::TryFrom<#inner>>::try_from(#value)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl #impl_generics ::std::convert::AsRef<#inner> for #ident #ty_generics #where_clause {
+            fn as_ref(&self) -> &#inner {
+                &self.0
+            }
+        }
+
+        impl #impl_generics ::std::ops::Deref for #ident #ty_generics #where_clause {
+            type Target = #inner;
+
+            fn deref(&self) -> &#inner {
+                &self.0
+            }
+        }
+
+        #display_impl
+    )
+}
+
+/// Parses the impls skipped with `#[newtype(skip(from_str, display))]`.
+fn find_skipped_impls(attrs: &[syn::Attribute]) -> Vec<&'static str> {
+    let mut skipped = vec![];
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(NEWTYPE)) {
+        let res = attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(SKIP) {
+                return Err(meta.error("Unrecognized newtype parameter, accepted are: skip"));
+            }
+
+            meta.parse_nested_meta(|meta| {
+                let Some(name) = [FROM_STR, DISPLAY]
+                    .into_iter()
+                    .find(|name| meta.path.is_ident(name))
+                else {
+                    return Err(
+                        meta.error("Unrecognized skipped impl, accepted are: from_str, display")
+                    );
+                };
+                skipped.push(name);
+                Ok(())
+            })
+        });
+
+        if let Err(e) = res {
+            abort!(e.span(), e)
+        }
+    }
+
+    skipped
+}
@@ -1,4 +1,5 @@
 use crate::{
+    bound::{bounded_types, is_type_param, with_predicates, BoundTarget},
     fields::{FieldInfo, Fields},
     serde::{extract_custom_serde, quote_custom_serde_payload_field},
 };
@@ -13,6 +14,107 @@ pub(super) fn payload_ident(original: &syn::Ident) -> syn::Ident {
     format_ident!("{}Payload", original)
 }
 
+/// Returns the generics of the conversions between the original and the payload, bounding the
+/// type parameters used in nested fields, along with the additional `bound`.
+pub(super) fn conversion_generics(
+    input: &syn::DeriveInput,
+    bound: Option<proc_macro2::TokenStream>,
+) -> syn::Generics {
+    let predicates = nested_bounded_types(input)
+        .iter()
+        .flat_map(|ty| {
+            let payload = quote!(<#ty as ::validify::ValidifyPayload>::Payload);
+            let mut predicates: Vec<syn::WherePredicate> = vec![
+                syn::parse_quote!(#ty: ::validify::ValidifyPayload + From<#payload>),
+                syn::parse_quote!(#payload: From<#ty>),
+            ];
+            if let Some(ref bound) = bound {
+                predicates.push(syn::parse_quote!(#ty: #bound));
+            }
+            predicates
+        })
+        .collect();
+
+    with_predicates(
+        &input.generics,
+        &input.attrs,
+        BoundTarget::Payload,
+        predicates,
+    )
+}
+
+/// Returns the type parameters, or their associated types, used in `#[validify]` fields.
+fn nested_bounded_types(input: &syn::DeriveInput) -> Vec<syn::Type> {
+    let syn::Data::Struct(ref strct) = input.data else {
+        return vec![];
+    };
+
+    let nested = strct.fields.iter().filter(|field| is_nested_field(field));
+
+    bounded_types(&input.generics, nested.map(|field| &field.ty))
+}
+
+fn is_nested_field(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("validify") && attr.meta.require_path_only().is_ok())
+}
+
+/// Serde does not infer bounds for associated types nested in other types, so the payloads
+/// of nested type parameters get bounded explicitly. Skipped if serde bounds are specified.
+fn serde_bound(
+    input: &syn::DeriveInput,
+    attributes: &[syn::Attribute],
+) -> Option<proc_macro2::TokenStream> {
+    let nested = nested_bounded_types(input);
+
+    if nested.is_empty() || has_serde_bound(attributes) {
+        return None;
+    }
+
+    let syn::Data::Struct(ref strct) = input.data else {
+        return None;
+    };
+
+    let other = bounded_types(
+        &input.generics,
+        strct
+            .fields
+            .iter()
+            .filter(|field| !is_nested_field(field))
+            .map(|field| &field.ty),
+    );
+
+    let predicates = nested
+        .iter()
+        .map(|ty| quote!(<#ty as ::validify::ValidifyPayload>::Payload))
+        .chain(other.iter().map(|ty| quote!(#ty)))
+        .map(|ty| quote!(#ty: serde::Deserialize<'de>).to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(quote!(#[serde(bound(deserialize = #predicates))]))
+}
+
+/// Returns true if any of the serde attributes contains a `bound` argument.
+fn has_serde_bound(attributes: &[syn::Attribute]) -> bool {
+    attributes.iter().any(|attr| {
+        let mut found = false;
+        // Errors are left to serde
+        let _ = attr.parse_nested_meta(|meta| {
+            found |= meta.path.is_ident("bound");
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<proc_macro2::Group>()?;
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
 pub(super) fn generate_struct(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
     let ident = &input.ident;
 
@@ -41,14 +143,30 @@ pub(super) fn generate_struct(input: &syn::DeriveInput) -> proc_macro2::TokenStr
     let mut custom_serdes = vec![];
 
     for field in fields.0.iter() {
-        let (payload_tokens, custom_serde) = map_payload_fields(field);
+        let (payload_tokens, custom_serde) = map_payload_fields(field, &input.generics);
         payload_fields.push(payload_tokens);
         if let Some(custom_de) = custom_serde {
             custom_serdes.push(custom_de);
         }
     }
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // The payload refers to the payloads of nested type parameters
+    let payload_predicates = nested_bounded_types(input)
+        .iter()
+        .map(|ty| syn::parse_quote!(#ty: ::validify::ValidifyPayload))
+        .collect();
+    let payload_generics = with_predicates(
+        &input.generics,
+        &input.attrs,
+        BoundTarget::Payload,
+        payload_predicates,
+    );
+    let (payload_impl_generics, _, payload_where_clause) = payload_generics.split_for_impl();
+
+    let serde_bound = serde_bound(input, &attributes);
+
+    let conversion_generics = conversion_generics(input, None);
+    let (impl_generics, ty_generics, where_clause) = conversion_generics.split_for_impl();
 
     let into_fields = fields
         .0
@@ -65,20 +183,21 @@ pub(super) fn generate_struct(input: &syn::DeriveInput) -> proc_macro2::TokenStr
     let quoted = quote!(
         #[derive(Debug, ::validify::Validate, serde::Deserialize)]
         #(#attributes)*
-        #visibility struct #payload_ident #ty_generics #where_clause {
+        #serde_bound
+        #visibility struct #payload_ident #payload_impl_generics #payload_where_clause {
             #(#payload_fields)*
         }
 
-        impl #impl_generics From<#ident> for #payload_ident {
-            fn from(original: #ident) -> Self {
+        impl #impl_generics From<#ident #ty_generics> for #payload_ident #ty_generics #where_clause {
+            fn from(original: #ident #ty_generics) -> Self {
                 Self {
                     #(#into_fields)*
                 }
             }
         }
 
-        impl #impl_generics From<#payload_ident> for #ident {
-            fn from(payload: #payload_ident) -> Self {
+        impl #impl_generics From<#payload_ident #ty_generics> for #ident #ty_generics #where_clause {
+            fn from(payload: #payload_ident #ty_generics) -> Self {
                 Self {
                     #(#from_fields)*
                 }
@@ -93,6 +212,7 @@ pub(super) fn generate_struct(input: &syn::DeriveInput) -> proc_macro2::TokenStr
 
 fn map_payload_fields(
     info: &FieldInfo,
+    generics: &syn::Generics,
 ) -> (proc_macro2::TokenStream, Option<proc_macro2::TokenStream>) {
     let ident = info.field.ident.as_ref().unwrap();
 
@@ -145,10 +265,9 @@ fn map_payload_fields(
         };
 
         if is_list {
-            payload_path_angle_bracketed(&mut path);
+            payload_path_angle_bracketed(&mut path, generics);
         } else {
-            let ty_ident = &path.path.segments.last().unwrap().ident;
-            path.path.segments.last_mut().unwrap().ident = format_ident!("{ty_ident}Payload");
+            map_payload_path(&mut path, generics);
         }
 
         let payload_type = syn::Type::Path(path);
@@ -198,10 +317,9 @@ fn map_payload_fields(
     };
 
     if is_list {
-        payload_path_angle_bracketed(inner_path);
+        payload_path_angle_bracketed(inner_path, generics);
     } else {
-        let type_ident = &inner_path.path.segments.last().unwrap().ident;
-        inner_path.path.segments.last_mut().unwrap().ident = format_ident!("{type_ident}Payload");
+        map_payload_path(inner_path, generics);
     }
 
     let payload_type = syn::Type::Path(path);
@@ -276,7 +394,7 @@ fn map_into_fields(info: &FieldInfo) -> proc_macro2::TokenStream {
     }
 }
 
-fn payload_path_angle_bracketed(path: &mut syn::TypePath) {
+fn payload_path_angle_bracketed(path: &mut syn::TypePath, generics: &syn::Generics) {
     // Type is contained in a List<T>. It will always have angle args and will
     // always be the last segment of the path
     let syn::PathArguments::AngleBracketed(ref mut args) =
@@ -290,10 +408,22 @@ fn payload_path_angle_bracketed(path: &mut syn::TypePath) {
         abort!(path.span(), "Cannot apply payload type to field")
     };
 
-    let Some(segment) = p.path.segments.last_mut() else {
+    if p.path.segments.is_empty() {
         abort!(p.span(), "Invalid path provided for validify")
-    };
+    }
+
+    map_payload_path(p, generics);
+}
+
+/// Maps the path of a nested type to its payload: `Nested` => `NestedPayload`, or
+/// `T` => `T::Payload` if the type is a type parameter.
+fn map_payload_path(path: &mut syn::TypePath, generics: &syn::Generics) {
+    if is_type_param(generics, path) {
+        path.path.segments.push(format_ident!("Payload").into());
+        return;
+    }
 
+    let segment = path.path.segments.last_mut().unwrap();
     segment.ident = format_ident!("{}Payload", segment.ident);
 }
 
@@ -6,23 +6,24 @@ pub fn impl_payload(input: &DeriveInput) -> proc_macro2::TokenStream {
     let strct = super::generate_struct(input);
     let payload_id = super::payload_ident(ident);
 
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = super::conversion_generics(input, Some(quote!(::validify::Validify)));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote!(
         #strct
 
         impl #impl_generics ::validify::ValidifyPayload for #ident #ty_generics #where_clause {
-            type Payload = #payload_id;
+            type Payload = #payload_id #ty_generics;
 
             fn validate_from(payload: Self::Payload) -> Result<Self, ::validify::ValidationErrors>
             {
                 <Self::Payload as ::validify::Validate>::validate(&payload)?;
 
-                let mut this = #ident ::from(payload);
+                let mut this = Self::from(payload);
 
                 let mut errors = ::validify::ValidationErrors::new();
 
-                if let Err(errs) = <#ident as ::validify::Validate>::validate(&this) {
+                if let Err(errs) = <Self as ::validify::Validate>::validate(&this) {
                     errors.merge(errs);
                 }
 
@@ -37,11 +38,11 @@ pub fn impl_payload(input: &DeriveInput) -> proc_macro2::TokenStream {
             {
                 <Self::Payload as ::validify::Validate>::validate(&payload)?;
 
-                let mut this = #ident ::from(payload);
+                let mut this = Self::from(payload);
 
                 let mut errors = ::validify::ValidationErrors::new();
 
-                if let Err(errs) = <#ident as ::validify::Validify>::validify(&mut this) {
+                if let Err(errs) = <Self as ::validify::Validify>::validify(&mut this) {
                     errors.merge(errs);
                 }
 
@@ -0,0 +1,142 @@
+use crate::fields::Fields;
+use crate::introspection::rules_tokens;
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::{bracketed, parse::Parse, Token};
+
+const MODIFY: &str = "modify";
+
+/// `[vis] Name[: Type] = [validators][, modify = [modifiers]]`
+pub struct RuleSet {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    ty: syn::Type,
+    validators: proc_macro2::TokenStream,
+    modifiers: proc_macro2::TokenStream,
+}
+
+impl Parse for RuleSet {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+
+        let ty = if input.parse::<Option<Token![:]>>()?.is_some() {
+            input.parse()?
+        } else {
+            syn::parse_quote!(::std::string::String)
+        };
+
+        input.parse::<Token![=]>()?;
+        let content;
+        bracketed!(content in input);
+        let validators = content.parse()?;
+
+        let mut modifiers = proc_macro2::TokenStream::new();
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != MODIFY {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "Unrecognized rule set parameter, accepted is: modify",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let content;
+            bracketed!(content in input);
+            modifiers = content.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            ty,
+            validators,
+            modifiers,
+        })
+    }
+}
+
+/// Entrypoint for `rule_set!`.
+///
+/// The rules are parsed as the annotations of a field of the rule set's type, so they
+/// support everything fields do.
+pub fn impl_rule_set(rule_set: RuleSet) -> proc_macro2::TokenStream {
+    let RuleSet {
+        attrs,
+        vis,
+        ident,
+        ty,
+        validators,
+        modifiers,
+    } = rule_set;
+
+    let value = format_ident!("value");
+    let field_name = value.to_string();
+
+    let fields: syn::FieldsNamed = syn::parse_quote!({
+        #[validate(#validators)]
+        #[modify(#modifiers)]
+        #value: #ty
+    });
+    let mut fields = Fields::collect(&[], &syn::Fields::Named(fields));
+
+    // Rule sets are applied before validation, so there is no phase to run these in
+    if !fields.0[0].after_validate_modifiers.is_empty() {
+        abort!(
+            modifiers,
+            "`after_validate` modifiers cannot be used in rule sets, apply them to the field instead"
+        )
+    }
+    fields.0[0].ident_override = Some(value.clone());
+
+    let validations = fields.to_validate_tokens();
+    let modifications = fields.to_modify_tokens();
+    let rules = rules_tokens(&fields.0[0].validations);
+
+    quote!(
+        #(#attrs)*
+        #[derive(Debug, Clone, Copy)]
+        #vis struct #ident;
+
+        impl #ident {
+            /// The descriptions of the validators of the rule set.
+            pub const RULES: &'static [::validify::Rule] = #rules;
+
+            /// Runs the validators of the rule set. The errors are located at the value.
+            #[allow(unused_mut, unused_variables, clippy::ptr_arg)]
+            pub fn validate(#value: &#ty) -> Result<(), ::validify::ValidationErrors> {
+                let mut errors = ::validify::ValidationErrors::new();
+
+                #(#validations)*
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    ::validify::__root::into_root(&mut errors, #field_name);
+                    Err(errors)
+                }
+            }
+
+            /// Runs the modifiers of the rule set. The errors of fallible modifiers are
+            /// located at the value.
+            #[allow(unused_mut, unused_variables, clippy::ptr_arg)]
+            pub fn modify(#value: &mut #ty) -> Result<(), ::validify::ValidationErrors> {
+                let mut errors = ::validify::ValidationErrors::new();
+
+                #(#modifications)*
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    ::validify::__root::into_root(&mut errors, #field_name);
+                    Err(errors)
+                }
+            }
+        }
+    )
+}
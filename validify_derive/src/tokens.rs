@@ -1,7 +1,12 @@
+use crate::fields::{
+    is_hashed, sorted_tokens, try_extract_list_element, try_extract_map_value, unwrap_type,
+    unwrap_type_tokens,
+};
 use crate::validate::parser::ValueOrPath;
 use crate::validate::validation::{
-    Contains, CreditCard, Custom, Describe, Email, In, Ip, Length, NonControlChar, Phone, Range,
-    Regex, Required, SchemaValidation, Time, TimeMultiplier, Url, Validator,
+    Contains, CreditCard, Custom, Describe, Email, In, Ip, IpFormat, Length, NoHtml,
+    NonControlChar, Phone, Range, Regex, Required, Rules, SchemaValidation, Time, TimeMultiplier,
+    TimeOp, Url, Validator, With,
 };
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
@@ -10,23 +15,33 @@ use syn::spanned::Spanned;
 
 /// Utility for generating error messages
 pub trait ValidationErrorTokens {
-    /// Quote an error based on the validation's settings
-    fn quote_error(&self, field_name: &str) -> proc_macro2::TokenStream
+    /// The code specified in the annotation, if any
+    fn custom_code(&self) -> Option<&str>;
+
+    /// Quote the handling of the error returned from a `validify::rules` check based on the
+    /// validation's settings. The rule provides the default code and the params.
+    fn quote_check(&self, check: TokenStream, field_name: &str, in_iter: bool) -> TokenStream
     where
         Self: Describe,
     {
-        let message = if let Some(ref m) = self.message() {
-            quote!(err.set_message(String::from(#m));)
+        let code = self.custom_code().map(|code| quote!(err.set_code(#code);));
+        let message = self
+            .message()
+            .map(|m| quote!(err.set_message(String::from(#m));));
+        let error_location = if in_iter {
+            quote!(err.set_location_idx(__i, #field_name);)
         } else {
-            quote!()
+            quote!(err.set_location(#field_name);)
         };
 
-        let code = self.code();
-
         quote!(
-            let mut err = ::validify::ValidationError::new_field(#code);
-            err.set_field(#field_name);
-            #message
+            if let Err(mut err) = #check {
+                #code
+                #message
+                err.set_field(#field_name);
+                #error_location
+                errors.add(err);
+            }
         )
     }
 }
@@ -34,7 +49,11 @@ pub trait ValidationErrorTokens {
 macro_rules! impl_error {
     ($($id:ident),*) => (
         $(
-            impl ValidationErrorTokens for $id {}
+            impl ValidationErrorTokens for $id {
+                fn custom_code(&self) -> Option<&str> {
+                    self.code.as_deref()
+                }
+            }
         )*
     )
 }
@@ -48,6 +67,7 @@ impl_error! {
     Phone,
     Custom,
     NonControlChar,
+    NoHtml,
     Required,
     Regex,
     Contains,
@@ -110,6 +130,14 @@ impl Validator {
                 let tokens = v.to_validify_tokens(field_name, validator_param, false);
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
             }
+            Validator::With(v) => {
+                let tokens = v.to_validify_tokens(field_name, validator_param, false);
+                ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
+            }
+            Validator::Rules(v) => {
+                let tokens = v.to_validify_tokens(field_name, validator_param, false);
+                ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
+            }
             Validator::Range(v) => {
                 let tokens = v.to_validify_tokens(field_name, validator_param, false);
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
@@ -122,6 +150,10 @@ impl Validator {
                 let tokens = v.to_validify_tokens(field_name, validator_param, false);
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
             }
+            Validator::NoHtml(v) => {
+                let tokens = v.to_validify_tokens(field_name, validator_param, false);
+                ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
+            }
             Validator::Regex(v) => {
                 let tokens = v.to_validify_tokens(field_name, validator_param, false);
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
@@ -165,105 +197,264 @@ impl Validator {
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
             }
             Validator::Iter(v) => {
-                let validator_param = quote!(el);
-                let inner_tokens = v.iter().map(|v| match v {
-                    Validator::Iter(_) => {
-                        abort!(field_info.field.span(), "`iter` validator cannot be nested.")
-                    }
-                    Validator::Nested => {
-                        abort!(field_info.field.span(), "`validate/validify` is not valid in `iter`. To validate collections of type T, use `validate/validify` directly on the field.")
-                    },
-                    Validator::Email(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Url(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::CreditCard(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Phone(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Custom(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Range(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Length(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Ip(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::NonControlCharacter(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Required(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    }
-                    Validator::Regex(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                let element = try_extract_list_element(unwrap_type(&field_info.field.ty));
+                let inner_tokens = Self::element_tokens(v, field_info, &field_name, element);
+                let iter = field_info.collection_iter_tokens(quote!(iter));
+                let tokens = quote!(
+                    for (__i, el) in #iter.enumerate() {
+                        #inner_tokens
                     }
-                    Validator::Contains(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                );
+                ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
+            }
+            Validator::Keys(v) => {
+                if !field_info.is_map() {
+                    abort!(field_info.field.span(), "`keys` can only be used on maps.")
+                }
+                let inner_tokens = Self::element_tokens(v, field_info, &field_name, None);
+                let iter = field_info.collection_iter_tokens(quote!(keys));
+                // Errors are located by the key
+                let tokens = quote!(
+                    for el in #iter {
+                        let __i = &::validify::escape_location(el);
+                        #inner_tokens
                     }
-                    Validator::Time(v) => {
-                        v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
-                    },
-                    Validator::In(v) => v.to_validify_tokens(field_name.clone(), validator_param.clone(), false, true),
-                });
-                let validator_param = field_info
-                    .ident_override
-                    .as_ref()
-                    .map(|ident| quote!(#ident))
-                    .unwrap_or_else(|| {
-                        let ident = field_info.field.ident.as_ref();
-                        if field_info.is_option() {
-                            return quote!(#ident);
-                        }
-                        quote!(self.#ident)
-                    });
-
+                );
+                ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
+            }
+            Validator::Values(v) => {
+                if !field_info.is_map() {
+                    abort!(
+                        field_info.field.span(),
+                        "`values` can only be used on maps."
+                    )
+                }
+                let value = try_extract_map_value(unwrap_type(&field_info.field.ty));
+                let inner_tokens = Self::element_tokens(v, field_info, &field_name, value);
+                let iter = field_info.collection_iter_tokens(quote!(iter));
                 let tokens = quote!(
-                    for (__i, el) in #validator_param.iter().enumerate() {
-                        #(#inner_tokens)*
+                    for (__k, el) in #iter {
+                        let __i = &::validify::escape_location(__k);
+                        #inner_tokens
                     }
                 );
                 ValidationTokens::Normal(field_info.wrap_tokens_if_option(tokens))
             }
             Validator::Nested => {
-                let validator_field = field_info
-                    .ident_override
-                    .as_ref()
-                    .map(|id| quote!(#id))
-                    .unwrap_or_else(|| {
-                        let ident = &field_info.field.ident;
+                Self::nested_tokens(field_info, &field_name, |param| quote!(#param.validate()))
+            }
+            Validator::Remote(def) => Self::nested_tokens(
+                field_info,
+                &field_name,
+                |param| quote!(<#def>::validate_remote(#param)),
+            ),
+            // Nested validation is generated here unless the field is also annotated with `#[validate]`
+            // or `#[validate(remote = "...")]`
+            Validator::MaxDepth(_) => {
+                if field_info
+                    .validations
+                    .iter()
+                    .any(|v| matches!(v, Validator::Nested | Validator::Remote(_)))
+                {
+                    return ValidationTokens::Nested(quote!());
+                }
+                Validator::Nested.to_validate_tokens(field_info, validator_param)
+            }
+        }
+    }
 
-                        if field_info.is_option() || field_info.is_list() || field_info.is_map() {
-                            quote!(#ident)
-                        } else {
-                            quote!(self.#ident)
+    /// Validates the nested values of the field with `validate`, locating their errors at them.
+    fn nested_tokens(
+        field_info: &crate::fields::FieldInfo,
+        field_name: &str,
+        validate: impl Fn(&TokenStream) -> TokenStream,
+    ) -> ValidationTokens {
+        let tokens = field_info.nested_tokens(false, |param, segments| {
+            let segments = segments.iter().rev().collect::<Vec<_>>();
+            let validate = validate(&param);
+            let tokens = quote!(
+                if let Err(mut errs) = #validate {
+                    errs.errors_mut().iter_mut().for_each(|err| {
+                        // Errors of newtypes and other unnamed values belong to the field
+                        if err.field_name().is_none() {
+                            err.set_field(#field_name);
                         }
+                        #(err.set_location(#segments);)*
                     });
+                    errors.merge(errs);
+                }
+            );
+            let max_depth = field_info.max_depth();
+            // The error is located at the value which would exceed the depth
+            field_info.wrap_depth_guard(
+                tokens,
+                quote!(
+                    let mut err = ::validify::ValidationError::new_field("max_depth");
+                    err.set_field(#field_name);
+                    err.add_param("max", &#max_depth);
+                    #(err.set_location(#segments);)*
+                    errors.add(err);
+                ),
+            )
+        });
+        ValidationTokens::Nested(tokens)
+    }
 
-                let field_name = field_info.name();
+    /// Returns the validation tokens for a single element of a collection or map, bound to `el`.
+    /// The element's index or key must be bound to `__i`.
+    ///
+    /// If the element's type is known, any options and smart pointers wrapping it are unwrapped
+    /// for all validators except `required`,
+    /// and nested `iter` validators iterate over the inner lists, joining the indices in `__i`.
+    fn element_tokens(
+        validators: &[Validator],
+        field_info: &crate::fields::FieldInfo,
+        field_name: &str,
+        ty: Option<&syn::Type>,
+    ) -> TokenStream {
+        // `required` checks the option itself
+        let (required, validators): (Vec<_>, Vec<_>) = validators
+            .iter()
+            .partition(|v| matches!(v, Validator::Required(_)));
+
+        let required = Self::unwrapped_element_tokens(&required, field_info, field_name, None);
+
+        let Some(ty) = ty else {
+            let tokens = Self::unwrapped_element_tokens(&validators, field_info, field_name, None);
+            return quote!(
+                #required
+                #tokens
+            );
+        };
 
-                let tokens = quote!(
-                    if let Err(mut errs) = #validator_field.validate() {
-                        errs.errors_mut().iter_mut().for_each(|err| err.set_location(#field_name));
-                        errors.merge(errs);
-                    }
-                );
-                ValidationTokens::Nested(field_info.wrap_tokens_if_option(
-                    field_info.wrap_validator_if_collection(validator_field, tokens),
-                ))
-            }
-        }
+        let tokens = unwrap_type_tokens(ty, &quote!(el), false, &|ty| {
+            Self::unwrapped_element_tokens(&validators, field_info, field_name, Some(ty))
+        });
+
+        quote!(
+            #required
+            #tokens
+        )
+    }
+
+    fn unwrapped_element_tokens(
+        validators: &[&Validator],
+        field_info: &crate::fields::FieldInfo,
+        field_name: &str,
+        ty: Option<&syn::Type>,
+    ) -> TokenStream {
+        let field_name = field_name.to_string();
+        let validator_param = quote!(el);
+        let tokens = validators
+            .iter()
+            .map(|v| match v {
+                Validator::Iter(v) => {
+                    let element = ty.and_then(try_extract_list_element);
+                    let iter = match ty {
+                        Some(ty) if is_hashed(ty) => sorted_tokens(quote!(el.iter())),
+                        _ => quote!(el.iter()),
+                    };
+                    let tokens = Self::element_tokens(v, field_info, &field_name, element);
+                    quote!(
+                        for (__j, el) in #iter.enumerate() {
+                            let __i = &::std::format!("{}/{}", __i, __j);
+                            #tokens
+                        }
+                    )
+                }
+                Validator::Keys(_) | Validator::Values(_) => {
+                    abort!(field_info.field.span(), "`keys` and `values` validators cannot be nested.")
+                }
+                Validator::Nested | Validator::MaxDepth(_) | Validator::Remote(_) => {
+                    abort!(field_info.field.span(), "`validate/validify` is not valid in `iter`. To validate collections of type T, use `validate/validify` directly on the field.")
+                },
+                Validator::Email(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Url(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::CreditCard(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Phone(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Custom(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::With(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Rules(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Range(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Length(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Ip(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::NonControlCharacter(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::NoHtml(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Required(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Regex(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Contains(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                }
+                Validator::Time(v) => {
+                    v.to_validify_tokens(field_name.clone(), validator_param.clone(), true)
+                },
+                Validator::In(v) => v.to_validify_tokens(field_name.clone(), validator_param.clone(), false, true),
+            });
+
+        quote!(#(#tokens)*)
     }
 }
 
+/// Implements `to_validify_tokens` for validators whose rules take no parameters.
+macro_rules! impl_unit_rule {
+    ($($id:ident),*) => {
+        $(
+            impl $id {
+                fn to_validify_tokens(
+                    &self,
+                    field_name: String,
+                    validator_param: TokenStream,
+                    in_iter: bool,
+                ) -> TokenStream {
+                    self.quote_check(
+                        quote!(::validify::rules::$id.check(#validator_param)),
+                        &field_name,
+                        in_iter,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_unit_rule! {
+    Email,
+    Url,
+    Phone,
+    CreditCard,
+    NonControlChar,
+    NoHtml,
+    Required
+}
+
 impl Ip {
     fn to_validify_tokens(
         &self,
@@ -271,30 +462,16 @@ impl Ip {
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-
-        let Ip { ref format, .. } = self;
-
-        let validate_fn = match format {
-            Some(format) => match format {
-                crate::validate::validation::IpFormat::V4 => quote!(validate_ip_v4),
-                crate::validate::validation::IpFormat::V6 => quote!(validate_ip_v6),
-            },
-            None => quote!(validate_ip),
+        let format = match self.format {
+            Some(IpFormat::V4) => quote!(Some(::validify::rules::IpFormat::V4)),
+            Some(IpFormat::V6) => quote!(Some(::validify::rules::IpFormat::V6)),
+            None => quote!(None),
         };
 
-        quote!(
-            if !::validify::#validate_fn(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
+        self.quote_check(
+            quote!((::validify::rules::Ip { format: #format }).check(#validator_param)),
+            &field_name,
+            in_iter,
         )
     }
 }
@@ -313,66 +490,25 @@ impl Length {
             ..
         } = self;
 
-        let quoted_error = self.quote_error(&field_name);
-        let error_param =
-            quote!(err.add_param("actual", &::validify::Length::length(&#validator_param)););
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-
-        let min_err_param_quoted = if let Some(ref v) = min {
-            quote!(err.add_param("min", &#v);)
-        } else {
-            quote!()
-        };
-
-        let max_err_param_quoted = if let Some(ref v) = max {
-            quote!(err.add_param("max", &#v);)
-        } else {
-            quote!()
-        };
-
-        let equal_err_param_quoted = if let Some(ref v) = equal {
-            quote!(err.add_param("equal", &#v);)
-        } else {
-            quote!()
-        };
-
-        let min_tokens = min
-            .as_ref()
-            .map(ValueOrPath::tokens)
-            .map(|x| quote!(Some(#x as usize)))
-            .unwrap_or(quote!(None));
-
-        let max_tokens = max
-            .as_ref()
-            .map(ValueOrPath::tokens)
-            .map(|x| quote!(Some(#x as usize)))
-            .unwrap_or(quote!(None));
-
-        let equal_tokens = equal
-            .as_ref()
-            .map(ValueOrPath::tokens)
-            .map(|x| quote!(Some(#x as usize)))
-            .unwrap_or(quote!(None));
+        let [min, max, equal] = [min, max, equal].map(|value| {
+            value
+                .as_ref()
+                .map(ValueOrPath::tokens)
+                .map(|x| quote!(Some(#x as usize)))
+                .unwrap_or(quote!(None))
+        });
 
-        quote!(
-            if !::validify::validate_length(
-                #validator_param,
-                #min_tokens,
-                #max_tokens,
-                #equal_tokens
-            ) {
-                #quoted_error
-                #min_err_param_quoted
-                #max_err_param_quoted
-                #equal_err_param_quoted
-                #error_param
-                #error_location
-                errors.add(err);
-            }
+        self.quote_check(
+            quote!(
+                (::validify::rules::Length {
+                    min: #min,
+                    max: #max,
+                    equal: #equal,
+                })
+                .check(#validator_param)
+            ),
+            &field_name,
+            in_iter,
         )
     }
 }
@@ -384,211 +520,133 @@ impl Range {
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-
         let Range {
             ref min, ref max, ..
         } = self;
 
-        let min_err_param_quoted = if let Some(v) = min {
-            quote!(err.add_param("min", &#v);)
-        } else {
-            quote!()
-        };
-
-        let max_err_param_quoted = if let Some(v) = max {
-            quote!(err.add_param("max", &#v);)
-        } else {
-            quote!()
-        };
-
-        let min_tokens = min
-            .as_ref()
-            .map(ValueOrPath::tokens)
-            .map(|x| quote!(Some(#x as f64)))
-            .unwrap_or(quote!(None));
-
-        let max_tokens = max
-            .as_ref()
-            .map(ValueOrPath::tokens)
-            .map(|x| quote!(Some(#x as f64)))
-            .unwrap_or(quote!(None));
+        let [min, max] = [min, max].map(|value| {
+            value
+                .as_ref()
+                .map(ValueOrPath::tokens)
+                .map(|x| quote!(Some(#x)))
+                .unwrap_or(quote!(None::<f64>))
+        });
 
-        quote!(
-            if !::validify::validate_range(
-                *#validator_param as f64,
-                #min_tokens,
-                #max_tokens
-            ) {
-                #quoted_error
-                #min_err_param_quoted
-                #max_err_param_quoted
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
+        self.quote_check(
+            quote!((::validify::rules::Range { min: #min, max: #max }).check(#validator_param)),
+            &field_name,
+            in_iter,
         )
     }
 }
 
-impl CreditCard {
+impl Custom {
     fn to_validify_tokens(
         &self,
         field_name: String,
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-        quote!(
-            if !::validify::validate_credit_card(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
+        let Custom { ref path, .. } = self;
+        quote_custom_call(
+            quote!(#path(#validator_param)),
+            self.code.as_deref(),
+            self.message(),
+            field_name,
+            in_iter,
         )
     }
 }
 
-impl Phone {
+impl With {
     fn to_validify_tokens(
         &self,
         field_name: String,
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-        quote!(
-            if !::validify::validate_phone(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
+        let With { ref validator, .. } = self;
+        quote_custom_call(
+            quote!(::validify::Validator::validate(&(#validator), #validator_param)),
+            self.code.as_deref(),
+            self.message(),
+            field_name,
+            in_iter,
         )
     }
 }
 
-impl Custom {
+impl Rules {
     fn to_validify_tokens(
         &self,
         field_name: String,
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let Custom { ref path, .. } = self;
+        let Rules { ref path, .. } = self;
 
-        let err_with_msg = if let Some(msg) = self.message() {
-            quote!(err.with_message(#msg.to_string()))
-        } else {
-            quote!(err)
-        };
+        let code = self.code.as_ref().map(|code| quote!(err.set_code(#code);));
+        let message = self
+            .message()
+            .map(|msg| quote!(err.set_message(#msg.to_string());));
         let error_location = if in_iter {
             quote!(err.set_location_idx(__i, #field_name);)
         } else {
             quote!(err.set_location(#field_name);)
         };
 
+        // Rule set errors are located at the value
         quote!(
-            if let Err(mut err) = #path(#validator_param) {
-                let f_name = err.field_name().map(|s|s.to_string());
-                if let Some(field_name) = f_name {
-                    err.set_location(field_name);
-                } else {
-                    err.set_field(#field_name);
+            if let Err(mut errs) = #path::validate(#validator_param) {
+                for err in errs.errors_mut() {
+                    if err.field_name().is_none() {
+                        err.set_field(#field_name);
+                    }
                     #error_location
+                    #code
+                    #message
                 }
-                errors.add(#err_with_msg);
-            };
-        )
-    }
-}
-
-impl NonControlChar {
-    fn to_validify_tokens(
-        &self,
-        field_name: String,
-        validator_param: TokenStream,
-        in_iter: bool,
-    ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-        quote!(
-            if !::validify::validate_non_control_character(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
+                errors.merge(errs);
             }
         )
     }
 }
 
-impl Url {
-    fn to_validify_tokens(
-        &self,
-        field_name: String,
-        validator_param: TokenStream,
-        in_iter: bool,
-    ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-        quote!(
-            if !::validify::validate_url(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
+/// Quotes the handling of the error returned from a custom validator call. Errors
+/// with a field name are located at it, otherwise they get the current field's.
+/// A `code` given in the attribute replaces the code of the returned error.
+fn quote_custom_call(
+    call: TokenStream,
+    code: Option<&str>,
+    message: Option<&str>,
+    field_name: String,
+    in_iter: bool,
+) -> TokenStream {
+    let code = code.map(|code| quote!(err.set_code(#code);));
+    let err_with_msg = if let Some(msg) = message {
+        quote!(err.with_message(#msg.to_string()))
+    } else {
+        quote!(err)
+    };
+    let error_location = if in_iter {
+        quote!(err.set_location_idx(__i, #field_name);)
+    } else {
+        quote!(err.set_location(#field_name);)
+    };
+
+    quote!(
+        if let Err(mut err) = #call {
+            let f_name = err.field_name().map(|s|s.to_string());
+            if let Some(field_name) = f_name {
+                err.set_location(field_name);
+            } else {
+                err.set_field(#field_name);
                 #error_location
-                errors.add(err);
             }
-        )
-    }
-}
-
-impl Email {
-    fn to_validify_tokens(
-        &self,
-        field_name: String,
-        validator_param: TokenStream,
-        in_iter: bool,
-    ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
+            #code
+            errors.add(#err_with_msg);
         };
-        quote!(
-            if !::validify::validate_email(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
-        )
-    }
+    )
 }
 
 impl Regex {
@@ -599,20 +657,17 @@ impl Regex {
         in_iter: bool,
     ) -> TokenStream {
         let Regex { ref path, .. } = self;
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-
-        quote!(
-            if !#path.is_match(#validator_param) {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
+        // Duck typed so any regex type with an `is_match` method can be used
+        self.quote_check(
+            quote!(
+                if #path.is_match(#validator_param) {
+                    Ok(())
+                } else {
+                    Err(::validify::rules::Regex::error(#validator_param))
+                }
+            ),
+            &field_name,
+            in_iter,
         )
     }
 }
@@ -627,31 +682,17 @@ impl In {
     ) -> TokenStream {
         let In { ref expr, not, .. } = self;
 
-        let quoted_error = self.quote_error(&field_name);
-
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
+        let rule = quote!((::validify::rules::In { collection: &#expr, not: #not }));
 
         if is_option {
+            let check = self.quote_check(quote!(#rule.check(&param)), &field_name, in_iter);
             quote!(
                 if let Some(ref param) = #validator_param {
-                    if !::validify::validate_contains(&#expr, &param, #not) {
-                        #quoted_error
-                        #error_location
-                        errors.add(err);
-                    }
+                    #check
                 }
             )
         } else {
-            quote!(
-                if !::validify::validate_contains(&#expr, &#validator_param, #not) {
-                    #quoted_error
-                    #error_location
-                    errors.add(err);
-            })
+            self.quote_check(quote!(#rule.check(&#validator_param)), &field_name, in_iter)
         }
     }
 }
@@ -665,47 +706,17 @@ impl Contains {
     ) -> TokenStream {
         let Contains { not, ref value, .. } = self;
 
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
-        } else {
-            quote!(err.set_location(#field_name);)
-        };
-
         // Only add the target if it's a literal since otherwise it will just be the variable name
-        let added_param = matches!(value, Some(ValueOrPath::Value(_)))
-            .then_some(quote!(err.add_param("target", &#value);));
-
-        quote!(
-            if !::validify::validate_contains(#validator_param, &#value, #not) {
-                #quoted_error
-                #added_param
-                #error_location
-                errors.add(err);
-            }
-        )
-    }
-}
-
-impl Required {
-    fn to_validify_tokens(
-        &self,
-        field_name: String,
-        validator_param: TokenStream,
-        in_iter: bool,
-    ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
-        let error_location = if in_iter {
-            quote!(err.set_location_idx(__i, #field_name);)
+        let check = if matches!(value, Some(ValueOrPath::Value(_))) {
+            quote!(check)
         } else {
-            quote!(err.set_location(#field_name);)
+            quote!(check_without_target)
         };
-        quote!(
-            if !::validify::validate_required(#validator_param) {
-                #quoted_error
-                #error_location
-                errors.add(err);
-            }
+
+        self.quote_check(
+            quote!((::validify::rules::Contains { value: #value, not: #not }).#check(#validator_param)),
+            &field_name,
+            in_iter,
         )
     }
 }
@@ -717,7 +728,6 @@ impl Time {
         validator_param: TokenStream,
         in_iter: bool,
     ) -> TokenStream {
-        let quoted_error = self.quote_error(&field_name);
         let error_location = if in_iter {
             quote!(err.set_location_idx(__i, #field_name);)
         } else {
@@ -759,131 +769,46 @@ impl Time {
             },
         });
 
-        use crate::validate::validation::TimeOp as TO;
-        let validation_fn = match op {
-            TO::BeforeNow => {
-                if has_time {
-                    quote!(::validify::time::before_now(#validator_param, #inclusive))
-                } else {
-                    quote!(::validify::time::before_today(#validator_param, #inclusive))
-                }
+        // Rules with targets are checked against the `target` bound by [quote_time_with_target]
+        let rule = match op {
+            TimeOp::BeforeNow => {
+                quote!(::validify::rules::Time::BeforeNow { inclusive: #inclusive })
             }
-            TO::AfterNow => {
-                if has_time {
-                    quote!(::validify::time::after_now(#validator_param, #inclusive))
-                } else {
-                    quote!(::validify::time::after_today(#validator_param, #inclusive))
-                }
+            TimeOp::AfterNow => quote!(::validify::rules::Time::AfterNow { inclusive: #inclusive }),
+            TimeOp::BeforeFromNow => {
+                quote!(::validify::rules::Time::BeforeFromNow { duration: #duration })
             }
-            TO::BeforeFromNow => {
-                if has_time {
-                    quote!(::validify::time::before_from_now(#validator_param, #duration))
-                } else {
-                    quote!(::validify::time::before_from_now_date(#validator_param, #duration))
-                }
+            TimeOp::AfterFromNow => {
+                quote!(::validify::rules::Time::AfterFromNow { duration: #duration })
             }
-            TO::AfterFromNow => {
-                if has_time {
-                    quote!(::validify::time::after_from_now(#validator_param, #duration))
-                } else {
-                    quote!(::validify::time::after_from_now_date(#validator_param, #duration))
-                }
+            TimeOp::Before => {
+                quote!(::validify::rules::Time::Before { target: *target, inclusive: #inclusive })
             }
-            TO::Before => {
-                let validation = {
-                    let validation_fn = if has_time {
-                        quote!(!::validify::time::before(#validator_param, target, #inclusive))
-                    } else {
-                        quote!(!::validify::time::before_date(#validator_param, target, #inclusive))
-                    };
-                    quote!(
-                        if #validation_fn {
-                            #quoted_error
-                            err.add_param("actual", &#validator_param);
-                            err.add_param("target", target);
-                            #error_location
-                            errors.add(err);
-                        }
-                    )
-                };
-                return quote_time_with_target(
-                    target.as_ref().unwrap(),
-                    validation,
-                    quoted_parse_error,
-                    format.as_deref(),
-                    has_time,
-                );
+            TimeOp::After => {
+                quote!(::validify::rules::Time::After { target: *target, inclusive: #inclusive })
             }
-            TO::After => {
-                let validation = {
-                    let validation_fn = if has_time {
-                        quote!(!::validify::time::after(#validator_param, target, #inclusive))
-                    } else {
-                        quote!(!::validify::time::after_date(#validator_param, target, #inclusive))
-                    };
-                    quote!(
-                        if #validation_fn {
-                            #quoted_error
-                            err.add_param("actual", &#validator_param);
-                            err.add_param("target", target);
-                            #error_location
-                            errors.add(err);
-                        }
-                    )
-                };
-                return quote_time_with_target(
-                    target.as_ref().unwrap(),
-                    validation,
-                    quoted_parse_error,
-                    format.as_deref(),
-                    has_time,
-                );
+            TimeOp::InPeriod => {
+                quote!(::validify::rules::Time::InPeriod { start: *target, duration: #duration })
             }
-            TO::InPeriod => {
-                let validation = {
-                    let validation_fn = if has_time {
-                        quote!(!::validify::time::in_period(#validator_param, target, #duration))
-                    } else {
-                        quote!(!::validify::time::in_period_date(#validator_param, target, #duration))
-                    };
-
-                    // We can safely unwrap since we do a check for overflow before quoting
-                    quote!(
-                        if #validation_fn {
-                            #quoted_error
-                            err.add_param("actual", &#validator_param);
-                            let end = target.checked_add_signed(#duration).unwrap();
-                            if end < *target {
-                                err.add_param("from", &end);
-                                err.add_param("to", target);
-                            } else {
-                                err.add_param("from", target);
-                                err.add_param("to", &end);
-                            }
-                            #error_location
-                            errors.add(err);
-                        }
-                    )
-                };
-                return quote_time_with_target(
-                    target.as_ref().unwrap(),
-                    validation,
-                    quoted_parse_error,
-                    format.as_deref(),
-                    has_time,
-                );
-            }
-            TO::None => unreachable!(),
+            TimeOp::None => unreachable!(),
         };
 
-        quote!(
-            if !#validation_fn {
-                #quoted_error
-                err.add_param("actual", &#validator_param);
-                #error_location
-                errors.add(err);
-            }
-        )
+        let validation = self.quote_check(
+            quote!((#rule).check(#validator_param)),
+            &field_name,
+            in_iter,
+        );
+
+        match target {
+            Some(target) => quote_time_with_target(
+                target,
+                validation,
+                quoted_parse_error,
+                format.as_deref(),
+                has_time,
+            ),
+            None => validation,
+        }
     }
 }
 
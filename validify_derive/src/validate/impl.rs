@@ -1,9 +1,11 @@
 use super::parser::*;
 use super::validation::{
-    Contains, CreditCard, Custom, Email, In, Ip, NonControlChar, Phone, Regex, Required,
-    SchemaValidation, Url, Validator,
+    Contains, CreditCard, Custom, Email, In, Ip, NoHtml, NonControlChar, Phone, Regex, Required,
+    SchemaValidation, Url, Validator, With,
 };
-use crate::fields::{Fields, Variants};
+use crate::bound::{with_bound, BoundTarget, BOUND};
+use crate::fields::{FieldInfo, Fields, Variants};
+use crate::introspection::rules_fn_tokens;
 use crate::tokens::quote_schema_validation;
 use crate::validate::ValidationMeta;
 use proc_macro_error::abort;
@@ -14,6 +16,7 @@ use syn::spanned::Spanned;
 
 const VALIDATE: &str = "validate";
 const VALIDIFY: &str = "validify";
+const REMOTE: &str = "remote";
 
 const EMAIL: &str = "email";
 const URL: &str = "url";
@@ -22,6 +25,7 @@ const RANGE: &str = "range";
 const CONTAINS: &str = "contains";
 const CONTAINS_NOT: &str = "contains_not";
 const NON_CONTROL_CHAR: &str = "non_control_char";
+const NO_HTML: &str = "no_html";
 const CUSTOM: &str = "custom";
 const REGEX: &str = "regex";
 const CREDIT_CARD: &str = "credit_card";
@@ -32,70 +36,152 @@ const NOT_IN: &str = "not_in";
 const IP: &str = "ip";
 const TIME: &str = "time";
 const ITER: &str = "iter";
+const KEYS: &str = "keys";
+const VALUES: &str = "values";
+const MAX_DEPTH: &str = "max_depth";
+const WITH: &str = "with";
+const RULES: &str = "rules";
 
 /// Entrypoint for `#[derive(Validate)]`.
 pub fn impl_validate(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
     let ident = &input.ident;
+    let remote = find_remote(&input.attrs);
 
-    match input.data {
+    let schema_validation = collect_schema_validation(&input.attrs).unwrap();
+    let schema_validation = quote_schema_validation(&schema_validation);
+
+    let (validation, rules, generics) = match input.data {
         syn::Data::Struct(ref data_struct) => {
-            let fields = Fields::collect(&input.attrs, &data_struct.fields);
+            let mut fields = Fields::collect(&input.attrs, &data_struct.fields);
+            fields.limit_recursion();
             let field_validation = fields.to_validate_tokens();
+            let rules = rules_fn_tokens(fields.0.iter().map(|field| (None, field)));
+
+            let generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Validate,
+                &fields.0,
+                FieldInfo::is_nested,
+                quote!(::validify::Validate),
+            );
+
+            (quote!(#(#field_validation)*), rules, generics)
+        }
+        syn::Data::Enum(ref data_enum) => {
+            let mut variants = Variants::collect(data_enum);
+            variants.limit_recursion();
+            let variant_validation = variants.to_validate_tokens();
+            let rules = rules_fn_tokens(
+                variants
+                    .variant_fields()
+                    .map(|(variant, field)| (Some(variant), field)),
+            );
+
+            let generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Validate,
+                variants.fields(),
+                FieldInfo::is_nested,
+                quote!(::validify::Validate),
+            );
+
+            (variant_validation, rules, generics)
+        }
+        syn::Data::Union(_) => abort!(
+            input.span(),
+            "#[derive(Validate)] can only be used on structs with named fields or enums"
+        ),
+    };
 
-            let schema_validation = collect_schema_validation(&input.attrs).unwrap();
-            let schema_validation = quote_schema_validation(&schema_validation);
+    let validate_fn = quote!(
+        fn validate(&self) -> ::std::result::Result<(), ::validify::ValidationErrors> {
+            let mut errors = ::validify::ValidationErrors::new();
 
-            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            #validation
 
-            quote!(
-                impl #impl_generics ::validify::Validate for #ident #ty_generics #where_clause {
-                    fn validate(&self) -> ::std::result::Result<(), ::validify::ValidationErrors> {
-                        let mut errors = ::validify::ValidationErrors::new();
+            #(#schema_validation)*
 
-                        #(#field_validation)*
+            if errors.is_empty() {
+                ::std::result::Result::Ok(())
+            } else {
+                ::std::result::Result::Err(errors)
+            }
+        }
+    );
 
-                        #(#schema_validation)*
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-                        if errors.is_empty() {
-                            ::std::result::Result::Ok(())
-                        } else {
-                            ::std::result::Result::Err(errors)
-                        }
-                    }
+    let Some(remote) = remote else {
+        return quote!(
+            impl #impl_generics ::validify::Validate for #ident #ty_generics #where_clause {
+                #validate_fn
+
+                #rules
+            }
+        );
+    };
+
+    // The validation is implemented on the remote type through a local trait, since the
+    // generated code accesses the fields through `self`
+    quote!(
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Validates the remote type with the rules declared on this type.
+            ///
+            /// Remote types do not implement `Validate`, so their rules are not available through
+            /// `Validate::rules`, and fields containing them are not described by it.
+            pub fn validate_remote(
+                value: &#remote,
+            ) -> ::std::result::Result<(), ::validify::ValidationErrors> {
+                trait __ValidateRemote {
+                    fn validate(&self) -> ::std::result::Result<(), ::validify::ValidationErrors>;
                 }
-            )
+
+                impl #impl_generics __ValidateRemote for #remote #where_clause {
+                    #validate_fn
+                }
+
+                __ValidateRemote::validate(value)
+            }
         }
-        syn::Data::Enum(ref data_enum) => {
-            let variant_validation = Variants::collect(data_enum).to_validate_tokens();
+    )
+}
 
-            let schema_validation = collect_schema_validation(&input.attrs).unwrap();
-            let schema_validation = quote_schema_validation(&schema_validation);
+/// Parses the type from `#[validate(remote = "...")]` if present.
+pub fn find_remote(attrs: &[syn::Attribute]) -> Option<syn::Type> {
+    let mut remote = None;
 
-            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(VALIDATE)) {
+        let syn::Meta::List(ref list) = attr.meta else {
+            continue;
+        };
 
-            quote!(
-                impl #impl_generics ::validify::Validate for #ident #ty_generics #where_clause {
-                    fn validate(&self) -> ::std::result::Result<(), ::validify::ValidationErrors> {
-                        let mut errors = ::validify::ValidationErrors::new();
+        let res = list.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(REMOTE) {
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::LitStr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::Group>()?;
+                }
+                return Ok(());
+            }
 
-                        #variant_validation
+            let lit = meta.value()?.parse::<syn::LitStr>()?;
+            remote = Some(
+                lit.parse::<syn::Type>()
+                    .unwrap_or_else(|e| abort!(lit.span(), "Invalid remote type: {}", e)),
+            );
 
-                        #(#schema_validation)*
+            Ok(())
+        });
 
-                        if errors.is_empty() {
-                            ::std::result::Result::Ok(())
-                        } else {
-                            ::std::result::Result::Err(errors)
-                        }
-                    }
-                }
-            )
+        if let Err(e) = res {
+            abort!(attr.span(), "{}", e)
         }
-        syn::Data::Union(_) => abort!(
-            input.span(),
-            "#[derive(Validate)] can only be used on structs with named fields or enums"
-        ),
     }
+
+    remote
 }
 
 /// Find if a struct or enum has some schema validation and returns the info if so.
@@ -109,6 +195,16 @@ fn collect_schema_validation(
 
     for attr in filtered {
         attr.parse_nested_meta(|meta| {
+            // Handled when generating the impl
+            if meta.path.is_ident(BOUND) && meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<proc_macro2::Group>()?;
+                return Ok(());
+            }
+            if meta.path.is_ident(BOUND) || meta.path.is_ident(REMOTE) {
+                meta.value()?.parse::<syn::LitStr>()?;
+                return Ok(());
+            }
+
             validations.push(SchemaValidation {
                 function: meta.path,
             });
@@ -140,12 +236,25 @@ pub fn collect_validation(field: &syn::Field) -> Vec<Validator> {
 
         list.parse_nested_meta(|meta| {
             if meta.path.is_ident(ITER) {
-                let mut validators_iter = vec![];
+                validators.push(Validator::Iter(parse_element_validations(&meta)?));
+            } else if meta.path.is_ident(KEYS) {
+                let mut validators_keys = vec![];
                 meta.parse_nested_meta(|meta| {
-                    parse_single_validation(meta, &mut validators_iter)?;
+                    parse_single_validation(meta, &mut validators_keys)?;
                     Ok(())
                 })?;
-                validators.push(Validator::Iter(validators_iter));
+                validators.push(Validator::Keys(validators_keys));
+            } else if meta.path.is_ident(VALUES) {
+                validators.push(Validator::Values(parse_element_validations(&meta)?));
+            } else if meta.path.is_ident(MAX_DEPTH) {
+                let depth = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+                validators.push(Validator::MaxDepth(Some(depth)));
+            } else if meta.path.is_ident(REMOTE) {
+                let lit = meta.value()?.parse::<syn::LitStr>()?;
+                let def = lit.parse::<syn::Type>().map_err(|e| {
+                    syn::Error::new(lit.span(), format!("Invalid remote definition: {e}"))
+                })?;
+                validators.push(Validator::Remote(Box::new(def)));
             } else {
                 parse_single_validation(meta, &mut validators)?;
             }
@@ -157,6 +266,20 @@ pub fn collect_validation(field: &syn::Field) -> Vec<Validator> {
     validators
 }
 
+/// Parses the validations of `iter` and `values`, which may contain further `iter` validations
+/// for nested lists.
+fn parse_element_validations(meta: &ParseNestedMeta<'_>) -> Result<Vec<Validator>, syn::Error> {
+    let mut validators = vec![];
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident(ITER) {
+            validators.push(Validator::Iter(parse_element_validations(&meta)?));
+            return Ok(());
+        }
+        parse_single_validation(meta, &mut validators)
+    })?;
+    Ok(validators)
+}
+
 fn parse_single_validation(
     meta: ParseNestedMeta<'_>,
     validators: &mut Vec<Validator>,
@@ -268,6 +391,16 @@ fn parse_single_validation(
         return Ok(());
     }
 
+    if meta.path.is_ident(NO_HTML) {
+        if meta.is_full_pattern() {
+            let validation = parse_no_html_full(&meta)?;
+            validators.push(Validator::NoHtml(validation))
+        } else {
+            validators.push(Validator::NoHtml(NoHtml::default()));
+        }
+        return Ok(());
+    }
+
     if meta.path.is_ident(CUSTOM) {
         if meta.is_single_path(CUSTOM) {
             let content;
@@ -283,6 +416,22 @@ fn parse_single_validation(
         return Ok(());
     }
 
+    if meta.path.is_ident(RULES) {
+        validators.push(Validator::Rules(parse_rules(&meta)?));
+        return Ok(());
+    }
+
+    if meta.path.is_ident(WITH) {
+        if meta.is_full_pattern() {
+            let validation = parse_with_full(&meta)?;
+            validators.push(Validator::With(validation));
+        } else {
+            let validator = meta.value()?.parse::<syn::Expr>()?;
+            validators.push(Validator::With(With::new(validator)));
+        }
+        return Ok(());
+    }
+
     if meta.path.is_ident(REGEX) {
         if meta.is_single_path(REGEX) {
             let content;
@@ -1,6 +1,6 @@
 use super::validation::{
-    Contains, CreditCard, Custom, Email, In, Ip, Length, NonControlChar, Phone, Range, Regex,
-    Required, Time, TimeMultiplier, TimeOp, Url,
+    Contains, CreditCard, Custom, Email, In, Ip, Length, NoHtml, NonControlChar, Phone, Range,
+    Regex, Required, Rules, Time, TimeMultiplier, TimeOp, Url, With,
 };
 use proc_macro_error::abort;
 use quote::quote;
@@ -86,6 +86,7 @@ macro_rules! code_and_message {
 parser!(parse_email_full, Email);
 parser!(parse_url_full, Url);
 parser!(parse_non_control_char_full, NonControlChar);
+parser!(parse_no_html_full, NoHtml);
 parser!(parse_phone_full, Phone);
 parser!(parse_credit_card_full, CreditCard);
 parser!(parse_required_full, Required);
@@ -222,6 +223,75 @@ pub fn parse_contains_full(meta: &ParseNestedMeta, not: bool) -> Result<Contains
     Ok(validation)
 }
 
+pub fn parse_with_full(meta: &ParseNestedMeta) -> Result<With, syn::Error> {
+    let mut validation = With::new(syn::parse_quote!(()));
+    let mut has_validator = false;
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("validator") {
+            let content = meta.value()?;
+            match content.parse::<syn::Expr>() {
+                Ok(expr) => {
+                    *validation.validator = expr;
+                    has_validator = true;
+                }
+                Err(_) => {
+                    return Err(meta.error(
+                        "with value must be an expression implementing `Validator` for the type of the field",
+                    ))
+                }
+            }
+            return Ok(());
+        }
+
+        code_and_message!(validation, meta);
+
+        Err(meta.error("Unrecognized with parameter, accepted are: validator, code, message"))
+    })?;
+
+    if !has_validator {
+        abort!(
+            meta.input.span(),
+            "with validation must contain a validator"
+        )
+    }
+
+    Ok(validation)
+}
+
+/// Parses `rules(RuleSet, code = "...", message = "...")`.
+pub fn parse_rules(meta: &ParseNestedMeta) -> Result<Rules, syn::Error> {
+    let mut validation = Rules {
+        path: syn::Path {
+            leading_colon: None,
+            segments: Punctuated::new(),
+        },
+        code: None,
+        message: None,
+    };
+
+    meta.parse_nested_meta(|meta| {
+        code_and_message!(validation, meta);
+
+        if validation.path.segments.is_empty() {
+            validation.path = meta.path;
+            return Ok(());
+        }
+
+        Err(meta
+            .error("Unrecognized rules parameter, accepted are: the rule set path, code, message"))
+    })?;
+
+    if validation.path.segments.is_empty() {
+        abort!(
+            meta.input.span(),
+            "rules must contain the path of a rule set"
+        )
+    }
+
+    Ok(validation)
+}
+
 pub fn parse_custom_full(meta: &ParseNestedMeta) -> Result<Custom, syn::Error> {
     let mut validation = Custom {
         path: syn::Path {
@@ -19,14 +19,20 @@ pub trait Describe {
 #[derive(Debug)]
 pub enum Validator {
     Iter(Vec<Self>),
+    Keys(Vec<Self>),
+    Values(Vec<Self>),
     Email(Email),
     Url(Url),
     CreditCard(CreditCard),
     Phone(Phone),
     Custom(Custom),
+    With(With),
+    /// Runs a rule set defined with `rule_set!`
+    Rules(Rules),
     Range(Range),
     Length(Length),
     NonControlCharacter(NonControlChar),
+    NoHtml(NoHtml),
     Required(Required),
     Regex(Regex),
     Contains(Contains),
@@ -34,6 +40,12 @@ pub enum Validator {
     In(In),
     Ip(Ip),
     Nested,
+    /// Limits the nesting depth of nested fields, implies [Validator::Nested]. `None` is the default
+    /// limit of nested fields not annotated with `max_depth`.
+    MaxDepth(Option<usize>),
+    /// Validates a nested value of a foreign type with the `validate_remote` of its definition,
+    /// `#[validate(remote = "Def")]`
+    Remote(Box<syn::Type>),
 }
 
 /// Shortcut for creating simple validation structs.
@@ -97,6 +109,11 @@ validation!(
     Default;
 );
 
+validation!(
+    NoHtml : "no_html",
+    Default;
+);
+
 validation!(
     Length : "length",
     Default;
@@ -112,11 +129,6 @@ validation!(
     max: Option<ValueOrPath<f64>>
 );
 
-validation!(
-    Nested : "nested",
-    Default;
-);
-
 validation!(
     Required : "required",
     Default;
@@ -137,6 +149,26 @@ impl Custom {
     }
 }
 
+validation!(
+    With : "with";
+    validator: Box<syn::Expr>
+);
+
+impl With {
+    pub fn new(validator: syn::Expr) -> Self {
+        Self {
+            validator: Box::new(validator),
+            code: None,
+            message: None,
+        }
+    }
+}
+
+validation!(
+    Rules : "rules";
+    path: syn::Path
+);
+
 validation!(
     Regex : "regex";
     path: syn::Path
@@ -250,22 +282,17 @@ pub struct Time {
     pub has_time: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum TimeMultiplier {
     Seconds,
     Minutes,
     Hours,
     Days,
     Weeks,
+    #[default]
     None,
 }
 
-impl Default for TimeMultiplier {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl Describe for Time {
     fn code(&self) -> &str {
         if let Some(ref code) = self.code {
@@ -405,7 +432,7 @@ impl Time {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum TimeOp {
     BeforeNow,
     AfterNow,
@@ -414,6 +441,7 @@ pub enum TimeOp {
     BeforeFromNow,
     AfterFromNow,
     InPeriod,
+    #[default]
     None,
 }
 
@@ -433,9 +461,3 @@ impl From<String> for TimeOp {
         }
     }
 }
-
-impl Default for TimeOp {
-    fn default() -> Self {
-        Self::None
-    }
-}
@@ -1,52 +1,176 @@
-use super::modifier::Modifier;
-use crate::fields::{Fields, Variants};
-use crate::validate::r#impl::impl_validate;
+use super::modifier::{Modifier, TextUnit, TimeUnit};
+use crate::bound::{with_bound, BoundTarget};
+use crate::fields::{
+    try_extract_list_element, try_extract_map_key, try_extract_map_value, unwrap_type, FieldInfo,
+    Fields, Variants,
+};
+use crate::validate::r#impl::{find_remote, impl_validate};
 use proc_macro_error::abort;
 use quote::quote;
+use syn::meta::ParseNestedMeta;
 use syn::parenthesized;
 use syn::spanned::Spanned;
 
 const TRIM_MODIFIER: &str = "trim";
 const CUSTOM_MODIFIER: &str = "custom";
+const TRY_CUSTOM_MODIFIER: &str = "try_custom";
+const WITH_MODIFIER: &str = "with";
 const UPPERCASE_MODIFIER: &str = "uppercase";
 const LOWERCASE_MODIFIER: &str = "lowercase";
 const CAPITALIZE_MODIFIER: &str = "capitalize";
+const SNAKE_CASE_MODIFIER: &str = "snake_case";
+const KEBAB_CASE_MODIFIER: &str = "kebab_case";
+const CAMEL_CASE_MODIFIER: &str = "camel_case";
+const PASCAL_CASE_MODIFIER: &str = "pascal_case";
+const SCREAMING_SNAKE_CASE_MODIFIER: &str = "screaming_snake_case";
+const SLUGIFY_MODIFIER: &str = "slugify";
+const SANITIZE_HTML_MODIFIER: &str = "sanitize_html";
+const STRIP_HTML_MODIFIER: &str = "strip_html";
+const EMAIL_CANONICAL_MODIFIER: &str = "email_canonical";
+const PHONE_E164_MODIFIER: &str = "phone_e164";
+const URL_NORMALIZE_MODIFIER: &str = "url_normalize";
+const CLAMP_MODIFIER: &str = "clamp";
+const ROUND_MODIFIER: &str = "round";
+const FLOOR_MODIFIER: &str = "floor";
+const CEIL_MODIFIER: &str = "ceil";
+const ABS_MODIFIER: &str = "abs";
+const TRUNCATE_MODIFIER: &str = "truncate";
+const TO_UTC_MODIFIER: &str = "to_utc";
+const SORT_MODIFIER: &str = "sort";
+const SORT_BY_KEY_MODIFIER: &str = "sort_by_key";
+const DEDUP_MODIFIER: &str = "dedup";
+const RETAIN_NON_EMPTY_MODIFIER: &str = "retain_non_empty";
+const KEYS_MODIFIER: &str = "keys";
+const VALUES_MODIFIER: &str = "values";
+const ITER_MODIFIER: &str = "iter";
 const VALIDIFY: &str = "validify";
+const BEFORE_MODIFY: &str = "before_modify";
+const AFTER_VALIDATE: &str = "after_validate";
 const MODIFY: &str = "modify";
 
 /// Impl entry point
 pub fn impl_validify(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
     let ident = &input.ident;
 
+    if find_remote(&input.attrs).is_some() {
+        abort!(
+            input.span(),
+            "`remote` is only supported by #[derive(Validate)], as modifiers need mutable access"
+        )
+    }
+
     match input.data {
         syn::Data::Struct(ref data_struct) => {
-            let fields = Fields::collect(&input.attrs, &data_struct.fields);
+            let mut fields = Fields::collect(&input.attrs, &data_struct.fields);
+            fields.limit_recursion();
 
             let modifiers = fields.to_modify_tokens();
+            let after_validate_modifiers = fields.to_after_validate_tokens();
+            let report_modifiers = fields.to_report_tokens();
+            let report_phase = quote!(#(#report_modifiers)*);
+            let after_validate_phase = quote!(#(#after_validate_modifiers)*);
+
+            let hooks = ContainerHooks::collect(&input.attrs);
+            let container_modifiers = hooks.modifier_tokens();
+            let container_report = hooks.report_tokens();
+            let before_modify = hooks.before_modify_tokens();
+            let after_validate = hooks.after_validate_tokens();
 
             let validate_impl = impl_validate(input);
 
-            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            let modify_generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Modify,
+                &fields.0,
+                FieldInfo::is_nested_validify,
+                quote!(::validify::Modify),
+            );
+            let validify_generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Validify,
+                &fields.0,
+                FieldInfo::is_nested_validify,
+                quote!(::validify::Validify),
+            );
+
+            let (impl_generics, ty_generics, where_clause) = modify_generics.split_for_impl();
+            let (validify_impl_generics, _, validify_where_clause) =
+                validify_generics.split_for_impl();
 
             quote!(
                 #validate_impl
 
                 impl #impl_generics ::validify::Modify for #ident #ty_generics #where_clause {
                     fn modify(&mut self) {
+                        let _ = <Self as ::validify::Modify>::try_modify(self);
+                    }
+
+                    fn try_modify(&mut self) -> Result<(), ::validify::ValidationErrors> {
+                        let mut errors = ::validify::ValidationErrors::new();
+
                         #(#modifiers)*
+
+                        #(#container_modifiers)*
+
+                        if !errors.is_empty() {
+                            Err(errors)
+                        } else {
+                            Ok(())
+                        }
+                    }
+
+                    fn modify_with_report(&mut self) -> ::validify::ModificationReport {
+                        // Errors of fallible modifiers are ignored like in `modify`
+                        let mut errors = ::validify::ValidationErrors::new();
+                        let mut report = ::validify::ModificationReport::new();
+
+                        #report_phase
+
+                        #(#container_report)*
+
+                        let _ = errors;
+                        report
+                    }
+
+                    fn modify_after_validate(&mut self) -> Result<(), ::validify::ValidationErrors> {
+                        let mut errors = ::validify::ValidationErrors::new();
+
+                        #after_validate_phase
+
+                        if !errors.is_empty() {
+                            Err(errors)
+                        } else {
+                            Ok(())
+                        }
                     }
                 }
 
-                impl #impl_generics ::validify::Validify for #ident #ty_generics #where_clause {
+                impl #validify_impl_generics ::validify::Validify for #ident #ty_generics #validify_where_clause {
                     fn validify(&mut self) -> Result<(), ::validify::ValidationErrors> {
                         let mut errors = ::validify::ValidationErrors::new();
 
-                        <Self as ::validify::Modify>::modify(self);
+                        #before_modify
 
-                        if let Err(errs) = <Self as ::validify::Validate>::validate(self) {
+                        if let Err(errs) = <Self as ::validify::Modify>::try_modify(self) {
                             errors.merge(errs);
                         }
 
+                        // Fields whose modifiers failed are not validated
+                        if let Err(errs) = <Self as ::validify::Validate>::validate(self) {
+                            errors.merge_skip_failed(errs);
+                        }
+
+                        #after_validate
+
+                        // Post validation modifiers only run on valid data
+                        if errors.is_empty() {
+                            if let Err(errs) = <Self as ::validify::Modify>::modify_after_validate(self) {
+                                errors.merge(errs);
+                            }
+                        }
+
                         if !errors.is_empty() {
                             Err(errors)
                         } else {
@@ -57,35 +181,126 @@ pub fn impl_validify(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
             )
         }
         syn::Data::Enum(ref data_enum) => {
-            let variants = Variants::collect(data_enum);
+            let mut variants = Variants::collect(data_enum);
+            variants.limit_recursion();
 
             let modifiers = variants.to_modify_tokens();
+            let after_validate_modifiers = variants.to_after_validate_tokens();
+            let report_modifiers = variants.to_report_tokens();
+            let report_phase = quote!(
+                match self {
+                    #(#report_modifiers)*
+                }
+            );
+            let after_validate_phase = quote!(
+                match self {
+                    #(#after_validate_modifiers)*
+                }
+            );
+
+            let hooks = ContainerHooks::collect(&input.attrs);
+            let container_modifiers = hooks.modifier_tokens();
+            let container_report = hooks.report_tokens();
+            let before_modify = hooks.before_modify_tokens();
+            let after_validate = hooks.after_validate_tokens();
 
             let validate_impl = impl_validate(input);
 
-            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            let modify_generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Modify,
+                variants.fields(),
+                FieldInfo::is_nested_validify,
+                quote!(::validify::Modify),
+            );
+            let validify_generics = with_bound(
+                &input.generics,
+                &input.attrs,
+                BoundTarget::Validify,
+                variants.fields(),
+                FieldInfo::is_nested_validify,
+                quote!(::validify::Validify),
+            );
+
+            let (impl_generics, ty_generics, where_clause) = modify_generics.split_for_impl();
+            let (validify_impl_generics, _, validify_where_clause) =
+                validify_generics.split_for_impl();
 
             quote!(
                 #validate_impl
 
                 impl #impl_generics ::validify::Modify for #ident #ty_generics #where_clause {
                     fn modify(&mut self) {
+                        let _ = <Self as ::validify::Modify>::try_modify(self);
+                    }
+
+                    fn try_modify(&mut self) -> Result<(), ::validify::ValidationErrors> {
+                        let mut errors = ::validify::ValidationErrors::new();
+
                         match self {
                             #(#modifiers)*
                         }
+
+                        #(#container_modifiers)*
+
+                        if !errors.is_empty() {
+                            Err(errors)
+                        } else {
+                            Ok(())
+                        }
+                    }
+
+                    fn modify_with_report(&mut self) -> ::validify::ModificationReport {
+                        // Errors of fallible modifiers are ignored like in `modify`
+                        let mut errors = ::validify::ValidationErrors::new();
+                        let mut report = ::validify::ModificationReport::new();
+
+                        #report_phase
+
+                        #(#container_report)*
+
+                        let _ = errors;
+                        report
+                    }
+
+                    fn modify_after_validate(&mut self) -> Result<(), ::validify::ValidationErrors> {
+                        let mut errors = ::validify::ValidationErrors::new();
+
+                        #after_validate_phase
+
+                        if !errors.is_empty() {
+                            Err(errors)
+                        } else {
+                            Ok(())
+                        }
                     }
                 }
 
-                impl #impl_generics ::validify::Validify for #ident #ty_generics #where_clause {
+                impl #validify_impl_generics ::validify::Validify for #ident #ty_generics #validify_where_clause {
                     fn validify(&mut self) -> Result<(), ::validify::ValidationErrors> {
                         let mut errors = ::validify::ValidationErrors::new();
 
-                        <Self as ::validify::Modify>::modify(self);
+                        #before_modify
 
-                        if let Err(errs) = <Self as ::validify::Validate>::validate(self) {
+                        if let Err(errs) = <Self as ::validify::Modify>::try_modify(self) {
                             errors.merge(errs);
                         }
 
+                        // Fields whose modifiers failed are not validated
+                        if let Err(errs) = <Self as ::validify::Validate>::validate(self) {
+                            errors.merge_skip_failed(errs);
+                        }
+
+                        #after_validate
+
+                        // Post validation modifiers only run on valid data
+                        if errors.is_empty() {
+                            if let Err(errs) = <Self as ::validify::Modify>::modify_after_validate(self) {
+                                errors.merge(errs);
+                            }
+                        }
+
                         if !errors.is_empty() {
                             Err(errors)
                         } else {
@@ -102,12 +317,115 @@ pub fn impl_validify(input: &syn::DeriveInput) -> proc_macro2::TokenStream {
     }
 }
 
-pub fn collect_modifiers(field: &syn::Field) -> Vec<Modifier> {
-    let mut modifiers = vec![];
+/// Container level modifiers and lifecycle hooks obtained from `#[modify(custom(fn))]`
+/// and `#[validify(before_modify = fn, after_validate = fn)]`.
+#[derive(Debug, Default)]
+struct ContainerHooks {
+    /// Functions taking `&mut Self`, ran after all field modifiers
+    modifiers: Vec<syn::Path>,
+
+    /// Function taking `&mut Self`, ran before any modifiers in `validify`
+    before_modify: Option<syn::Path>,
+
+    /// Function taking `&mut Self` and `&mut ValidationErrors`, ran after validation in `validify`
+    after_validate: Option<syn::Path>,
+}
+
+impl ContainerHooks {
+    fn collect(attrs: &[syn::Attribute]) -> Self {
+        let mut hooks = Self::default();
+
+        for attr in attrs {
+            if attr.path().is_ident(MODIFY) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident(CUSTOM_MODIFIER) {
+                        let content;
+                        parenthesized!(content in meta.input);
+                        hooks.modifiers.push(content.parse()?);
+                        return Ok(());
+                    }
+
+                    Err(meta.error("Unrecognized container modifier, accepted are: custom"))
+                })
+                .unwrap_or_else(|e| abort!(e.span(), e));
+            }
+
+            if attr.path().is_ident(VALIDIFY) {
+                attr.parse_nested_meta(|meta| {
+                    let hook = if meta.path.is_ident(BEFORE_MODIFY) {
+                        &mut hooks.before_modify
+                    } else if meta.path.is_ident(AFTER_VALIDATE) {
+                        &mut hooks.after_validate
+                    } else {
+                        return Err(meta.error(
+                            "Unrecognized validify hook, accepted are: before_modify, after_validate",
+                        ));
+                    };
+
+                    if hook.is_some() {
+                        return Err(meta.error("Hook already specified"));
+                    }
+
+                    *hook = Some(meta.value()?.parse()?);
+                    Ok(())
+                })
+                .unwrap_or_else(|e| abort!(e.span(), e));
+            }
+        }
+
+        hooks
+    }
+
+    fn modifier_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        self.modifiers
+            .iter()
+            .map(|function| quote!(#function(self);))
+            .collect()
+    }
+
+    fn report_tokens(&self) -> Vec<proc_macro2::TokenStream> {
+        self.modifiers
+            .iter()
+            .map(|function| {
+                quote!(
+                    {
+                        use ::validify::__capture::{CaptureFallback as _, CaptureSerialize as _};
+                        let __before = (&::validify::__capture::Capture(&*self)).capture();
+                        #function(self);
+                        let __after = (&::validify::__capture::Capture(&*self)).capture();
+                        report.record("", "custom", __before, __after, false);
+                    }
+                )
+            })
+            .collect()
+    }
+
+    fn before_modify_tokens(&self) -> proc_macro2::TokenStream {
+        self.before_modify
+            .as_ref()
+            .map(|function| quote!(#function(self);))
+            .unwrap_or_default()
+    }
+
+    fn after_validate_tokens(&self) -> proc_macro2::TokenStream {
+        self.after_validate
+            .as_ref()
+            .map(|function| quote!(#function(self, &mut errors);))
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the field modifiers as the first element and the modifiers running only after successful
+/// validation, i.e. the ones in `#[modify(after_validate, ...)]` attributes, as the second.
+pub fn collect_modifiers(field: &syn::Field) -> (Vec<Modifier>, Vec<Modifier>) {
+    let mut field_modifiers = vec![];
+    let mut after_validate_modifiers = vec![];
+
     for attr in &field.attrs {
-        // Nest validified fields
+        // Nest validified fields, in both phases
         if attr.path().is_ident(VALIDIFY) {
-            modifiers.push(Modifier::Nested);
+            field_modifiers.push(Modifier::Nested);
+            after_validate_modifiers.push(Modifier::Nested);
             continue;
         }
 
@@ -115,38 +433,619 @@ pub fn collect_modifiers(field: &syn::Field) -> Vec<Modifier> {
             continue;
         }
 
-        attr.parse_nested_meta(|meta| {
-            if meta.path.is_ident(CUSTOM_MODIFIER) {
-                let content;
-                parenthesized!(content in meta.input);
-                let path: syn::Path = content.parse()?;
-                modifiers.push(Modifier::Custom { function: path });
-                return Ok(());
-            }
+        let mut modifiers = vec![];
+        let mut after_validate = false;
 
-            if meta.path.is_ident(TRIM_MODIFIER) {
-                modifiers.push(Modifier::Trim);
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(AFTER_VALIDATE) {
+                after_validate = true;
                 return Ok(());
             }
 
-            if meta.path.is_ident(LOWERCASE_MODIFIER) {
-                modifiers.push(Modifier::Lowercase);
+            if meta.path.is_ident(KEYS_MODIFIER) {
+                let mut key_modifiers = vec![];
+                meta.parse_nested_meta(|meta| parse_single_modifier(meta, &mut key_modifiers))?;
+                for modifier in key_modifiers {
+                    modifiers.push(Modifier::Keys(Box::new(element_modifier(&meta, modifier)?)));
+                }
                 return Ok(());
             }
 
-            if meta.path.is_ident(UPPERCASE_MODIFIER) {
-                modifiers.push(Modifier::Uppercase);
+            if meta.path.is_ident(VALUES_MODIFIER) {
+                let mut value_modifiers = vec![];
+                meta.parse_nested_meta(|meta| parse_single_modifier(meta, &mut value_modifiers))?;
+                for modifier in value_modifiers {
+                    modifiers.push(Modifier::Values(Box::new(element_modifier(
+                        &meta, modifier,
+                    )?)));
+                }
                 return Ok(());
             }
 
-            if meta.path.is_ident(CAPITALIZE_MODIFIER) {
-                modifiers.push(Modifier::Capitalize);
+            if meta.path.is_ident(ITER_MODIFIER) {
+                let mut element_modifiers = vec![];
+                meta.parse_nested_meta(|meta| parse_single_modifier(meta, &mut element_modifiers))?;
+                for modifier in element_modifiers {
+                    modifiers.push(Modifier::Iter(Box::new(iter_modifier(&meta, modifier)?)));
+                }
                 return Ok(());
             }
 
-            Err(meta.error("Unrecognized modify parameter"))
+            parse_single_modifier(meta, &mut modifiers)
         })
         .unwrap_or_else(|e| abort!(e.span(), e));
+
+        for modifier in modifiers.iter_mut() {
+            check_clamp_bounds(modifier, &field.ty);
+        }
+
+        if after_validate {
+            after_validate_modifiers.extend(modifiers);
+        } else {
+            field_modifiers.extend(modifiers);
+        }
+    }
+
+    (field_modifiers, after_validate_modifiers)
+}
+
+fn parse_single_modifier(
+    meta: ParseNestedMeta<'_>,
+    modifiers: &mut Vec<Modifier>,
+) -> Result<(), syn::Error> {
+    if meta.path.is_ident(CUSTOM_MODIFIER) {
+        let content;
+        parenthesized!(content in meta.input);
+        let path: syn::Path = content.parse()?;
+        modifiers.push(Modifier::Custom { function: path });
+        return Ok(());
+    }
+
+    if meta.path.is_ident(TRY_CUSTOM_MODIFIER) {
+        let content;
+        parenthesized!(content in meta.input);
+        let path: syn::Path = content.parse()?;
+        modifiers.push(Modifier::TryCustom { function: path });
+        return Ok(());
+    }
+
+    if meta.path.is_ident(WITH_MODIFIER) {
+        let modifier = meta.value()?.parse::<syn::Expr>()?;
+        modifiers.push(Modifier::With {
+            modifier: Box::new(modifier),
+        });
+        return Ok(());
+    }
+
+    if meta.path.is_ident(TRIM_MODIFIER) {
+        modifiers.push(Modifier::Trim);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(LOWERCASE_MODIFIER) {
+        modifiers.push(Modifier::Lowercase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(UPPERCASE_MODIFIER) {
+        modifiers.push(Modifier::Uppercase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(CAPITALIZE_MODIFIER) {
+        modifiers.push(Modifier::Capitalize);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SNAKE_CASE_MODIFIER) {
+        modifiers.push(Modifier::SnakeCase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(KEBAB_CASE_MODIFIER) {
+        modifiers.push(Modifier::KebabCase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(CAMEL_CASE_MODIFIER) {
+        modifiers.push(Modifier::CamelCase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(PASCAL_CASE_MODIFIER) {
+        modifiers.push(Modifier::PascalCase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SCREAMING_SNAKE_CASE_MODIFIER) {
+        modifiers.push(Modifier::ScreamingSnakeCase);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SLUGIFY_MODIFIER) {
+        modifiers.push(parse_slugify(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SANITIZE_HTML_MODIFIER) {
+        require_html_feature(&meta)?;
+        modifiers.push(parse_sanitize_html(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(STRIP_HTML_MODIFIER) {
+        require_html_feature(&meta)?;
+        modifiers.push(Modifier::StripHtml);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(EMAIL_CANONICAL_MODIFIER) {
+        modifiers.push(Modifier::EmailCanonical);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(PHONE_E164_MODIFIER) {
+        modifiers.push(parse_phone_e164(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(URL_NORMALIZE_MODIFIER) {
+        modifiers.push(Modifier::UrlNormalize);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(CLAMP_MODIFIER) {
+        modifiers.push(parse_clamp(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(ROUND_MODIFIER) {
+        modifiers.push(parse_round(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(FLOOR_MODIFIER) {
+        modifiers.push(Modifier::Floor);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(CEIL_MODIFIER) {
+        modifiers.push(Modifier::Ceil);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(ABS_MODIFIER) {
+        modifiers.push(Modifier::Abs);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(TRUNCATE_MODIFIER) {
+        modifiers.push(parse_truncate(&meta)?);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(TO_UTC_MODIFIER) {
+        modifiers.push(Modifier::ToUtc);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SORT_MODIFIER) {
+        modifiers.push(Modifier::Sort);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(SORT_BY_KEY_MODIFIER) {
+        let function = meta.value()?.parse::<syn::Path>().map_err(|_| {
+            meta.error("sort_by_key must be a path to a function, i.e. `sort_by_key = key_fn`")
+        })?;
+        modifiers.push(Modifier::SortByKey { function });
+        return Ok(());
+    }
+
+    if meta.path.is_ident(DEDUP_MODIFIER) {
+        modifiers.push(Modifier::Dedup);
+        return Ok(());
+    }
+
+    if meta.path.is_ident(RETAIN_NON_EMPTY_MODIFIER) {
+        modifiers.push(Modifier::RetainNonEmpty);
+        return Ok(());
+    }
+
+    Err(meta.error("Unrecognized modify parameter"))
+}
+
+/// Checks whether the modifier can be applied to a single map key or value.
+fn element_modifier(meta: &ParseNestedMeta, modifier: Modifier) -> Result<Modifier, syn::Error> {
+    if !modifier.is_element_wise()
+        || matches!(
+            modifier,
+            Modifier::Keys(_) | Modifier::Values(_) | Modifier::Iter(_)
+        )
+    {
+        return Err(meta.error(format!(
+            "`{}` cannot be used on map keys or values",
+            modifier.name()
+        )));
+    }
+    Ok(modifier)
+}
+
+/// Checks whether the modifier can be applied to each element of a list with `iter`. Element-wise
+/// modifiers are already applied to each element, so only the ones modifying whole values are accepted,
+/// e.g. `iter(truncate(max = 10))` for a `Vec<String>`.
+fn iter_modifier(meta: &ParseNestedMeta, modifier: Modifier) -> Result<Modifier, syn::Error> {
+    if !matches!(
+        modifier,
+        Modifier::Sort
+            | Modifier::SortByKey { .. }
+            | Modifier::Dedup
+            | Modifier::RetainNonEmpty
+            | Modifier::Truncate { .. }
+    ) {
+        return Err(meta.error(format!(
+            "`{}` cannot be used in `iter`, accepted are: sort, sort_by_key, dedup, retain_non_empty, truncate(max = N)",
+            modifier.name()
+        )));
+    }
+    Ok(modifier)
+}
+
+/// Parses `slugify` or `slugify(separator = "_", max_len = 64)`.
+fn parse_slugify(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    let mut separator = String::from("-");
+    let mut max_len = None;
+
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Modifier::Slugify { separator, max_len });
+    }
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("separator") {
+            let content = meta.value()?;
+            match content.parse::<syn::LitStr>() {
+                Ok(lit) => separator = lit.value(),
+                Err(_) => return Err(meta.error("separator must be a string literal")),
+            }
+            return Ok(());
+        }
+
+        if meta.path.is_ident("max_len") {
+            let content = meta.value()?;
+            match content.parse::<syn::LitInt>() {
+                Ok(lit) => max_len = Some(lit.base10_parse::<usize>()?),
+                Err(_) => return Err(meta.error("max_len must be an int literal")),
+            }
+            return Ok(());
+        }
+
+        Err(meta.error("Unrecognized slugify parameter, accepted are: separator, max_len"))
+    })?;
+
+    Ok(Modifier::Slugify { separator, max_len })
+}
+
+/// The HTML modifiers are only exported by validify when its `html` feature is enabled, which enables
+/// the same feature of this crate.
+fn require_html_feature(meta: &ParseNestedMeta) -> Result<(), syn::Error> {
+    if cfg!(feature = "html") {
+        return Ok(());
+    }
+    Err(meta.error(
+        "the `sanitize_html` and `strip_html` modifiers require the `html` feature of validify",
+    ))
+}
+
+/// Parses `sanitize_html` or `sanitize_html(tags("b", "i"), attributes("href"))`.
+fn parse_sanitize_html(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    let mut tags = None;
+    let mut attributes = None;
+
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Modifier::SanitizeHtml { tags, attributes });
+    }
+
+    meta.parse_nested_meta(|meta| {
+        let list = if meta.path.is_ident("tags") {
+            &mut tags
+        } else if meta.path.is_ident("attributes") {
+            &mut attributes
+        } else {
+            return Err(
+                meta.error("Unrecognized sanitize_html parameter, accepted are: tags, attributes")
+            );
+        };
+
+        let content;
+        parenthesized!(content in meta.input);
+        let lits =
+            content.parse_terminated(|input| input.parse::<syn::LitStr>(), syn::Token![,])?;
+        *list = Some(lits.into_iter().map(|lit| lit.value()).collect());
+
+        Ok(())
+    })?;
+
+    Ok(Modifier::SanitizeHtml { tags, attributes })
+}
+
+/// Parses `phone_e164` or `phone_e164(default_region = "US")`.
+fn parse_phone_e164(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    let mut default_region = None;
+
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Modifier::PhoneE164 { default_region });
     }
-    modifiers
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("default_region") {
+            let content = meta.value()?;
+            let Ok(lit) = content.parse::<syn::LitStr>() else {
+                return Err(meta.error("default_region must be a string literal"));
+            };
+            // Unknown regions would leave every national number unchanged
+            if lit
+                .value()
+                .to_uppercase()
+                .parse::<phonenumber::country::Id>()
+                .is_err()
+            {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "default_region must be an ISO 3166-1 alpha-2 region code, i.e. \"US\"",
+                ));
+            }
+            default_region = Some(lit.value());
+            return Ok(());
+        }
+
+        Err(meta.error("Unrecognized phone_e164 parameter, accepted are: default_region"))
+    })?;
+
+    Ok(Modifier::PhoneE164 { default_region })
+}
+
+/// Parses `clamp(min = expr, max = expr)`. At least one of the bounds must be present.
+fn parse_clamp(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    let mut min = None;
+    let mut max = None;
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("min") {
+            min = Some(Box::new(meta.value()?.parse::<syn::Expr>()?));
+            return Ok(());
+        }
+
+        if meta.path.is_ident("max") {
+            max = Some(Box::new(meta.value()?.parse::<syn::Expr>()?));
+            return Ok(());
+        }
+
+        Err(meta.error("Unrecognized clamp parameter, accepted are: min, max"))
+    })?;
+
+    if min.is_none() && max.is_none() {
+        return Err(meta.error("clamp must have at least one of: min, max"));
+    }
+
+    Ok(Modifier::Clamp { min, max })
+}
+
+/// Checks the literal bounds of `clamp` modifiers against the type they are applied to. Float bounds are
+/// rejected on integers, integer literals are turned into float literals on floats and literal bounds
+/// must not be reversed. Any other expression is passed as is and must be of the field's type.
+fn check_clamp_bounds(modifier: &mut Modifier, ty: &syn::Type) {
+    let (modifier, ty) = match modifier {
+        Modifier::Keys(modifier) => (&mut **modifier, try_extract_map_key(unwrap_type(ty))),
+        Modifier::Values(modifier) => (&mut **modifier, try_extract_map_value(unwrap_type(ty))),
+        modifier => (modifier, Some(ty)),
+    };
+
+    let Modifier::Clamp { min, max } = modifier else {
+        return;
+    };
+
+    let kind = ty.map(numeric_kind).unwrap_or(NumericKind::Other);
+
+    for bound in [&mut *min, &mut *max].into_iter().flatten() {
+        match (kind, literal_bound(bound)) {
+            (NumericKind::Integer, Some((_, true))) => abort!(
+                bound.span(),
+                "clamp bound must be an integer when applied to integers"
+            ),
+            (NumericKind::Float, Some((_, false))) => into_float_literal(bound),
+            _ => {}
+        }
+    }
+
+    if let (Some(min), Some(max)) = (min, max) {
+        if let (Some((lower, _)), Some((upper, _))) = (literal_bound(min), literal_bound(max)) {
+            if lower > upper {
+                abort!(max.span(), "clamp max must not be less than min");
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum NumericKind {
+    Integer,
+    Float,
+    Other,
+}
+
+/// Returns the kind of the primitive modified by element-wise modifiers, i.e. of the innermost
+/// element of options, pointers and lists.
+fn numeric_kind(ty: &syn::Type) -> NumericKind {
+    let mut ty = unwrap_type(ty);
+    while let Some(element) = try_extract_list_element(ty) {
+        ty = unwrap_type(element);
+    }
+
+    let syn::Type::Path(p) = ty else {
+        return NumericKind::Other;
+    };
+
+    let Some(ident) = p.path.get_ident() else {
+        return NumericKind::Other;
+    };
+
+    match ident.to_string().as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => NumericKind::Integer,
+        "f32" | "f64" => NumericKind::Float,
+        _ => NumericKind::Other,
+    }
+}
+
+/// Returns the value of a (negated) numeric literal and whether it is a float literal.
+fn literal_bound(expr: &syn::Expr) -> Option<(f64, bool)> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => Some((int.base10_parse().ok()?, false)),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Float(float),
+            ..
+        }) => Some((float.base10_parse().ok()?, true)),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_bound(expr).map(|(value, float)| (-value, float)),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => literal_bound(expr),
+        _ => None,
+    }
+}
+
+/// Replaces unsuffixed integer literals in the expression with float literals, i.e. `-1` with `-1.0`.
+fn into_float_literal(expr: &mut syn::Expr) {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
+            if let syn::Lit::Int(int) = lit {
+                if int.suffix().is_empty() {
+                    let float = format!("{}.0", int.base10_digits());
+                    *lit = syn::Lit::Float(syn::LitFloat::new(&float, int.span()));
+                }
+            }
+        }
+        syn::Expr::Unary(syn::ExprUnary { expr, .. })
+        | syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => into_float_literal(expr),
+        _ => {}
+    }
+}
+
+/// Parses `round`, `round(2)` or `round(decimals = 2)`.
+fn parse_round(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(Modifier::Round { decimals: 0 });
+    }
+
+    let content;
+    parenthesized!(content in meta.input);
+
+    if content.peek(syn::LitInt) {
+        let decimals = content.parse::<syn::LitInt>()?.base10_parse()?;
+        return Ok(Modifier::Round { decimals });
+    }
+
+    let name_value = content.parse::<syn::MetaNameValue>()?;
+
+    if !name_value.path.is_ident("decimals") {
+        return Err(meta.error("Unrecognized round parameter, accepted are: decimals"));
+    }
+
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Int(ref decimals),
+        ..
+    }) = name_value.value
+    else {
+        return Err(meta.error("decimals must be an int literal"));
+    };
+
+    Ok(Modifier::Round {
+        decimals: decimals.base10_parse()?,
+    })
+}
+
+/// Parses `truncate(seconds | minutes | hours | days)` for timestamps,
+/// `truncate(max = N)` for collections or
+/// `truncate(max = N, unit = chars | bytes | graphemes, ellipsis = "...")` for strings.
+fn parse_truncate(meta: &ParseNestedMeta) -> Result<Modifier, syn::Error> {
+    const USAGE: &str =
+        "truncate must specify a time unit (seconds, minutes, hours, days) or `max = N`";
+
+    if !meta.input.peek(syn::token::Paren) {
+        return Err(meta.error(USAGE));
+    }
+
+    let fork = meta.input.fork();
+    let content;
+    parenthesized!(content in fork);
+
+    // Time units are always single idents
+    if content.peek(syn::Ident) && content.peek2(syn::parse::End) {
+        let content;
+        parenthesized!(content in meta.input);
+        let unit = content.parse::<syn::Ident>()?;
+
+        let unit = match unit.to_string().as_str() {
+            "seconds" => TimeUnit::Seconds,
+            "minutes" => TimeUnit::Minutes,
+            "hours" => TimeUnit::Hours,
+            "days" => TimeUnit::Days,
+            _ => return Err(syn::Error::new(unit.span(), USAGE)),
+        };
+
+        return Ok(Modifier::TruncateTime { unit });
+    }
+
+    let mut max = None;
+    let mut unit = None;
+    let mut ellipsis = None;
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("max") {
+            max = Some(Box::new(meta.value()?.parse::<syn::Expr>()?));
+            return Ok(());
+        }
+
+        if meta.path.is_ident("unit") {
+            let id = meta.value()?.parse::<syn::Ident>()?;
+            unit = match id.to_string().as_str() {
+                "chars" => Some(TextUnit::Chars),
+                "bytes" => Some(TextUnit::Bytes),
+                "graphemes" => Some(TextUnit::Graphemes),
+                _ => {
+                    return Err(syn::Error::new(
+                        id.span(),
+                        "unit must be one of: chars, bytes, graphemes",
+                    ))
+                }
+            };
+            return Ok(());
+        }
+
+        if meta.path.is_ident("ellipsis") {
+            match meta.value()?.parse::<syn::LitStr>() {
+                Ok(lit) => ellipsis = Some(lit.value()),
+                Err(_) => return Err(meta.error("ellipsis must be a string literal")),
+            }
+            return Ok(());
+        }
+
+        Err(meta.error("Unrecognized truncate parameter, accepted are: max, unit, ellipsis"))
+    })?;
+
+    let Some(max) = max else {
+        return Err(meta.error(USAGE));
+    };
+
+    Ok(Modifier::Truncate {
+        max,
+        unit,
+        ellipsis,
+    })
 }
@@ -1,5 +1,7 @@
-use crate::fields::FieldInfo;
-use quote::quote;
+use crate::fields::{is_set, modify_element_tokens, try_extract_map_value, unwrap_type, FieldInfo};
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Modifier {
@@ -7,11 +9,149 @@ pub enum Modifier {
     Uppercase,
     Lowercase,
     Capitalize,
-    Custom { function: syn::Path },
+    SnakeCase,
+    KebabCase,
+    CamelCase,
+    PascalCase,
+    ScreamingSnakeCase,
+    Slugify {
+        separator: String,
+        max_len: Option<usize>,
+    },
+    SanitizeHtml {
+        tags: Option<Vec<String>>,
+        attributes: Option<Vec<String>>,
+    },
+    StripHtml,
+    EmailCanonical,
+    PhoneE164 {
+        default_region: Option<String>,
+    },
+    UrlNormalize,
+    Clamp {
+        min: Option<Box<syn::Expr>>,
+        max: Option<Box<syn::Expr>>,
+    },
+    Round {
+        decimals: u32,
+    },
+    Floor,
+    Ceil,
+    Abs,
+    TruncateTime {
+        unit: TimeUnit,
+    },
+    ToUtc,
+    Sort,
+    SortByKey {
+        function: syn::Path,
+    },
+    Dedup,
+    RetainNonEmpty,
+    Truncate {
+        max: Box<syn::Expr>,
+        unit: Option<TextUnit>,
+        ellipsis: Option<String>,
+    },
+    Custom {
+        function: syn::Path,
+    },
+    TryCustom {
+        function: syn::Path,
+    },
+    /// Runs the modifiers of a rule set, implied by `#[validate(rules(...))]`.
+    Rules {
+        path: syn::Path,
+    },
+    /// Applies an expression implementing `validify::Modifier`.
+    With {
+        modifier: Box<syn::Expr>,
+    },
+    /// Applies the element modifier to each key of a map.
+    Keys(Box<Modifier>),
+    /// Applies the element modifier to each value of a map.
+    Values(Box<Modifier>),
+    /// Applies the collection or string modifier to each element of a list.
+    Iter(Box<Modifier>),
     Nested,
 }
 
+/// The unit used by the temporal `truncate` modifier.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TimeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+/// The unit used by the string `truncate` modifier.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextUnit {
+    Chars,
+    Bytes,
+    Graphemes,
+}
+
 impl Modifier {
+    /// The name of the modifier as written in the attribute, used in modification reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Modifier::Trim => "trim",
+            Modifier::Uppercase => "uppercase",
+            Modifier::Lowercase => "lowercase",
+            Modifier::Capitalize => "capitalize",
+            Modifier::SnakeCase => "snake_case",
+            Modifier::KebabCase => "kebab_case",
+            Modifier::CamelCase => "camel_case",
+            Modifier::PascalCase => "pascal_case",
+            Modifier::ScreamingSnakeCase => "screaming_snake_case",
+            Modifier::Slugify { .. } => "slugify",
+            Modifier::SanitizeHtml { .. } => "sanitize_html",
+            Modifier::StripHtml => "strip_html",
+            Modifier::EmailCanonical => "email_canonical",
+            Modifier::PhoneE164 { .. } => "phone_e164",
+            Modifier::UrlNormalize => "url_normalize",
+            Modifier::Clamp { .. } => "clamp",
+            Modifier::Round { .. } => "round",
+            Modifier::Floor => "floor",
+            Modifier::Ceil => "ceil",
+            Modifier::Abs => "abs",
+            Modifier::TruncateTime { .. } | Modifier::Truncate { .. } => "truncate",
+            Modifier::ToUtc => "to_utc",
+            Modifier::Sort => "sort",
+            Modifier::SortByKey { .. } => "sort_by_key",
+            Modifier::Dedup => "dedup",
+            Modifier::RetainNonEmpty => "retain_non_empty",
+            Modifier::Custom { .. } => "custom",
+            Modifier::TryCustom { .. } => "try_custom",
+            Modifier::With { .. } => "with",
+            Modifier::Rules { .. } => "rules",
+            Modifier::Keys(modifier) | Modifier::Values(modifier) | Modifier::Iter(modifier) => {
+                modifier.name()
+            }
+            Modifier::Nested => "validify",
+        }
+    }
+
+    /// Whether the modifier is applied to each element when used on a collection.
+    pub fn is_element_wise(&self) -> bool {
+        !matches!(
+            self,
+            Modifier::Sort
+                | Modifier::SortByKey { .. }
+                | Modifier::Dedup
+                | Modifier::RetainNonEmpty
+                | Modifier::Truncate { .. }
+                | Modifier::Custom { .. }
+                | Modifier::TryCustom { .. }
+                | Modifier::With { .. }
+                | Modifier::Rules { .. }
+                | Modifier::Keys(_)
+                | Modifier::Nested
+        )
+    }
+
     /// Returns direct modification tokens as the first element and any nested validify tokens as the second element.
     /// Necessary because we need both in case a nested validify occurs. In that case, the first element will have the
     /// necessary modification tokens for nested elements in the `Modify` impl while the second will have the tokens
@@ -20,87 +160,454 @@ impl Modifier {
         let param = field_info.modifier_param_tokens();
 
         match self {
-            Modifier::Trim => {
-                let tokens = if field_info.ident_override.is_some() || field_info.is_option() {
-                    quote!(
-                        *#param = #param.trim().to_string();
-                    )
+            Modifier::Trim
+            | Modifier::Uppercase
+            | Modifier::Lowercase
+            | Modifier::Capitalize
+            | Modifier::SnakeCase
+            | Modifier::KebabCase
+            | Modifier::CamelCase
+            | Modifier::PascalCase
+            | Modifier::ScreamingSnakeCase
+            | Modifier::Slugify { .. }
+            | Modifier::SanitizeHtml { .. }
+            | Modifier::StripHtml
+            | Modifier::EmailCanonical
+            | Modifier::PhoneE164 { .. }
+            | Modifier::UrlNormalize
+            | Modifier::Clamp { .. }
+            | Modifier::Round { .. }
+            | Modifier::Floor
+            | Modifier::Ceil
+            | Modifier::Abs
+            | Modifier::TruncateTime { .. }
+            | Modifier::ToUtc => field_info.wrap_modifier_if_collection(self.element_tokens()),
+            Modifier::Sort
+            | Modifier::SortByKey { .. }
+            | Modifier::Dedup
+            | Modifier::RetainNonEmpty
+            | Modifier::Truncate { .. } => {
+                let modified = if field_info.ident_override.is_some() || field_info.is_option() {
+                    self.collection_tokens(quote!(#param))
                 } else {
-                    quote!(
-                        #param = #param.trim().to_string();
-                    )
+                    self.collection_tokens(quote!(&mut #param))
                 };
-                field_info.wrap_modifier_if_option(
-                    field_info.wrap_modifier_if_collection(param, tokens, self),
-                )
+                field_info.wrap_modifier_if_option(quote!(#modified;))
             }
-            Modifier::Uppercase => {
+            Modifier::Custom { function } => {
                 let tokens = if field_info.ident_override.is_some() || field_info.is_option() {
                     quote!(
-                        *#param = #param.to_uppercase();
+                        #function(#param);
                     )
                 } else {
                     quote!(
-                        #param = #param.to_uppercase();
+                        #function(&mut #param);
                     )
                 };
-                field_info.wrap_modifier_if_option(
-                    field_info.wrap_modifier_if_collection(param, tokens, self),
-                )
+                field_info.wrap_modifier_if_option(tokens)
             }
-            Modifier::Lowercase => {
-                let tokens = if field_info.ident_override.is_some() || field_info.is_option() {
-                    quote!(
-                        *#param = #param.to_lowercase();
-                    )
+            Modifier::With { modifier } => {
+                let param = if field_info.ident_override.is_some() || field_info.is_option() {
+                    quote!(#param)
                 } else {
-                    quote!(
-                        #param = #param.to_lowercase();
-                    )
+                    quote!(&mut #param)
                 };
-                field_info.wrap_modifier_if_option(
-                    field_info.wrap_modifier_if_collection(param, tokens, self),
-                )
+                field_info.wrap_modifier_if_option(quote!(
+                    ::validify::Modifier::modify(&(#modifier), #param);
+                ))
             }
-            Modifier::Capitalize => {
-                let tokens = if field_info.ident_override.is_some() || field_info.is_option() {
-                    quote!(
-                      *#param = ::std::format!("{}{}", &#param[0..1].to_uppercase(), &#param[1..]);
-                    )
+            Modifier::Rules { path } => {
+                let field_name = field_info.name();
+                let param = if field_info.ident_override.is_some() || field_info.is_option() {
+                    quote!(#param)
                 } else {
-                    quote!(
-                      #param = ::std::format!("{}{}", &#param[0..1].to_uppercase(), &#param[1..]);
-                    )
+                    quote!(&mut #param)
                 };
-                field_info.wrap_modifier_if_option(
-                    field_info.wrap_modifier_if_collection(param, tokens, self),
-                )
+                // Rule set errors are located at the value
+                field_info.wrap_modifier_if_option(quote!(
+                    if let Err(mut errs) = #path::modify(#param) {
+                        for err in errs.errors_mut() {
+                            if err.field_name().is_none() {
+                                err.set_field(#field_name);
+                            }
+                            err.set_location(#field_name);
+                        }
+                        errors.merge(errs);
+                    }
+                ))
             }
-            Modifier::Custom { function } => {
-                let tokens = if field_info.ident_override.is_some() || field_info.is_option() {
+            Modifier::TryCustom { function } => {
+                let field_name = field_info.name();
+                let param = if field_info.ident_override.is_some() || field_info.is_option() {
+                    quote!(#param)
+                } else {
+                    quote!(&mut #param)
+                };
+                let tokens = quote!(
+                    if let Err(mut err) = #function(#param) {
+                        let f_name = err.field_name().map(|s| s.to_string());
+                        if let Some(field_name) = f_name {
+                            err.set_location(field_name);
+                        } else {
+                            err.set_field(#field_name);
+                            err.set_location(#field_name);
+                        }
+                        errors.add(err);
+                    }
+                );
+                field_info.wrap_modifier_if_option(tokens)
+            }
+            Modifier::Keys(modifier) => {
+                let map = if field_info.ident_override.is_some() || field_info.is_option() {
+                    quote!(&mut *#param)
+                } else {
+                    quote!(&mut #param)
+                };
+                let field_name = field_info.name();
+                let modified = if modifier.is_rule_set() {
+                    let tokens = modifier.rule_set_element_tokens(&field_name, &[quote!(__k)]);
                     quote!(
-                        #function(#param);
+                        let __k = ::validify::escape_location(&__key);
+                        let el = &mut __key;
+                        #tokens
                     )
                 } else {
+                    let modified = modifier.element_tokens();
                     quote!(
-                        #function(&mut #param);
+                        let el = &mut __key;
+                        *el = #modified;
                     )
                 };
-                field_info.wrap_modifier_if_option(tokens)
+                // Keys cannot be modified in place so the map is rebuilt. Keys which end up equal
+                // would overwrite each other, so only the first one is kept and the rest are errors.
+                field_info.wrap_modifier_if_option(quote!(
+                    let __map = #map;
+                    for (mut __key, __value) in ::std::mem::take(__map) {
+                        {
+                            #modified
+                        }
+                        if __map.contains_key(&__key) {
+                            let mut err = ::validify::ValidationError::new_field_named(#field_name, "key_collision");
+                            err.set_location(::validify::escape_location(&__key));
+                            err.set_location(#field_name);
+                            errors.add(err);
+                            continue;
+                        }
+                        __map.insert(__key, __value);
+                    }
+                ))
             }
-            Modifier::Nested => {
-                let modifications = if field_info.is_list() {
-                    quote!(
-                        for el in #param.iter_mut() {
-                            el.modify();
+            Modifier::Values(modifier) => {
+                let value_ty = try_extract_map_value(unwrap_type(&field_info.field.ty))
+                    .unwrap_or_else(|| {
+                        abort!(
+                            field_info.field.span(),
+                            "`values` can only be used on maps."
+                        )
+                    });
+                if modifier.is_rule_set() {
+                    let tokens =
+                        modifier.rule_set_element_tokens(&field_info.name(), &[quote!(__k)]);
+                    return field_info.wrap_modifier_if_option(quote!(
+                        for (__k, el) in #param.iter_mut() {
+                            let __k = ::validify::escape_location(__k);
+                            #tokens
                         }
-                    )
-                } else {
-                    quote!(#param.modify();)
+                    ));
+                }
+                let tokens = modify_element_tokens(value_ty, &modifier.element_tokens());
+                field_info.wrap_modifier_if_option(quote!(
+                    for el in #param.values_mut() {
+                        #tokens
+                    }
+                ))
+            }
+            Modifier::Iter(modifier) => {
+                if !field_info.is_list() {
+                    abort!(field_info.field.span(), "`iter` can only be used on lists.")
+                }
+                if modifier.is_rule_set() {
+                    if is_set(unwrap_type(&field_info.field.ty)) {
+                        abort!(
+                            field_info.field.span(),
+                            "Elements of sets cannot be modified by rule sets, use `Validate` instead."
+                        )
+                    }
+                    let el = if field_info.ident_override.is_some() || field_info.is_option() {
+                        quote!(#param)
+                    } else {
+                        quote!(&mut #param)
+                    };
+                    let tokens = self.rule_set_element_tokens(&field_info.name(), &[]);
+                    return field_info.wrap_modifier_if_option(quote!(
+                        {
+                            let el = #el;
+                            #tokens
+                        }
+                    ));
+                }
+                let modified = modifier.collection_tokens(quote!(el));
+                field_info.wrap_modifier_if_option(quote!(
+                    for el in #param.iter_mut() {
+                        #modified;
+                    }
+                ))
+            }
+            Modifier::Nested => Self::nested_tokens(field_info, quote!(try_modify)),
+        }
+    }
+
+    /// Whether the modifier runs a rule set, either on the value or on its elements.
+    fn is_rule_set(&self) -> bool {
+        match self {
+            Modifier::Rules { .. } => true,
+            Modifier::Iter(modifier) => modifier.is_rule_set(),
+            _ => false,
+        }
+    }
+
+    /// Returns the tokens running the modifiers of a rule set on the value bound to `el`, or on each
+    /// of its elements for `iter`. Errors are located at the field followed by the `segments` of the value.
+    fn rule_set_element_tokens(
+        &self,
+        field_name: &str,
+        segments: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::Rules { path } => {
+                let segments = segments.iter().rev();
+                quote!(
+                    if let Err(mut errs) = #path::modify(el) {
+                        for err in errs.errors_mut() {
+                            if err.field_name().is_none() {
+                                err.set_field(#field_name);
+                            }
+                            #(err.set_location(&#segments);)*
+                            err.set_location(#field_name);
+                        }
+                        errors.merge(errs);
+                    }
+                )
+            }
+            Modifier::Iter(modifier) => {
+                let index = format_ident!("__i{}", segments.len());
+                let segments = [segments, &[quote!(#index)]].concat();
+                let tokens = modifier.rule_set_element_tokens(field_name, &segments);
+                quote!(
+                    for (#index, el) in el.iter_mut().enumerate() {
+                        #tokens
+                    }
+                )
+            }
+            _ => unreachable!("not a rule set modifier"),
+        }
+    }
+
+    /// Returns the expression modifying a single element of a collection, bound to `el`.
+    pub fn element_tokens(&self) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::Trim => quote!(el.trim().to_string()),
+            Modifier::Uppercase => quote!(el.to_uppercase()),
+            Modifier::Lowercase => quote!(el.to_lowercase()),
+            Modifier::Capitalize => {
+                quote!(::std::format!("{}{}", &el[0..1].to_uppercase(), &el[1..]))
+            }
+            Modifier::SnakeCase
+            | Modifier::KebabCase
+            | Modifier::CamelCase
+            | Modifier::PascalCase
+            | Modifier::ScreamingSnakeCase
+            | Modifier::Slugify { .. }
+            | Modifier::SanitizeHtml { .. }
+            | Modifier::StripHtml
+            | Modifier::EmailCanonical
+            | Modifier::PhoneE164 { .. }
+            | Modifier::UrlNormalize => self.string_tokens(quote!(el)),
+            Modifier::Clamp { .. }
+            | Modifier::Round { .. }
+            | Modifier::Floor
+            | Modifier::Ceil
+            | Modifier::Abs
+            | Modifier::TruncateTime { .. }
+            | Modifier::ToUtc => self.value_tokens(quote!(*el)),
+            _ => unreachable!("not an element modifier"),
+        }
+    }
+
+    /// Same as [Modifier::to_validify_tokens], except nested fields run their own modifiers
+    /// of the after validation phase.
+    pub fn to_after_validate_tokens(&self, field_info: &FieldInfo) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::Nested => Self::nested_tokens(field_info, quote!(modify_after_validate)),
+            _ => self.to_validify_tokens(field_info),
+        }
+    }
+
+    /// Same as [Modifier::to_validify_tokens], except the change is recorded in the `report`.
+    /// Nested fields merge their own reports.
+    pub fn to_report_tokens(&self, field_info: &FieldInfo) -> proc_macro2::TokenStream {
+        if let Modifier::Nested = self {
+            return Self::nested_report_tokens(field_info);
+        }
+
+        let field = field_info.capture_param_tokens();
+        let location = format!("/{}", field_info.name());
+        let name = self.name();
+        let element_wise = (field_info.is_list() || field_info.is_map()) && self.is_element_wise();
+        let modification = self.to_validify_tokens(field_info);
+
+        quote!(
+            {
+                use ::validify::__capture::{CaptureFallback as _, CaptureSerialize as _};
+                let __before = (&::validify::__capture::Capture(#field)).capture();
+                #modification
+                let __after = (&::validify::__capture::Capture(#field)).capture();
+                report.record(#location, #name, __before, __after, #element_wise);
+            }
+        )
+    }
+
+    /// Merges the report of the nested field, prepending the field's location to the modifications.
+    fn nested_report_tokens(field_info: &FieldInfo) -> proc_macro2::TokenStream {
+        field_info.nested_tokens(true, |param, segments| {
+            let segments = segments.iter().rev();
+            // Values past the depth limit are left as is and reported by validation
+            field_info.wrap_depth_guard(
+                quote!(
+                    let mut rep = ::validify::Modify::modify_with_report(#param);
+                    rep.modifications_mut().iter_mut().for_each(|m| {
+                        #(m.set_location(#segments);)*
+                    });
+                    report.merge(rep);
+                ),
+                quote!(),
+            )
+        })
+    }
+
+    /// Calls the given `Modify` method on the nested field, prepending the field's location to any errors.
+    fn nested_tokens(
+        field_info: &FieldInfo,
+        method: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        field_info.nested_tokens(true, |param, segments| {
+            let segments = segments.iter().rev();
+            field_info.wrap_depth_guard(
+                quote!(
+                    if let Err(mut errs) = ::validify::Modify::#method(#param) {
+                        errs.errors_mut().iter_mut().for_each(|err| {
+                            #(err.set_location(#segments);)*
+                        });
+                        errors.merge(errs);
+                    }
+                ),
+                quote!(),
+            )
+        })
+    }
+
+    /// Returns the call to the string modification function for the given string parameter.
+    pub fn string_tokens(&self, param: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::SnakeCase => quote!(::validify::to_snake_case(#param)),
+            Modifier::KebabCase => quote!(::validify::to_kebab_case(#param)),
+            Modifier::CamelCase => quote!(::validify::to_camel_case(#param)),
+            Modifier::PascalCase => quote!(::validify::to_pascal_case(#param)),
+            Modifier::ScreamingSnakeCase => quote!(::validify::to_screaming_snake_case(#param)),
+            Modifier::Slugify { separator, max_len } => {
+                let max_len = max_len
+                    .map(|len| quote!(Some(#len)))
+                    .unwrap_or(quote!(None));
+                quote!(::validify::slugify(#param, #separator, #max_len))
+            }
+            Modifier::SanitizeHtml { tags, attributes } => {
+                let tags = tags
+                    .as_ref()
+                    .map(|tags| quote!(Some(&[#(#tags),*])))
+                    .unwrap_or(quote!(None));
+                let attributes = attributes
+                    .as_ref()
+                    .map(|attributes| quote!(Some(&[#(#attributes),*])))
+                    .unwrap_or(quote!(None));
+                quote!(::validify::sanitize_html(#param, #tags, #attributes))
+            }
+            Modifier::StripHtml => quote!(::validify::strip_html(#param)),
+            Modifier::EmailCanonical => quote!(::validify::email_canonical(#param)),
+            Modifier::PhoneE164 { default_region } => {
+                let default_region = default_region
+                    .as_ref()
+                    .map(|region| quote!(Some(#region)))
+                    .unwrap_or(quote!(None));
+                quote!(::validify::phone_e164(#param, #default_region))
+            }
+            Modifier::UrlNormalize => quote!(::validify::url_normalize(#param)),
+            _ => unreachable!("not a string modifier"),
+        }
+    }
+
+    /// Returns the call to the numeric or temporal modification function for the given parameter.
+    /// The parameter is always passed by value as all the supported types are `Copy`.
+    pub fn value_tokens(&self, param: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::Clamp { min, max } => {
+                let min = min
+                    .as_ref()
+                    .map(|min| quote!(Some(#min)))
+                    .unwrap_or(quote!(None));
+                let max = max
+                    .as_ref()
+                    .map(|max| quote!(Some(#max)))
+                    .unwrap_or(quote!(None));
+                quote!(::validify::clamp(#param, #min, #max))
+            }
+            Modifier::Round { decimals } => quote!(::validify::round(#param, #decimals)),
+            Modifier::Floor => quote!(::validify::floor(#param)),
+            Modifier::Ceil => quote!(::validify::ceil(#param)),
+            Modifier::Abs => quote!(::validify::abs(#param)),
+            Modifier::TruncateTime { unit } => {
+                let unit = match unit {
+                    TimeUnit::Seconds => quote!(::validify::TimeUnit::Seconds),
+                    TimeUnit::Minutes => quote!(::validify::TimeUnit::Minutes),
+                    TimeUnit::Hours => quote!(::validify::TimeUnit::Hours),
+                    TimeUnit::Days => quote!(::validify::TimeUnit::Days),
                 };
+                quote!(::validify::truncate_time(#param, #unit))
+            }
+            Modifier::ToUtc => quote!(::validify::to_utc(#param)),
+            _ => unreachable!("not a value modifier"),
+        }
+    }
 
-                field_info.wrap_modifier_if_option(modifications)
+    /// Returns the call to the function modifying the whole collection. The parameter is always
+    /// a mutable reference to the collection.
+    pub fn collection_tokens(&self, param: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Modifier::Sort => quote!(::validify::sort(#param)),
+            Modifier::SortByKey { function } => quote!(::validify::sort_by_key(#param, #function)),
+            Modifier::Dedup => quote!(::validify::dedup(#param)),
+            Modifier::RetainNonEmpty => quote!(::validify::retain_non_empty(#param)),
+            Modifier::Truncate {
+                max,
+                unit: None,
+                ellipsis: None,
+            } => quote!(::validify::truncate(#param, (#max) as usize)),
+            Modifier::Truncate {
+                max,
+                unit,
+                ellipsis,
+            } => {
+                let unit = match unit.unwrap_or(TextUnit::Chars) {
+                    TextUnit::Chars => quote!(::validify::TextUnit::Chars),
+                    TextUnit::Bytes => quote!(::validify::TextUnit::Bytes),
+                    TextUnit::Graphemes => quote!(::validify::TextUnit::Graphemes),
+                };
+                let ellipsis = ellipsis
+                    .as_ref()
+                    .map(|ellipsis| quote!(Some(#ellipsis)))
+                    .unwrap_or(quote!(None));
+                quote!(::validify::truncate_string(#param, (#max) as usize, #unit, #ellipsis))
             }
+            _ => unreachable!("not a collection modifier"),
         }
     }
 }